@@ -113,10 +113,23 @@ private:
 	friend PseudoDouble pow(const PseudoDouble x, const PseudoDouble y);
 	friend PseudoDouble sin_rev(const PseudoDouble x);
 	friend PseudoDouble cos_rev(const PseudoDouble x);
+	friend PseudoDouble asin_rev(const PseudoDouble x);
+	friend PseudoDouble acos_rev(const PseudoDouble x);
+	friend PseudoDouble atan_rev(const PseudoDouble x);
 	friend PseudoDouble atan2_rev(const PseudoDouble y, const PseudoDouble x);
 	friend PseudoDouble sin(const PseudoDouble x);
 	friend PseudoDouble cos(const PseudoDouble x);
+	friend PseudoDouble asin(const PseudoDouble x);
+	friend PseudoDouble acos(const PseudoDouble x);
+	friend PseudoDouble atan(const PseudoDouble x);
 	friend PseudoDouble atan2(const PseudoDouble y, const PseudoDouble x);
+#ifdef PSEUDO_DOUBLE_HAS_INT128
+	friend PseudoDouble erf(const PseudoDouble x);
+	friend PseudoDouble erfc(const PseudoDouble x);
+	friend PseudoDouble erf_inv(const PseudoDouble x);
+	friend PseudoDouble normal_cdf(const PseudoDouble x);
+	friend PseudoDouble normal_quantile(const PseudoDouble p);
+#endif
 	friend PseudoDouble abs(const PseudoDouble x);
 	friend PseudoDouble fabs(const PseudoDouble x);
 	friend PseudoDouble PD_create_fixed10(int64_t x, int32_t e);
@@ -142,10 +155,23 @@ inline PseudoDouble log10(const PseudoDouble x) {return PseudoDouble::create(pdi
 inline PseudoDouble pow(const PseudoDouble x, const PseudoDouble y) {return PseudoDouble::create(pdi_pow(x.val,y.val));}
 inline PseudoDouble sin_rev(const PseudoDouble x) {return PseudoDouble::create(pdi_sin_rev(x.val));}
 inline PseudoDouble cos_rev(const PseudoDouble x) {return PseudoDouble::create(pdi_cos_rev(x.val));}
+inline PseudoDouble asin_rev(const PseudoDouble x) {return PseudoDouble::create(pdi_asin_rev(x.val));}
+inline PseudoDouble acos_rev(const PseudoDouble x) {return PseudoDouble::create(pdi_acos_rev(x.val));}
+inline PseudoDouble atan_rev(const PseudoDouble x) {return PseudoDouble::create(pdi_atan_rev(x.val));}
 inline PseudoDouble atan2_rev(const PseudoDouble y, const PseudoDouble x) {return PseudoDouble::create(pdi_atan2_rev(y.val,x.val));}
 inline PseudoDouble sin(const PseudoDouble x) {return PseudoDouble::create(pdi_sin(x.val));}
 inline PseudoDouble cos(const PseudoDouble x) {return PseudoDouble::create(pdi_cos(x.val));}
+inline PseudoDouble asin(const PseudoDouble x) {return PseudoDouble::create(pdi_asin(x.val));}
+inline PseudoDouble acos(const PseudoDouble x) {return PseudoDouble::create(pdi_acos(x.val));}
+inline PseudoDouble atan(const PseudoDouble x) {return PseudoDouble::create(pdi_atan(x.val));}
 inline PseudoDouble atan2(const PseudoDouble y, const PseudoDouble x) {return PseudoDouble::create(pdi_atan2(y.val,x.val));}
+#ifdef PSEUDO_DOUBLE_HAS_INT128
+inline PseudoDouble erf(const PseudoDouble x) {return PseudoDouble::create(pdi_erf(x.val));}
+inline PseudoDouble erfc(const PseudoDouble x) {return PseudoDouble::create(pdi_erfc(x.val));}
+inline PseudoDouble erf_inv(const PseudoDouble x) {return PseudoDouble::create(pdi_erf_inv(x.val));}
+inline PseudoDouble normal_cdf(const PseudoDouble x) {return PseudoDouble::create(pdi_normal_cdf(x.val));}
+inline PseudoDouble normal_quantile(const PseudoDouble p) {return PseudoDouble::create(pdi_normal_quantile(p.val));}
+#endif
 inline PseudoDouble abs(const PseudoDouble x) {return PseudoDouble::create(pdi_abs(x.val));}
 inline PseudoDouble fabs(const PseudoDouble x) {return PseudoDouble::create(pdi_abs(x.val));}
 inline PseudoDouble PD_create_fixed10(int64_t x, int32_t e) {return PseudoDouble::create(int64fixed10_to_pdi(x,e));}
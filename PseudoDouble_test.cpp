@@ -301,6 +301,26 @@ int main() {
 				cout << "cos  " << f << ' ' << cos(f) << ' ' << ff << endl;
 			}
 		}
+		if(-1.0<=f && f<=1.0) {
+			ff=asin(pd);
+			count++;
+			if(!compare(asin(f),ff,NEAR_EXACT13)) {
+				failures++;
+				cout << "asin  " << f << ' ' << asin(f) << ' ' << ff << endl;
+			}
+			ff=acos(pd);
+			count++;
+			if(!compare(acos(f),ff,NEAR_EXACT13)) {
+				failures++;
+				cout << "acos  " << f << ' ' << acos(f) << ' ' << ff << endl;
+			}
+		}
+		ff=atan(pd);
+		count++;
+		if(!compare(atan(f),ff,NEAR_EXACT13)) {
+			failures++;
+			cout << "atan  " << f << ' ' << atan(f) << ' ' << ff << endl;
+		}
 	}
 	for(uint32_t i=0;i<list.size();i++) {
 		double f1=list[i];
@@ -337,6 +357,94 @@ int main() {
 			cout << "uint convert" << i << ' ' << ii << endl;
 		}
 	}
+	// x, asin_rev, acos_rev, atan_rev, asin, acos, atan
+	// the same vectors are checked against the Rust library in tests/integration_test.rs
+	const static uint64_t inverse_trig_vectors[][7]={
+		{0x4CCCCCCCCCCD8000ULL,0x63507A3010B17FFDULL,0x672BE173FBD47FFFULL,0x5F000980497F7FFDULL,0x4E00567853DC8000ULL,0x5107D7B2FBBD8002ULL,0x4A9CE9987ABC8000ULL},
+		{0xA666666666668001ULL,0x81A13E3231257FFEULL,0x5F97B07373B78000ULL,0x9C7796A767067FFEULL,0x9CBFC65B54098001ULL,0x4B140511B3588003ULL,0xB1D3BB4A10AD8001ULL},
+		{0x7FDF3B645A1D8001ULL,0x7C5B0036EDC17FFFULL,0x749FF92247EB7FFAULL,0x7FEB20F9A7DD7FFEULL,0x61AB26B463E48002ULL,0x5B98D3959A157FFDULL,0x647788EA30FF8001ULL},
+		{0x9C35E642BFBD7F9EULL,0x80F1B1CF937A7F9BULL,0x4000000000008000ULL,0x80F1B1CF937A7F9BULL,0x9C35E642BFBD7F9EULL,0x6487ED5110B48002ULL,0x9C35E642BFBD7F9EULL},
+		{0x4000000000008001ULL,0x5555555555557FFEULL,0x5555555555557FFFULL,0x4B90147677CC7FFEULL,0x430548E0B5CE8001ULL,0x430548E0B5CE8002ULL,0x76B19C1586ED8000ULL},
+		{0x8000000000008001ULL,0x8000000000007FFFULL,0x4000000000008001ULL,0x8000000000007FFEULL,0x9B7812AEEF4C8002ULL,0x6487ED5110B48003ULL,0x9B7812AEEF4C8001ULL},
+		{0x4000000000000000ULL,0x0000000000000000ULL,0x4000000000008000ULL,0x0000000000000000ULL,0x4000000000000000ULL,0x6487ED5110B48002ULL,0x4000000000000000ULL},
+		{0x7FFFFFFFFE808001ULL,0x7FFFF3865CA67FFFULL,0x63CD1AD30C307FECULL,0x7FFFFFFFFF0C7FFEULL,0x6487E384C9AA8002ULL,0x4E62385024987FEFULL,0x6487ED510FF48001ULL},
+	};
+	for(uint32_t i=0;i<sizeof(inverse_trig_vectors)/sizeof(inverse_trig_vectors[0]);i++) {
+		pseudo_double_i x=inverse_trig_vectors[i][0];
+		pseudo_double_i r[6]={pdi_asin_rev(x),pdi_acos_rev(x),pdi_atan_rev(x),pdi_asin(x),pdi_acos(x),pdi_atan(x)};
+		for(uint32_t j=0;j<6;j++) {
+			count++;
+			if(r[j]!=inverse_trig_vectors[i][j+1]) {
+				failures++;
+				cout << "inverse trig vector " << i << ',' << j << std::hex << ' ' << r[j] << "!=" << inverse_trig_vectors[i][j+1] << std::dec << endl;
+			}
+		}
+	}
+	// y, x, atan2_rev, atan2
+	const static uint64_t atan2_vectors[][4]={
+		{0x6000000000008003ULL,0x8000000000008003ULL,0x65C80A3B3BE68000ULL,0x4FF05DADEA158003ULL},
+		{0x920C8098A1097FE0ULL,0x8000000000008002ULL,0x8000000008C08000ULL,0x9B7812AEF62B8003ULL},
+		{0x5000000000008004ULL,0x5E72843249087FBFULL,0x4000000000008000ULL,0x6487ED5110B48002ULL},
+		{0x9000000000008004ULL,0x7000000000008004ULL,0x8000000000007FFEULL,0x9B7812AEEF4C8001ULL},
+		{0x830000000000800BULL,0x4189374BC6A87FF8ULL,0x80000557216C7FFFULL,0x9B7816E0AD348002ULL},
+		{0x4000000000008000ULL,0xA000000000008000ULL,0x680AE90E442F8000ULL,0x51B7027B0A518003ULL},
+	};
+	for(uint32_t i=0;i<sizeof(atan2_vectors)/sizeof(atan2_vectors[0]);i++) {
+		pseudo_double_i r[2]={pdi_atan2_rev(atan2_vectors[i][0],atan2_vectors[i][1]),pdi_atan2(atan2_vectors[i][0],atan2_vectors[i][1])};
+		for(uint32_t j=0;j<2;j++) {
+			count++;
+			if(r[j]!=atan2_vectors[i][j+2]) {
+				failures++;
+				cout << "atan2 vector " << i << ',' << j << std::hex << ' ' << r[j] << "!=" << atan2_vectors[i][j+2] << std::dec << endl;
+			}
+		}
+	}
+#ifdef PSEUDO_DOUBLE_HAS_INT128
+	// x, erf, erfc, normal_cdf
+	const static uint64_t erf_vectors[][4]={
+		{0x4CCCCCCCCCCD8000ULL,0x5420E22077A88000ULL,0x55EF8EEFC42C8001ULL,0x4F17B8B3102E8001ULL},
+		{0xA666666666668001ULL,0xA93DCF7BA3A78001ULL,0x6B6118422E2D8002ULL,0x7BE2A8EA3CD57FFFULL},
+		{0x5000000000008003ULL,0x7FF2AA3D32708001ULL,0x6AAE166C82B27FF6ULL,0x7F34858D40DB8001ULL},
+		{0xB000000000008004ULL,0x8000000000D88001ULL,0x7FFFFFFFFF948002ULL,0x4CF28BC84CE17FECULL},
+		{0x6C00000000008006ULL,0x4000000000008002ULL,0x6783C337E0EA7BE0ULL,0x4000000000008002ULL},
+		{0x5E72843249087FBFULL,0x6A9288C0912B7FBFULL,0x4000000000008002ULL,0x4000000000008001ULL},
+		{0xB50000000000800AULL,0x8000000000008001ULL,0x4000000000008003ULL,0x0000000000000000ULL},
+		{0x4666666666668001ULL,0x481AFAE5B8308001ULL,0x6FCA0A348FA08000ULL,0x5ABB4785AA4C8001ULL},
+	};
+	for(uint32_t i=0;i<sizeof(erf_vectors)/sizeof(erf_vectors[0]);i++) {
+		pseudo_double_i x=erf_vectors[i][0];
+		pseudo_double_i r[3]={pdi_erf(x),pdi_erfc(x),pdi_normal_cdf(x)};
+		for(uint32_t j=0;j<3;j++) {
+			count++;
+			if(r[j]!=erf_vectors[i][j+1]) {
+				failures++;
+				cout << "erf vector " << i << ',' << j << std::hex << ' ' << r[j] << "!=" << erf_vectors[i][j+1] << std::dec << endl;
+			}
+		}
+	}
+	// p, erf_inv, normal_quantile
+	const static uint64_t erf_inv_vectors[][3]={
+		{0x4CCCCCCCCCCD8000ULL,0x45C01DD1217B8000ULL,0xBCE071A9F4E88001ULL},
+		{0x4CCCCCCCCCCD8001ULL,0x4C2CC38593618001ULL,0x40DB5B12812E8000ULL},
+		{0x7333333333338001ULL,0x4A700519DC198002ULL,0x5204F0DB89B48002ULL},
+		{0x7FFFF79C84308001ULL,0x6EAF65923D0B8003ULL,0x4C0E06A6098E8004ULL},
+		{0x51EB851EB8527FFBULL,0x489A032532B77FFBULL,0xB58E8EE73C048003ULL},
+		{0x465E6604B7A87FDAULL,0x7CB9B0A47CC37FD9ULL,0x8F72C116A1738004ULL},
+		{0x55B87F0BE3CD7C1DULL,0x4BF7CDAFDA6E7C1DULL,0xB5E7E2FE6CF68007ULL},
+		{0x6000000000008001ULL,0x681E243DACAF8001ULL,0x5655AE1D4A298001ULL},
+	};
+	for(uint32_t i=0;i<sizeof(erf_inv_vectors)/sizeof(erf_inv_vectors[0]);i++) {
+		pseudo_double_i p=erf_inv_vectors[i][0];
+		pseudo_double_i r[2]={pdi_erf_inv(p),pdi_normal_quantile(p)};
+		for(uint32_t j=0;j<2;j++) {
+			count++;
+			if(r[j]!=erf_inv_vectors[i][j+1]) {
+				failures++;
+				cout << "erf_inv vector " << i << ',' << j << std::hex << ' ' << r[j] << "!=" << erf_inv_vectors[i][j+1] << std::dec << endl;
+			}
+		}
+	}
+#endif
 	cout << "Tests done, passed " << (count-failures) << '/' << count << endl;
 
 
@@ -736,106 +736,725 @@ uint64_t atan_rev_64_fixed(uint64_t x) {
     return mults64hi(u,x)<<2;
 }
 
-pseudo_double_i pdi_atan2_rev(pseudo_double_i y, pseudo_double_i x) {
-	int negative=0; // boolean
-	uint64_t add_const;
+// chebyfit of atan(x)*4/(pi*x) as a polynomial in x^2 over x in [0,1/2]
+// atan_rev_64_fixed is minimax in absolute error over [0,1], which is not enough relative precision near 0
+
+// x is a 2.62 unsigned fixed in the range [0,1/2]
+// calculate atan(x)*4/(pi*x)
+// result is 2.62 fixed in the range [1.18,4/pi]
+int64_t atan_rev_ratio_64_fixed(uint64_t x) {
+	int64_t x2=mults64hi(x,x)<<2;
+	int64_t u=                  40388472590229660LL;
+	u=(mults64hi(u,x2)<<2)    -119451630339286274LL;
+	u=(mults64hi(u,x2)<<2)    +193529897998212425LL;
+	u=(mults64hi(u,x2)<<2)    -242918260579836743LL;
+	u=(mults64hi(u,x2)<<2)    +276933265933681911LL;
+	u=(mults64hi(u,x2)<<2)    -308619541776180293LL;
+	u=(mults64hi(u,x2)<<2)    +345350417265416808LL;
+	u=(mults64hi(u,x2)<<2)    -391448034899518093LL;
+	u=(mults64hi(u,x2)<<2)    +451675223703085032LL;
+	u=(mults64hi(u,x2)<<2)    -533798263624760681LL;
+	u=(mults64hi(u,x2)<<2)    +652420111582013399LL;
+	u=(mults64hi(u,x2)<<2)    -838825858076414360LL;
+	u=(mults64hi(u,x2)<<2)   +1174356201312765387LL;
+	u=(mults64hi(u,x2)<<2)   -1957260335521334034LL;
+	u=(mults64hi(u,x2)<<2)+(5871781006564002453LL+2);
+	return u;
+}
+
+// chebyfit of asin(x)*4/(pi*x) as a polynomial in x^2 over x in [0,1/2]
+
+// x is a 2.62 unsigned fixed in the range [0,1/2]
+// calculate asin(x)*4/(pi*x)
+// result is 2.62 fixed in the range [4/pi,4/3]
+int64_t asin_rev_ratio_64_fixed(uint64_t x) {
+	int64_t x2=mults64hi(x,x)<<2;
+	int64_t u=                 204687944468886641LL;
+	u=(mults64hi(u,x2)<<2)    -185796818239096680LL;
+	u=(mults64hi(u,x2)<<2)    +169436626661961889LL;
+	u=(mults64hi(u,x2)<<2)     -15919253184275463LL;
+	u=(mults64hi(u,x2)<<2)     +57520733836525906LL;
+	u=(mults64hi(u,x2)<<2)     +46449826291178632LL;
+	u=(mults64hi(u,x2)<<2)     +57712527250989742LL;
+	u=(mults64hi(u,x2)<<2)     +67789391129176525LL;
+	u=(mults64hi(u,x2)<<2)     +82001476168680166LL;
+	u=(mults64hi(u,x2)<<2)    +101891476645809060LL;
+	u=(mults64hi(u,x2)<<2)    +131364424456699808LL;
+	u=(mults64hi(u,x2)<<2)    +178396124198268983LL;
+	u=(mults64hi(u,x2)<<2)    +262133080652076499LL;
+	u=(mults64hi(u,x2)<<2)    +440383575492286004LL;
+	u=(mults64hi(u,x2)<<2)    +978630167760667117LL;
+	u=(mults64hi(u,x2)<<2)+(5871781006564002453LL+2);
+	return u;
+}
+
+// split a nonzero pseudo_double_i into sign and magnitude sig*2^exp2, with sig in [2^62,2^63)
+static uint64_t pdi_to_sig62(pseudo_double_i x, int32_t* exp2, int* negative) {
+	signed_pd_internal vx=(signed_pd_internal)(x&EXP_MASK_INV);
+	*negative=(vx<0);
+	uint64_t mag=*negative?-(uint64_t)vx:(uint64_t)vx;
+	int32_t exponent=(int32_t)(x&EXP_MASK)-(int32_t)PSEUDO_DOUBLE_EXP_BIAS-PSEUDO_DOUBLE_TOTAL_BITS;
+	if(mag>>63) {
+		*exp2=exponent+1;
+		return mag>>1;
+	}
+	int32_t lz=clz(mag)-1;
+	*exp2=exponent-lz;
+	return mag<<lz;
+}
+
+// round q*2^exp2 to nearest even, sticky is set if there are nonzero bits below q
+// *status is set to 1 on overflow and -1 on underflow, with a result of 0
+static pseudo_double_i round_u64_to_pdi(uint64_t q, int64_t exp2, int sticky, int negative, int* status) {
+	*status=0;
+	if(q==0) {
+		return 0;
+	}
+	// round to the bits of the mantissa, nearest even
+	int32_t drop=64-clz(q)-(PSEUDO_DOUBLE_TOTAL_BITS-PSEUDO_DOUBLE_EXP_BITS-1);
+	uint64_t sig;
+	if(drop<=0) {
+		sig=q<<-drop;
+	} else {
+		sig=q>>drop;
+		uint64_t rem=q&((1ULL<<drop)-1);
+		uint64_t half=1ULL<<(drop-1);
+		if(rem>half || (rem==half && (sticky || (sig&1)!=0))) {
+			sig++;
+			if(sig==(1ULL<<(PSEUDO_DOUBLE_TOTAL_BITS-PSEUDO_DOUBLE_EXP_BITS-1))) {
+				sig>>=1;
+				drop++;
+			}
+		}
+	}
+	int64_t new_exponent=(int64_t)PSEUDO_DOUBLE_EXP_BIAS+PSEUDO_DOUBLE_TOTAL_BITS-PSEUDO_DOUBLE_EXP_BITS+exp2+drop;
+	if(negative && sig==(1ULL<<(PSEUDO_DOUBLE_TOTAL_BITS-PSEUDO_DOUBLE_EXP_BITS-2))) {
+		// a negative power of 2 has the most negative mantissa and one less exponent
+		sig<<=1;
+		new_exponent--;
+	}
+	if(new_exponent>EXP_MASK) {
+		*status=1;
+		return 0;
+	}
+	if(new_exponent<0) {
+		*status=-1;
+		return 0;
+	}
+	pseudo_double_i mantissa=sig<<PSEUDO_DOUBLE_EXP_BITS;
+	return (negative?-mantissa:mantissa)+new_exponent;
+}
+
+// round an inverse trig result of q*2^exp2 revolutions, converting to radians first if asked so
+// that there is a single rounding. Results too small to represent underflow to zero
+static pseudo_double_i rev_fixed_to_pdi(uint64_t q, int32_t exp2, int negative, int radians) {
+	if(q==0) {
+		return 0;
+	}
+	int32_t lz=clz(q);
+	q<<=lz;
+	exp2-=lz;
+	if(radians) {
+		q=multu64hi(q,0xC90FDAA22168C235ULL); // tau as a 3.61 unsigned fixed
+		exp2+=3;
+	}
+	int status;
+	pseudo_double_i r=round_u64_to_pdi(q,exp2,0,negative,&status);
+#if PD_ERROR_CHECK
+	if(status<0) {
+		PD_DO_ERROR_UNDERFLOW;
+	}
+#endif
+	return r;
+}
+
+// floor(sqrt(x))
+static uint64_t isqrt64(uint64_t x) {
+	uint64_t r=0;
+	uint64_t bit=1ULL<<62;
+	while(bit!=0) {
+		if(x>=r+bit) {
+			x-=r+bit;
+			r=(r>>1)+bit;
+		} else {
+			r>>=1;
+		}
+		bit>>=2;
+	}
+	return r;
+}
+
+// asin_rev(x) for |x|=sig*2^exp2<1/2 as m*2^(exp2-3), keeping the full precision of x
+static uint64_t asin_rev_small_fixed(uint64_t sig, int32_t exp2) {
+	int32_t shift=-62-exp2;
+	uint64_t xf=(shift>63)?0:(sig>>shift);
+	return multu64hi(sig<<1,((uint64_t)asin_rev_ratio_64_fixed(xf))<<1);
+}
+
+// 2*asin_rev(sqrt(d*2^-64)) for 0<d<=2^62 as m*2^(-64-k)
+// the square root is taken in fixed point so no rounding is added before the kernel
+static uint64_t two_asin_rev_sqrt_fixed(uint64_t d, int32_t* k) {
+	int32_t sh=clz(d)&~1;
+	uint64_t dn=d<<sh;
+	// one Newton-Raphson step from the integer square root, sqrt(d)*2^(31+sh/2)
+	uint64_t s0=isqrt64(dn);
+	uint64_t s=(s0<<31)+((dn-s0*s0)<<30)/s0;
+	uint64_t u=asin_rev_ratio_64_fixed(s>>(1+sh/2));
+	*k=sh/2;
+	return multu64hi(s,u<<1);
+}
+
+// 1-|x| for 1/2<=|x|=sig*2^exp2<=1 as a 1.63 unsigned fixed, which is exact
+static uint64_t one_minus_sig62(uint64_t sig, int32_t exp2) {
+	return (1ULL<<63)-((exp2==-63)?sig:(sig<<1));
+}
+
+// asin_rev(x), or asin(x) if radians is set
+static pseudo_double_i pdi_asin_scaled(pseudo_double_i x, int radians) {
+#if PD_ERROR_CHECK
+	if(pdi_gt(x,uint64_to_pdi(1)) || pdi_gt(int64_to_pdi(-1),x)) {
+		PD_DO_ERROR_RANGE;
+	}
+#endif
+	if(x==0) {
+		return 0;
+	}
+	int32_t exp2;
+	int negative;
+	uint64_t sig=pdi_to_sig62(x,&exp2,&negative);
+	if(exp2<-63) {
+		// |x|<1/2
+		return rev_fixed_to_pdi(asin_rev_small_fixed(sig,exp2),exp2-3,negative,radians);
+	}
+	// asin(x)=pi/2-2*asin(sqrt((1-x)/2))
+	uint64_t d=one_minus_sig62(sig,exp2);
+	if(d==0) {
+		return rev_fixed_to_pdi(1ULL<<62,-64,negative,radians);
+	}
+	int32_t k;
+	uint64_t m=two_asin_rev_sqrt_fixed(d,&k);
+	return rev_fixed_to_pdi((1ULL<<62)-(m>>k),-64,negative,radians);
+}
+
+// acos_rev(x), or acos(x) if radians is set
+static pseudo_double_i pdi_acos_scaled(pseudo_double_i x, int radians) {
+#if PD_ERROR_CHECK
+	if(pdi_gt(x,uint64_to_pdi(1)) || pdi_gt(int64_to_pdi(-1),x)) {
+		PD_DO_ERROR_RANGE;
+	}
+#endif
+	if(x==0) {
+		return rev_fixed_to_pdi(1ULL<<62,-64,0,radians);
+	}
+	int32_t exp2;
+	int negative;
+	uint64_t sig=pdi_to_sig62(x,&exp2,&negative);
+	if(exp2<-63) {
+		// |x|<1/2, acos(x)=pi/2-asin(x)
+		int32_t shift=-61-exp2;
+		uint64_t a=(shift>63)?0:(asin_rev_small_fixed(sig,exp2)>>shift);
+		return rev_fixed_to_pdi(negative?(1ULL<<62)+a:(1ULL<<62)-a,-64,0,radians);
+	}
+	// acos(x)=2*asin(sqrt((1-x)/2)), acos(-x)=pi-acos(x)
+	uint64_t d=one_minus_sig62(sig,exp2);
+	if(d==0) {
+		return rev_fixed_to_pdi(negative?(1ULL<<63):0,-64,0,radians);
+	}
+	int32_t k;
+	uint64_t m=two_asin_rev_sqrt_fixed(d,&k);
+	if(negative) {
+		return rev_fixed_to_pdi((1ULL<<63)-(m>>k),-64,0,radians);
+	}
+	return rev_fixed_to_pdi(m,-64-k,0,radians);
+}
+
+// atan2_rev(y,x), or atan2(y,x) if radians is set
+static pseudo_double_i pdi_atan2_scaled(pseudo_double_i y, pseudo_double_i x, int radians) {
 	if(y==0) {
-		if(((signed_pd_internal)x)>=0) {
-			return uint64_to_pdi(0);
+		// 0 or 1/2
+		return rev_fixed_to_pdi((((signed_pd_internal)x)>=0)?0:(1ULL<<63),-64,0,radians);
+	}
+	if(x==0) {
+		// 1/4 or 3/4
+		return rev_fixed_to_pdi((((signed_pd_internal)y)>0)?(1ULL<<62):(3ULL<<62),-64,0,radians);
+	}
+	int32_t ye,xe;
+	int yneg,xneg;
+	uint64_t ys=pdi_to_sig62(y,&ye,&yneg);
+	uint64_t xs=pdi_to_sig62(x,&xe,&xneg);
+	// reduce to a/b with a<=b, the result is then +-(base+atan_rev(a/b)) or +-(base-atan_rev(a/b))
+	int swap=(ye>xe || (ye==xe && ys>xs));
+	uint64_t a=swap?xs:ys;
+	int32_t ae=swap?xe:ye;
+	uint64_t b=swap?ys:xs;
+	int32_t be=swap?ye:xe;
+	uint64_t base=swap?(1ULL<<62):(xneg?(1ULL<<63):0);
+	int subtract=swap?!xneg:xneg;
+	// a/b in 2.62 fixed is rq>>k
+	uint64_t rq=divs64hi(a>>2,b);
+	int32_t k=be-ae;
+	uint64_t r;
+	int32_t exp2=-64;
+	if(k>=2 || (k==1 && rq<=(1ULL<<62))) {
+		// a/b<=1/2, atan(t)=t*ratio(t^2) as m*2^(-65-k)
+		uint64_t tf=(k>63)?0:(rq>>k);
+		uint64_t m=multu64hi(rq<<1,((uint64_t)atan_rev_ratio_64_fixed(tf))<<1);
+		if(base==0) {
+			r=m;
+			exp2=-65-k;
 		} else {
-			return int64fixed2_to_pdi(1,-1); // 1/2
+			uint64_t v=(k>62)?0:(m>>(k+1));
+			r=subtract?base-v:base+v;
 		}
-	} else if(((signed_pd_internal)y)>0) {
-		if(x==0) {
-			return int64fixed2_to_pdi(1,-2); // 1/4
-		} else if (((signed_pd_internal)x)>0) {
-			if(pdi_gte(x,y)) {
-				// q1
-				add_const=0;
-			} else {
-				// q2
-				{ pseudo_double_i t=y; y=x; x=t;}
-				add_const=0x4000000000000000ULL;
-				negative=1; // boolean
-			}
-		} else { // x<0
-			x=pdi_neg(x);
-			if(pdi_gte(x,y)) {
-				// q4
-				add_const=0x8000000000000000ULL;
-				negative=1; // boolean
-			} else {
-				// q3
-				{ pseudo_double_i t=y; y=x; x=t;}
-				add_const=0x4000000000000000ULL;
-			}
+	} else {
+		// 1/2<a/b<=1, atan(t)=pi/4-atan((1-t)/(1+t)) with the ratio w in 0.64 fixed
+		uint64_t t=rq>>k;
+		uint64_t w=(t==(1ULL<<62))?0:divs64hi((1ULL<<62)-t,t+(1ULL<<62));
+		uint64_t u=(1ULL<<61)-(multu64hi(w,atan_rev_ratio_64_fixed(w>>2))>>1);
+		r=subtract?base-u:base+u;
+	}
+	return rev_fixed_to_pdi(r,exp2,yneg,radians);
+}
+
+pseudo_double_i pdi_asin_rev(pseudo_double_i x) {
+	return pdi_asin_scaled(x,0);
+}
+
+pseudo_double_i pdi_acos_rev(pseudo_double_i x) {
+	return pdi_acos_scaled(x,0);
+}
+
+pseudo_double_i pdi_atan_rev(pseudo_double_i x) {
+	return pdi_atan2_scaled(x,uint64_to_pdi(1),0);
+}
+
+pseudo_double_i pdi_atan2_rev(pseudo_double_i y, pseudo_double_i x) {
+	return pdi_atan2_scaled(y,x,0);
+}
+
+pseudo_double_i pdi_asin(pseudo_double_i x) {
+	return pdi_asin_scaled(x,1);
+}
+
+pseudo_double_i pdi_acos(pseudo_double_i x) {
+	return pdi_acos_scaled(x,1);
+}
+
+pseudo_double_i pdi_atan(pseudo_double_i x) {
+	return pdi_atan2_scaled(x,uint64_to_pdi(1),1);
+}
+
+pseudo_double_i pdi_atan2(pseudo_double_i y, pseudo_double_i x) {
+	return pdi_atan2_scaled(y,x,1);
+}
+
+// Taylor series (e^x-1)/x = sum x^k/(k+1)!, the last term used is below 2^-70 on the range
+// x is a 1.63 signed fixed in the range [-0.5,0.5]
+// calculate (e^x-1)/x
+// result is 2.62 unsigned fixed in the range (0.78,1.3)
+uint64_t expm1_ratio_64_fixed(int64_t x) {
+	int64_t u=                             720LL;
+	u=(mults64hi(u,x)<<1)                +12966LL;
+	u=(mults64hi(u,x)<<1)               +220414LL;
+	u=(mults64hi(u,x)<<1)              +3526632LL;
+	u=(mults64hi(u,x)<<1)             +52899477LL;
+	u=(mults64hi(u,x)<<1)            +740592679LL;
+	u=(mults64hi(u,x)<<1)           +9627704831LL;
+	u=(mults64hi(u,x)<<1)         +115532457973LL;
+	u=(mults64hi(u,x)<<1)        +1270857037706LL;
+	u=(mults64hi(u,x)<<1)       +12708570377060LL;
+	u=(mults64hi(u,x)<<1)      +114377133393536LL;
+	u=(mults64hi(u,x)<<1)      +915017067148291LL;
+	u=(mults64hi(u,x)<<1)     +6405119470038039LL;
+	u=(mults64hi(u,x)<<1)    +38430716820228233LL;
+	u=(mults64hi(u,x)<<1)   +192153584101141163LL;
+	u=(mults64hi(u,x)<<1)   +768614336404564651LL;
+	u=(mults64hi(u,x)<<1)  +2305843009213693952LL;
+	u=(mults64hi(u,x)<<1)  +4611686018427387904LL;
+	return u;
+}
+
+// chebyfit of erf(x)/x as a polynomial in x^2 over x in [0,1/2]
+
+// x is a 2.62 unsigned fixed in the range [0,1/2]
+// calculate erf(x)/x
+// result is 2.62 unsigned fixed in the range [1.04,2/sqrt(pi)]
+uint64_t erf_ratio_64_fixed(uint64_t x) {
+	int64_t x2=mults64hi(x,x)<<2;
+	int64_t u=                       -674270619470LL;
+	u=(mults64hi(u,x2)<<2)         +7549899290572LL;
+	u=(mults64hi(u,x2)<<2)        -68820205985852LL;
+	u=(mults64hi(u,x2)<<2)       +555951948259304LL;
+	u=(mults64hi(u,x2)<<2)      -3942219785046172LL;
+	u=(mults64hi(u,x2)<<2)     +24091344560033059LL;
+	u=(mults64hi(u,x2)<<2)    -123898343532229526LL;
+	u=(mults64hi(u,x2)<<2)    +520373042837899826LL;
+	u=(mults64hi(u,x2)<<2)   -1734576809459705449LL;
+	u=(mults64hi(u,x2)<<2)+(5203730428379116615LL+1);
+	return u;
+}
+
+// chebyfit of erfcx(y)*(1+2*y), with erfcx(y)=e^(y^2)*erfc(y), as a polynomial in t=(y-4)/(y+4)
+// over y in [1/4,inf). The function tends to 2/sqrt(pi) so one polynomial covers the half line.
+
+// t is a 1.63 signed fixed in the range [-15/17,1)
+// calculate erfcx(y)*(1+2*y)
+// result is 2.62 unsigned fixed in the range [1.12,1.29]
+uint64_t erfcx_64_fixed(int64_t t) {
+	int64_t u=                          360423075LL;
+	u=(mults64hi(u,t)<<1)             -838154950LL;
+	u=(mults64hi(u,t)<<1)            -3353432198LL;
+	u=(mults64hi(u,t)<<1)            +9326314630LL;
+	u=(mults64hi(u,t)<<1)           +20240605355LL;
+	u=(mults64hi(u,t)<<1)           -70311563182LL;
+	u=(mults64hi(u,t)<<1)          -103579810425LL;
+	u=(mults64hi(u,t)<<1)          +511061152291LL;
+	u=(mults64hi(u,t)<<1)          +366164191223LL;
+	u=(mults64hi(u,t)<<1)         -3807141292336LL;
+	u=(mults64hi(u,t)<<1)         +1340332423400LL;
+	u=(mults64hi(u,t)<<1)        +26330725311845LL;
+	u=(mults64hi(u,t)<<1)        -51746103956183LL;
+	u=(mults64hi(u,t)<<1)       -112520923268901LL;
+	u=(mults64hi(u,t)<<1)       +694618046531138LL;
+	u=(mults64hi(u,t)<<1)       -918909514344204LL;
+	u=(mults64hi(u,t)<<1)      -3494612138197377LL;
+	u=(mults64hi(u,t)<<1)     +23205865630450866LL;
+	u=(mults64hi(u,t)<<1)     -74698863724826841LL;
+	u=(mults64hi(u,t)<<1)    +171404911739933498LL;
+	u=(mults64hi(u,t)<<1)    -305894820610575561LL;
+	u=(mults64hi(u,t)<<1)    +432266404629666298LL;
+	u=(mults64hi(u,t)<<1)    -465960448154687733LL;
+	u=(mults64hi(u,t)<<1)    +314042233002452322LL;
+	u=(mults64hi(u,t)<<1)     +70926126569930300LL;
+	u=(mults64hi(u,t)<<1)    -643888752410838821LL;
+	u=(mults64hi(u,t)<<1)+(5686186349354679083LL+15);
+	return u;
+}
+
+#ifdef PSEUDO_DOUBLE_HAS_INT128
+// The error functions below keep their intermediate values in 128 bit fixed point
+
+// ln(2)*2^100
+#define LN_2_FIXED_100 ((((unsigned_large_pd_internal)0xB17217F7DULL)<<64)|0x1CF79ABC9E3B3980ULL)
+
+// 1/sqrt(2) as a 0.64 unsigned fixed
+#define FRAC_1_SQRT_2_FIXED_64 13043817825332782212ULL
+
+// pseudo_double_i constants, correctly rounded
+#define PDI_PI                0x6487ED5110B48003ULL
+#define PDI_SQRT_2            0x5A827999FCEF8002ULL
+#define PDI_FRAC_2_SQRT_PI    0x48375D410A6E8002ULL
+#define PDI_FRAC_1_SQRT_PI_2  0x662114CF50D98001ULL // 1/sqrt(2*pi)*2
+#define PDI_ONE_THIRD         0x5555555530AE8000ULL // 0.3333333333
+#define PDI_SEVEN_THIRTIETHS  0x777777772E2A7FFFULL // 0.2333333333
+#define PDI_THREE_QUARTERS    0x6000000000008001ULL
+
+static int32_t clz128(unsigned_large_pd_internal u) {
+	uint64_t hi=(uint64_t)(u>>64);
+	if(hi!=0) {
+		return clz(hi);
+	}
+	uint64_t lo=(uint64_t)u;
+	return (lo==0)?128:64+clz(lo);
+}
+
+// split a nonzero pseudo_double_i into sign and magnitude sig*2^exp2, with sig in [2^46,2^47)
+static uint64_t pdi_to_sig47(pseudo_double_i x, int32_t* exp2, int* negative) {
+	uint64_t sig=pdi_to_sig62(x,exp2,negative);
+	*exp2+=16;
+	return sig>>16;
+}
+
+// u*2^shift truncated, zero when shifted out
+static unsigned_large_pd_internal shift_u128(unsigned_large_pd_internal u, int64_t shift) {
+	if(shift>=0) {
+		return u<<shift;
+	}
+	return (shift>-128)?(u>>-shift):0;
+}
+
+// u*2^exp2 rounded to nearest, underflowing to zero
+static pseudo_double_i u128_scaled_to_pdi(unsigned_large_pd_internal u, int64_t exp2, int negative) {
+	int32_t shift=((u>>64)!=0)?64-clz((uint64_t)(u>>64)):0;
+	int sticky=(shift>0 && (u&((((unsigned_large_pd_internal)1)<<shift)-1))!=0);
+	int status;
+	pseudo_double_i r=round_u64_to_pdi((uint64_t)(u>>shift),exp2+shift,sticky,negative,&status);
+#if PD_ERROR_CHECK
+	if(status>0) {
+		PD_DO_ERROR_OVERFLOW;
+	}
+	if(status<0) {
+		PD_DO_ERROR_UNDERFLOW;
+	}
+#endif
+	return r;
+}
+
+// n/d*2^exp2 rounded to nearest, n and d nonzero
+static pseudo_double_i ratio_u128_to_pdi(unsigned_large_pd_internal n, unsigned_large_pd_internal d, int64_t exp2, int negative) {
+	int32_t ln=clz128(n);
+	int32_t ld=clz128(d);
+	unsigned_large_pd_internal nn=(n<<ln)>>2;
+	unsigned_large_pd_internal dd=(uint64_t)((d<<ld)>>64);
+	unsigned_large_pd_internal q=nn/dd;
+	int sticky=(nn%dd!=0 || (uint64_t)(d<<ld)!=0);
+	int status;
+	pseudo_double_i r=round_u64_to_pdi((uint64_t)q,exp2+2-ln-64+ld,sticky,negative,&status);
+#if PD_ERROR_CHECK
+	if(status>0) {
+		PD_DO_ERROR_OVERFLOW;
+	}
+	if(status<0) {
+		PD_DO_ERROR_UNDERFLOW;
+	}
+#endif
+	return r;
+}
+
+// e^a or e^-a for a=x*2^-100 with x<2^116, as m*2^(k-125) with m in [2^124.5,2^125.5]
+// a=k*ln(2)+r with ln(2) to 100 bits, so the reduction stays exact enough over the whole range
+static unsigned_large_pd_internal exp_fixed_100(unsigned_large_pd_internal x, int negative, int64_t* k) {
+	unsigned_large_pd_internal kk=(x+LN_2_FIXED_100/2)/LN_2_FIXED_100;
+	int64_t r=(int64_t)(((signed_large_pd_internal)x-(signed_large_pd_internal)(kk*LN_2_FIXED_100))>>37); // 1.63 fixed in [-ln(2)/2,ln(2)/2]
+	if(negative) {
+		r=-r;
+	}
+	*k=negative?-(int64_t)kk:(int64_t)kk;
+	return (unsigned_large_pd_internal)((((signed_large_pd_internal)1)<<125)+(signed_large_pd_internal)r*(signed_large_pd_internal)expm1_ratio_64_fixed(r));
+}
+
+// y*2^64 for y=x, or y=x/sqrt(2) if normal is set, where x=sig*2^exp2<2^8
+static unsigned_large_pd_internal erf_arg_fixed(uint64_t sig, int32_t exp2, int normal) {
+	unsigned_large_pd_internal p=normal?((unsigned_large_pd_internal)sig)*FRAC_1_SQRT_2_FIXED_64:((unsigned_large_pd_internal)sig)<<64;
+	return (exp2<=-128)?0:(p>>-exp2);
+}
+
+// erfcx(y)=g/d*4 for y*2^64 in [2^62,2^72), with d returned in *d
+static uint64_t erfcx_fixed(unsigned_large_pd_internal yf, unsigned_large_pd_internal* d) {
+	signed_large_pd_internal n=(signed_large_pd_internal)yf-(((signed_large_pd_internal)4)<<64);
+	unsigned_large_pd_internal dd=yf+(((unsigned_large_pd_internal)4)<<64);
+	int32_t shift=64-clz128(dd); // dd>>shift is in [2^63,2^64)
+	int64_t t=(int64_t)(((n>>shift)<<63)/(signed_large_pd_internal)(dd>>shift));
+	*d=(((unsigned_large_pd_internal)1)<<64)+2*yf;
+	return erfcx_64_fixed(t);
+}
+
+// erf(y)=v*2^e for y<1/2, with y*2^64 from erf_arg_fixed of x=sig*2^exp2
+// v keeps the full precision of x
+static unsigned_large_pd_internal erf_small_fixed(uint64_t sig, int32_t exp2, unsigned_large_pd_internal yf, int normal, int64_t* e) {
+	uint64_t u=erf_ratio_64_fixed((uint64_t)(yf>>2));
+	if(normal) {
+		u=multu64hi(u,FRAC_1_SQRT_2_FIXED_64);
+	}
+	*e=(int64_t)exp2-62;
+	return ((unsigned_large_pd_internal)sig)*u;
+}
+
+// erfc(y)=n/d*2^e for 1/4<=y<2^8, with y*2^64 from erf_arg_fixed of x=sig*2^exp2
+// y^2 is exact so e^(-y^2) keeps its relative precision all the way to underflow
+static unsigned_large_pd_internal erfc_large_fixed(uint64_t sig, int32_t exp2, unsigned_large_pd_internal yf, int normal, unsigned_large_pd_internal* d, int64_t* e) {
+	unsigned_large_pd_internal y2=(((unsigned_large_pd_internal)sig)*sig)<<(2*exp2+(normal?99:100)); // y^2*2^100
+	int64_t k;
+	unsigned_large_pd_internal m=exp_fixed_100(y2,1,&k);
+	uint64_t g=erfcx_fixed(yf,d);
+	*e=k-61;
+	return (m>>62)*g;
+}
+
+// erf(y), or erfc(y) if complement is set, for y=+-|x|, or y=+-|x|/sqrt(2) with the result halved if normal is set
+// erf and erfc near -1 and 2 are taken from the fixed values so there is only one rounding
+static pseudo_double_i pdi_erf_erfc(pseudo_double_i x, int negative, int complement, int normal) {
+	int h=normal?1:0;
+	if(x==0) {
+		return complement?pdi_ldexp(uint64_to_pdi(1),-h):0;
+	}
+	int32_t exp2;
+	int xneg;
+	uint64_t sig=pdi_to_sig47(x,&exp2,&xneg);
+	if(exp2>-39) {
+		// |x|>=2^8, erfc has underflowed
+		if(!complement) {
+			return pdi_ldexp(negative?pdi_neg(uint64_to_pdi(1)):uint64_to_pdi(1),-h);
 		}
-	} else { // y<0
-		y=pdi_neg(y);
-		if(x==0) {
-			return int64fixed2_to_pdi(3,-2); // 3/2
-		} else if (((signed_pd_internal)x)>0) {
-			if(pdi_gte(x,y)) {
-				// q8
-				add_const=0; // boolean
-				negative=1;
-			} else {
-				// q7
-				{ pseudo_double_i t=y; y=x; x=t;}
-				add_const=0xC000000000000000ULL;
-			}
-		} else { // x<0
-			x=pdi_neg(x);
-			if(pdi_gte(x,y)) {
-				// q5
-				add_const=0x8000000000000000ULL;
-			} else {
-				// q6
-				{ pseudo_double_i t=y; y=x; x=t;}
-				add_const=0xC000000000000000ULL;
-				negative=1; // boolean
-			}
+		return negative?pdi_ldexp(uint64_to_pdi(1),1-h):0;
+	}
+	unsigned_large_pd_internal yf=erf_arg_fixed(sig,exp2,normal);
+	int64_t e;
+	if(yf<(((unsigned_large_pd_internal)1)<<63)) {
+		// |y|<1/2, erf(y)=y*ratio(y^2)
+		unsigned_large_pd_internal v=erf_small_fixed(sig,exp2,yf,normal,&e);
+		if(!complement) {
+			return u128_scaled_to_pdi(v,e-h,negative);
 		}
+		unsigned_large_pd_internal ef=shift_u128(v,e+126);
+		unsigned_large_pd_internal one=((unsigned_large_pd_internal)1)<<126;
+		return u128_scaled_to_pdi(negative?one+ef:one-ef,-126-h,0);
 	}
-	int32_t expx=x&EXP_MASK;
-	int32_t expy=y&EXP_MASK;
-	signed_pd_internal vx=(signed_pd_internal)(x&EXP_MASK_INV);
-	signed_pd_internal vy=(signed_pd_internal)(y&EXP_MASK_INV);
-	uint64_t ratio;
-	if(x==y) {
-		ratio=uint64_to_pdi(1);
-	} else if(x==pdi_neg(y)) {
-		ratio=int64_to_pdi(-1);
-	} else {
-		signed_pd_internal vr=(signed_pd_internal)divs64hi((vy>>2),vx);
-		if(vr==0) {
-			ratio=0;
-		} else {
-			int32_t leading_bits=clz(vr)-1;
-			vr<<=leading_bits;
-			int32_t new_exponent=expy-expx-leading_bits;
-			if(new_exponent<-63) {
-				ratio=0;
-			} else {
-				ratio=vr>>(-new_exponent);
-			}
+	unsigned_large_pd_internal d;
+	unsigned_large_pd_internal n=erfc_large_fixed(sig,exp2,yf,normal,&d,&e);
+	if(complement && !negative) {
+		return ratio_u128_to_pdi(n,d,e-h,0);
+	}
+	unsigned_large_pd_internal ef=shift_u128(n/d,e+126); // erfc(|y|)*2^126
+	if(complement) {
+		return u128_scaled_to_pdi((((unsigned_large_pd_internal)1)<<127)-ef,-126-h,0);
+	}
+	return u128_scaled_to_pdi((((unsigned_large_pd_internal)1)<<126)-ef,-126-h,negative);
+}
+
+// |1-a| for 1/2<=a<=2, exact unlike the rounded subtraction
+static pseudo_double_i abs_one_minus_exact(pseudo_double_i a) {
+	int32_t exp2;
+	int negative;
+	uint64_t sig=pdi_to_sig47(a,&exp2,&negative);
+	uint64_t one=1ULL<<-exp2;
+	return u128_scaled_to_pdi((one>sig)?one-sig:sig-one,exp2,0);
+}
+
+// x+c*2^e rounded once, for x>0 and |c*2^e| well below x
+static pseudo_double_i add_scaled(pseudo_double_i x, pseudo_double_i c, int64_t e) {
+	if(c==0) {
+		return x;
+	}
+	int32_t exp2,cexp;
+	int negative,cneg;
+	uint64_t sig=pdi_to_sig47(x,&exp2,&negative);
+	uint64_t csig=pdi_to_sig47(c,&cexp,&cneg);
+	signed_large_pd_internal cv=(signed_large_pd_internal)shift_u128(csig,cexp+e-exp2+64);
+	signed_large_pd_internal t=(((signed_large_pd_internal)sig)<<64)+(cneg?-cv:cv);
+	return u128_scaled_to_pdi((unsigned_large_pd_internal)t,(int64_t)exp2-64,0);
+}
+
+// inverse of erf(y)=r, or of erf(x/sqrt(2))=r if normal is set, for 0<r<=1/2
+// Newton from a series, with the last step taking erf(y)-r from the unrounded fixed value
+static pseudo_double_i erf_inv_central(pseudo_double_i r, int normal) {
+	// below 2^-32 the terms in y^2 are under the precision, and y^2 could underflow
+	int tiny=((int64_t)(r&EXP_MASK)<(int64_t)PSEUDO_DOUBLE_EXP_BIAS-30);
+	pseudo_double_i z=pdi_div(r,PDI_FRAC_2_SQRT_PI);
+	pseudo_double_i y=z;
+	if(!tiny) {
+		// erf_inv(r)=z+z^3/3+7*z^5/30+...
+		pseudo_double_i z2=pdi_mult(z,z);
+		y=pdi_add(z,pdi_mult(pdi_mult(z,z2),pdi_add(PDI_ONE_THIRD,pdi_mult(z2,PDI_SEVEN_THIRTIETHS))));
+		for(int i=0;i<2;i++) {
+			y=pdi_sub(y,pdi_div(pdi_mult(pdi_sub(pdi_erf(y),r),pdi_exp(pdi_mult(y,y))),PDI_FRAC_2_SQRT_PI));
 		}
 	}
-	int64_t d=atan_rev_64_fixed(ratio)>>1;
-	d=add_const+(negative?-d:d);
-	if(d==0) {
+	pseudo_double_i x=normal?pdi_mult(y,PDI_SQRT_2):y;
+	int32_t exp2,er;
+	int negative;
+	uint64_t sig=pdi_to_sig47(x,&exp2,&negative);
+	int64_t e;
+	unsigned_large_pd_internal v=erf_small_fixed(sig,exp2,erf_arg_fixed(sig,exp2,normal),normal,&e);
+	uint64_t sr=pdi_to_sig47(r,&er,&negative);
+	int64_t shift=er-e;
+	signed_large_pd_internal rv=(shift>=0)?(((signed_large_pd_internal)sr)<<shift):(((signed_large_pd_internal)sr)>>-shift);
+	signed_large_pd_internal res=(signed_large_pd_internal)v-rv; // (erf(y)-r)*2^-e
+	if(res==0) {
+		return x;
+	}
+	// dx=-(erf(y)-r)/(dy/dx*2/sqrt(pi)*e^(-y^2)), with erf(y)-r=c*2^e
+	pseudo_double_i c=u128_scaled_to_pdi((unsigned_large_pd_internal)((res<0)?-res:res),0,res>0);
+	pseudo_double_i f=normal?PDI_FRAC_1_SQRT_PI_2:PDI_FRAC_2_SQRT_PI;
+	if(tiny) {
+		return add_scaled(x,pdi_div(c,f),e);
+	}
+	pseudo_double_i y2=normal?pdi_ldexp(pdi_mult(x,x),-1):pdi_mult(x,x);
+	return add_scaled(x,pdi_div(pdi_mult(c,pdi_exp(y2)),f),e);
+}
+
+// inverse of erfc(y)=q, or of erfc(x/sqrt(2))=q if normal is set, for 0<q<1/2
+// Newton on ln(erfc(y)) from the asymptotic y^2=-ln(q)-ln(-pi*ln(q))/2, with the last step
+// taking erfc(y)/q-1 from the unrounded fixed value
+static pseudo_double_i erf_inv_tail(pseudo_double_i q, int normal) {
+	pseudo_double_i lq=pdi_log(q);
+	pseudo_double_i l=pdi_neg(lq);
+	pseudo_double_i y=pdi_sqrt(pdi_sub(l,pdi_ldexp(pdi_log(pdi_mult(PDI_PI,l)),-1)));
+	int32_t exp2,eq;
+	int negative;
+	uint64_t sig;
+	unsigned_large_pd_internal d;
+	for(int i=0;i<3;i++) {
+		sig=pdi_to_sig47(y,&exp2,&negative);
+		uint64_t g=erfcx_fixed(erf_arg_fixed(sig,exp2,0),&d);
+		pseudo_double_i ex=ratio_u128_to_pdi(g,d,2,0);
+		pseudo_double_i f=pdi_sub(pdi_sub(pdi_log(ex),pdi_mult(y,y)),lq); // ln(erfc(y))-ln(q)
+		y=pdi_add(y,pdi_div(pdi_mult(f,ex),PDI_FRAC_2_SQRT_PI));
+	}
+	pseudo_double_i x=normal?pdi_mult(y,PDI_SQRT_2):y;
+	sig=pdi_to_sig47(x,&exp2,&negative);
+	unsigned_large_pd_internal yf=erf_arg_fixed(sig,exp2,normal);
+	int64_t e;
+	unsigned_large_pd_internal n=erfc_large_fixed(sig,exp2,yf,normal,&d,&e);
+	uint64_t sq=pdi_to_sig47(q,&eq,&negative);
+	signed_large_pd_internal res=(signed_large_pd_internal)n-(signed_large_pd_internal)shift_u128(((unsigned_large_pd_internal)sq)*d,eq-e); // (erfc(y)-q)*d*2^-e
+	if(res==0) {
+		return x;
+	}
+	// dx=(erfc(y)-q)/erfc(y)*erfcx(y)/(dy/dx*2/sqrt(pi))
+	pseudo_double_i rho=ratio_u128_to_pdi((unsigned_large_pd_internal)((res<0)?-res:res),n,0,res<0);
+	uint64_t g=erfcx_fixed(yf,&d);
+	pseudo_double_i ex=ratio_u128_to_pdi(g,d,2,0);
+	pseudo_double_i f=normal?PDI_FRAC_1_SQRT_PI_2:PDI_FRAC_2_SQRT_PI;
+	return add_scaled(x,pdi_div(pdi_mult(rho,ex),f),0);
+}
+
+pseudo_double_i pdi_erf(pseudo_double_i x) {
+	return pdi_erf_erfc(x,((signed_pd_internal)x)<0,0,0);
+}
+
+pseudo_double_i pdi_erfc(pseudo_double_i x) {
+	return pdi_erf_erfc(x,((signed_pd_internal)x)<0,1,0);
+}
+
+pseudo_double_i pdi_erf_inv(pseudo_double_i x) {
+#if PD_ERROR_CHECK
+	if(pdi_gte(x,uint64_to_pdi(1)) || pdi_gte(pdi_neg(uint64_to_pdi(1)),x)) {
+		PD_DO_ERROR_RANGE;
+	}
+#endif
+	if(x==0) {
 		return 0;
 	}
-	int negatived=(d<0);
-	int lead_bits=clz(negatived?~d:d);
-	return ((shift_left_signed(d,PSEUDO_DOUBLE_TOTAL_BITS+lead_bits-65))&EXP_MASK_INV)+PSEUDO_DOUBLE_EXP_BIAS+1-lead_bits;
+	int negative=((signed_pd_internal)x)<0;
+	pseudo_double_i a=negative?pdi_neg(x):x;
+	pseudo_double_i r;
+	if(pdi_gte(pdi_ldexp(uint64_to_pdi(1),-1),a)) {
+		r=erf_inv_central(a,0);
+	} else {
+		r=erf_inv_tail(abs_one_minus_exact(a),0);
+	}
+	return negative?pdi_neg(r):r;
 }
 
-pseudo_double_i pdi_atan2(pseudo_double_i y, pseudo_double_i x) {
-	const pseudo_double_i pdi_tau=int64fixed10_to_pdi(6283185307179586477,-18);
-	return pdi_mult(pdi_atan2_rev(y,x),pdi_tau);
+pseudo_double_i pdi_normal_cdf(pseudo_double_i x) {
+	return pdi_erf_erfc(x,((signed_pd_internal)x)>0,1,1);
 }
 
+pseudo_double_i pdi_normal_quantile(pseudo_double_i p) {
+#if PD_ERROR_CHECK
+	if(((signed_pd_internal)p)<=0 || pdi_gte(p,uint64_to_pdi(1))) {
+		PD_DO_ERROR_RANGE;
+	}
+#endif
+	// the argument of the erf or erfc inverse is exact in each range
+	if(pdi_gt(pdi_ldexp(uint64_to_pdi(1),-2),p)) {
+		return pdi_neg(erf_inv_tail(pdi_ldexp(p,1),1));
+	}
+	pseudo_double_i r=abs_one_minus_exact(pdi_ldexp(p,1));
+	if(r==0) {
+		return 0;
+	}
+	if(pdi_gt(pdi_ldexp(uint64_to_pdi(1),-1),p)) {
+		return pdi_neg(erf_inv_central(r,1));
+	}
+	if(pdi_gte(PDI_THREE_QUARTERS,p)) {
+		return erf_inv_central(r,1);
+	}
+	return erf_inv_tail(pdi_ldexp(abs_one_minus_exact(p),1),1);
+}
+#endif
+
 pseudo_double_i pdi_floor(pseudo_double_i x) {
 	int32_t exponent=(x&EXP_MASK);
 	int32_t e=exponent-PSEUDO_DOUBLE_EXP_BIAS;
@@ -73,6 +73,7 @@ inline signed_pd_internal divs64hi(signed_pd_internal x,signed_pd_internal y) {_
 #define clz __builtin_clzll
 typedef __int128 signed_large_pd_internal;
 typedef unsigned __int128 unsigned_large_pd_internal;
+#define PSEUDO_DOUBLE_HAS_INT128 1
 inline signed_pd_internal mults64hi(signed_pd_internal x,signed_pd_internal y) {return (signed_pd_internal)(((((signed_large_pd_internal)x)*y)>>64));}
 inline unsigned_pd_internal multu64hi(unsigned_pd_internal x,unsigned_pd_internal y) {return (unsigned_pd_internal)((((unsigned_large_pd_internal)x)*y)>>64);}
 inline signed_pd_internal divs64hi(signed_pd_internal x,signed_pd_internal y) {return (signed_pd_internal)((((signed_large_pd_internal)x)<<64)/y);}
@@ -442,10 +443,24 @@ pseudo_double_i pdi_log10(pseudo_double_i x);
 pseudo_double_i pdi_pow(pseudo_double_i x, pseudo_double_i y);
 pseudo_double_i pdi_sin_rev(pseudo_double_i x);
 pseudo_double_i pdi_cos_rev(pseudo_double_i x);
+pseudo_double_i pdi_asin_rev(pseudo_double_i x);
+pseudo_double_i pdi_acos_rev(pseudo_double_i x);
+pseudo_double_i pdi_atan_rev(pseudo_double_i x);
 pseudo_double_i pdi_atan2_rev(pseudo_double_i y, pseudo_double_i x);
 pseudo_double_i pdi_sin(pseudo_double_i x);
 pseudo_double_i pdi_cos(pseudo_double_i x);
+pseudo_double_i pdi_asin(pseudo_double_i x);
+pseudo_double_i pdi_acos(pseudo_double_i x);
+pseudo_double_i pdi_atan(pseudo_double_i x);
 pseudo_double_i pdi_atan2(pseudo_double_i y, pseudo_double_i x);
+#ifdef PSEUDO_DOUBLE_HAS_INT128
+// The error functions need 128 bit integers, so are not available with Visual Studio
+pseudo_double_i pdi_erf(pseudo_double_i x);
+pseudo_double_i pdi_erfc(pseudo_double_i x);
+pseudo_double_i pdi_erf_inv(pseudo_double_i x);
+pseudo_double_i pdi_normal_cdf(pseudo_double_i x);
+pseudo_double_i pdi_normal_quantile(pseudo_double_i p);
+#endif
 pseudo_double_i string_to_pdi(const char* str);
 
 // x is a 2.62 unsigned fixed in the range (1,4)
@@ -471,6 +486,31 @@ uint64_t sin_rev_64_fixed(uint64_t x);
 // result is 2.62 unsigned fixed in the range [0,1]
 uint64_t atan_rev_64_fixed(uint64_t x);
 
+// x is a 2.62 unsigned fixed in the range [0,1/2]
+// calculate atan(x)*4/(pi*x)
+// result is 2.62 fixed in the range [1.18,4/pi]
+int64_t atan_rev_ratio_64_fixed(uint64_t x);
+
+// x is a 2.62 unsigned fixed in the range [0,1/2]
+// calculate asin(x)*4/(pi*x)
+// result is 2.62 fixed in the range [4/pi,4/3]
+int64_t asin_rev_ratio_64_fixed(uint64_t x);
+
+// x is a 1.63 signed fixed in the range [-0.5,0.5]
+// calculate (e^x-1)/x
+// result is 2.62 unsigned fixed in the range (0.78,1.3)
+uint64_t expm1_ratio_64_fixed(int64_t x);
+
+// x is a 2.62 unsigned fixed in the range [0,1/2]
+// calculate erf(x)/x
+// result is 2.62 unsigned fixed in the range [1.04,2/sqrt(pi)]
+uint64_t erf_ratio_64_fixed(uint64_t x);
+
+// t is a 1.63 signed fixed in the range [-15/17,1)
+// calculate erfcx(y)*(1+2*y) for y=4*(1+t)/(1-t), with erfcx(y)=e^(y^2)*erfc(y)
+// result is 2.62 unsigned fixed in the range [1.12,1.29]
+uint64_t erfcx_64_fixed(int64_t t);
+
 void debug_pdi_output(pseudo_double_i d);
 
 /* =========================================================================================================================================================================
@@ -521,10 +561,23 @@ inline pseudo_double pd_log10(pseudo_double x) {return create_pseudo_double_from
 inline pseudo_double pd_pow(pseudo_double x, pseudo_double y) {return create_pseudo_double_from_internal(pdi_pow(x.val,y.val));}
 inline pseudo_double pd_sin_rev(pseudo_double x) {return create_pseudo_double_from_internal(pdi_sin_rev(x.val));}
 inline pseudo_double pd_cos_rev(pseudo_double x) {return create_pseudo_double_from_internal(pdi_cos_rev(x.val));}
+inline pseudo_double pd_asin_rev(pseudo_double x) {return create_pseudo_double_from_internal(pdi_asin_rev(x.val));}
+inline pseudo_double pd_acos_rev(pseudo_double x) {return create_pseudo_double_from_internal(pdi_acos_rev(x.val));}
+inline pseudo_double pd_atan_rev(pseudo_double x) {return create_pseudo_double_from_internal(pdi_atan_rev(x.val));}
 inline pseudo_double pd_atan2_rev(pseudo_double y, pseudo_double x) {return create_pseudo_double_from_internal(pdi_atan2_rev(x.val,y.val));}
 inline pseudo_double pd_sin(pseudo_double x) {return create_pseudo_double_from_internal(pdi_sin(x.val));}
 inline pseudo_double pd_cos(pseudo_double x) {return create_pseudo_double_from_internal(pdi_cos(x.val));}
+inline pseudo_double pd_asin(pseudo_double x) {return create_pseudo_double_from_internal(pdi_asin(x.val));}
+inline pseudo_double pd_acos(pseudo_double x) {return create_pseudo_double_from_internal(pdi_acos(x.val));}
+inline pseudo_double pd_atan(pseudo_double x) {return create_pseudo_double_from_internal(pdi_atan(x.val));}
 inline pseudo_double pd_atan2(pseudo_double y, pseudo_double x) {return create_pseudo_double_from_internal(pdi_atan2(x.val,y.val));}
+#ifdef PSEUDO_DOUBLE_HAS_INT128
+inline pseudo_double pd_erf(pseudo_double x) {return create_pseudo_double_from_internal(pdi_erf(x.val));}
+inline pseudo_double pd_erfc(pseudo_double x) {return create_pseudo_double_from_internal(pdi_erfc(x.val));}
+inline pseudo_double pd_erf_inv(pseudo_double x) {return create_pseudo_double_from_internal(pdi_erf_inv(x.val));}
+inline pseudo_double pd_normal_cdf(pseudo_double x) {return create_pseudo_double_from_internal(pdi_normal_cdf(x.val));}
+inline pseudo_double pd_normal_quantile(pseudo_double p) {return create_pseudo_double_from_internal(pdi_normal_quantile(p.val));}
+#endif
 inline bool pd_gt_zero(pseudo_double x) {return (((signed_pd_internal)x.val)>0);}
 inline bool pd_gte_zero(pseudo_double x) {return (((signed_pd_internal)x.val)>=0);}
 inline bool pd_lt_zero(pseudo_double x) {return (((signed_pd_internal)x.val)<0);}
@@ -31,18 +31,21 @@ use simba::scalar::{Field,RealField,ComplexField,SubsetOf};
 use simba::simd::{SimdValue};
 use std::ops::{Add, AddAssign, Sub, SubAssign, Neg, Mul, MulAssign, Div, DivAssign, Rem, RemAssign};
 use std::cmp::{Eq, Ordering};
+use std::iter::{Sum, Product};
 use approx::{UlpsEq, AbsDiffEq, RelativeEq};
 use std::convert::From;
-use num_traits::{Bounded,Signed,Num,Zero,One,FromPrimitive};
-use std::fmt::{Display,Formatter};
+use std::str::FromStr;
+use num_traits::{Bounded,Signed,Num,Zero,One,FromPrimitive,ToPrimitive,AsPrimitive,Pow,Euclid,CheckedAdd,CheckedSub,CheckedMul,CheckedDiv,MulAdd,MulAddAssign,Inv,FloatConst};
+use std::num::FpCategory;
+use std::fmt::{Debug,Display,Formatter,LowerExp,UpperExp,LowerHex,UpperHex};
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Copy, Clone, Eq, PartialEq)]
 pub struct PseudoDouble(pub i64);
 
 const PSEUDO_DOUBLE_TOTAL_BITS: i32 = 64;
 const PSEUDO_DOUBLE_EXP_BITS: i32 = 16;
 const EXP_MASK: i64 = (1<<PSEUDO_DOUBLE_EXP_BITS)-1;
-const EXP_MASK_INV: i64 = !EXP_MASK as i64;
+const EXP_MASK_INV: i64 = !EXP_MASK;
 const PSEUDO_DOUBLE_HALF_ULP: i64 = (1<<(PSEUDO_DOUBLE_EXP_BITS-1))-1;
 const PSEUDO_DOUBLE_EXP_BIAS: i64 = 1<<(PSEUDO_DOUBLE_EXP_BITS-1);
 
@@ -56,7 +59,123 @@ pub const PD_INV_LOG_2_10: PseudoDouble = PseudoDouble::pdc10(301029995663981195
 pub const PD_TAU:          PseudoDouble = PseudoDouble::pdc10(6283185307179586477,-18);
 pub const PD_PI:           PseudoDouble = PD_TAU.ldexp(-1);
 pub const PD_INV_TAU:      PseudoDouble = PseudoDouble::pdc10(1591549430918953358,-19);
-pub const PD_EPSILON:      PseudoDouble = PseudoDouble((1i64<<(PSEUDO_DOUBLE_TOTAL_BITS-2))+(1i64<<(PSEUDO_DOUBLE_EXP_BITS-1))+(PSEUDO_DOUBLE_EXP_BITS-PSEUDO_DOUBLE_TOTAL_BITS+4) as i64);
+pub const PD_EPSILON:      PseudoDouble = PseudoDouble::EPSILON;
+const PD_DEGREES_PER_RADIAN: PseudoDouble = PseudoDouble::const_from_str("57.29577951308232087679815481410517033241");
+const PD_RADIANS_PER_DEGREE: PseudoDouble = PseudoDouble::const_from_str("0.01745329251994329576923690768488612713443");
+
+/// Mathematical constants, correctly rounded, like `std::f64::consts`.
+pub mod consts {
+	use crate::PseudoDouble;
+
+	/// π
+	pub const PI:              PseudoDouble = PseudoDouble::const_from_str("3.141592653589793238462643383279502884197");
+	/// 2π
+	pub const TAU:             PseudoDouble = PseudoDouble::const_from_str("6.283185307179586476925286766559005768394");
+	/// π/2
+	pub const FRAC_PI_2:       PseudoDouble = PseudoDouble::const_from_str("1.570796326794896619231321691639751442099");
+	/// π/3
+	pub const FRAC_PI_3:       PseudoDouble = PseudoDouble::const_from_str("1.047197551196597746154214461093167628066");
+	/// π/4
+	pub const FRAC_PI_4:       PseudoDouble = PseudoDouble::const_from_str("0.7853981633974483096156608458198757210493");
+	/// π/6
+	pub const FRAC_PI_6:       PseudoDouble = PseudoDouble::const_from_str("0.5235987755982988730771072305465838140329");
+	/// π/8
+	pub const FRAC_PI_8:       PseudoDouble = PseudoDouble::const_from_str("0.3926990816987241548078304229099378605246");
+	/// 1/π
+	pub const FRAC_1_PI:       PseudoDouble = PseudoDouble::const_from_str("0.3183098861837906715377675267450287240689");
+	/// 2/π
+	pub const FRAC_2_PI:       PseudoDouble = PseudoDouble::const_from_str("0.6366197723675813430755350534900574481378");
+	/// 1/sqrt(π)
+	pub const FRAC_1_SQRT_PI:  PseudoDouble = PseudoDouble::const_from_str("0.5641895835477562869480794515607725858441");
+	/// 1/sqrt(2π)
+	pub const FRAC_1_SQRT_2PI: PseudoDouble = PseudoDouble::const_from_str("0.3989422804014326779399460599343818684759");
+	/// 2/sqrt(π)
+	pub const FRAC_2_SQRT_PI:  PseudoDouble = PseudoDouble::const_from_str("1.128379167095512573896158903121545171688");
+	/// sqrt(2)
+	pub const SQRT_2:          PseudoDouble = PseudoDouble::const_from_str("1.414213562373095048801688724209698078570");
+	/// 1/sqrt(2)
+	pub const FRAC_1_SQRT_2:   PseudoDouble = PseudoDouble::const_from_str("0.7071067811865475244008443621048490392848");
+	/// sqrt(3)
+	pub const SQRT_3:          PseudoDouble = PseudoDouble::const_from_str("1.732050807568877293527446341505872366943");
+	/// 1/sqrt(3)
+	pub const FRAC_1_SQRT_3:   PseudoDouble = PseudoDouble::const_from_str("0.5773502691896257645091487805019574556476");
+	/// Euler's number e
+	pub const E:               PseudoDouble = PseudoDouble::const_from_str("2.718281828459045235360287471352662497757");
+	/// log2(e)
+	pub const LOG2_E:          PseudoDouble = PseudoDouble::const_from_str("1.442695040888963407359924681001892137427");
+	/// log2(10)
+	pub const LOG2_10:         PseudoDouble = PseudoDouble::const_from_str("3.321928094887362347870319429489390175865");
+	/// log10(e)
+	pub const LOG10_E:         PseudoDouble = PseudoDouble::const_from_str("0.4342944819032518276511289189166050822944");
+	/// log10(2)
+	pub const LOG10_2:         PseudoDouble = PseudoDouble::const_from_str("0.3010299956639811952137388947244930267682");
+	/// ln(2)
+	pub const LN_2:            PseudoDouble = PseudoDouble::const_from_str("0.6931471805599453094172321214581765680755");
+	/// ln(10)
+	pub const LN_10:           PseudoDouble = PseudoDouble::const_from_str("2.302585092994045684017991454684364207601");
+	/// The golden ratio φ
+	pub const PHI:             PseudoDouble = PseudoDouble::const_from_str("1.618033988749894848204586834365638117720");
+	/// The Euler-Mascheroni constant γ
+	pub const EGAMMA:          PseudoDouble = PseudoDouble::const_from_str("0.5772156649015328606065120900824024310422");
+}
+
+/// A `PseudoDouble` from a decimal literal, parsed and correctly rounded at compile time.
+/// Malformed or out of range literals are compile errors. Number literals can use `_` separators
+/// and a type suffix such as `f64`, which is ignored: `pd!(2.5f32)` is exactly 2.5, not the `f32`.
+///
+/// ```
+/// use pseudodouble::{pd, PseudoDouble};
+///
+/// const TAU: PseudoDouble = pd!("6.283185307179586477");
+/// static SMALL: PseudoDouble = pd!(1.5e-3);
+/// let x = pd!(-0.25) * TAU;
+/// assert_eq!(SMALL, "0.0015".parse().unwrap());
+/// assert_eq!(x, -TAU.ldexp(-2));
+/// assert_eq!(pd!(1_000.5f64), pd!("1000.5"));
+/// ```
+///
+/// ```compile_fail
+/// use pseudodouble::{pd, PseudoDouble};
+///
+/// const BAD: PseudoDouble = pd!("1.2.3");
+/// ```
+#[macro_export]
+macro_rules! pd {
+	(- $s:literal) => {{
+		const PD_LITERAL: $crate::PseudoDouble = $crate::PseudoDouble::const_from_literal(stringify!($s)).const_neg();
+		PD_LITERAL
+	}};
+	($s:literal) => {{
+		const PD_LITERAL: $crate::PseudoDouble = $crate::PseudoDouble::const_from_literal(stringify!($s));
+		PD_LITERAL
+	}};
+}
+
+// number literals with _ separators are copied into a buffer of this size for parsing
+const LITERAL_MAX_LEN: usize = 256;
+
+// the length of the type suffix of a number literal, such as f64 in 2.5f64, or 0 if there is none.
+// A hex literal can end in f32 or f64 as digits, so only integer suffixes are taken from it.
+const fn literal_suffix_len(bytes:&[u8]) -> usize {
+	const SUFFIXES:[&str;14]=["f32","f64","i8","i16","i32","i64","i128","isize","u8","u16","u32","u64","u128","usize"];
+	let hex=bytes.len()>=2 && bytes[0]==b'0' && (bytes[1]==b'x' || bytes[1]==b'X');
+	let mut k=0;
+	while k<SUFFIXES.len() {
+		let suffix=SUFFIXES[k].as_bytes();
+		if suffix.len()<bytes.len() && !(hex && suffix[0]==b'f') {
+			let start=bytes.len()-suffix.len();
+			let mut j=0;
+			while j<suffix.len() && bytes[start+j]==suffix[j] {
+				j+=1;
+			}
+			if j==suffix.len() {
+				return suffix.len();
+			}
+		}
+		k+=1;
+	}
+	0
+}
 
 #[inline]
 const fn shift_left_signed(x:i64, shift:i32) -> i64 {
@@ -99,12 +218,57 @@ const fn inv_sqrt64_fixed(x:u64) -> u64 {
 	// 3	~3e-5
 	// 4	~1e-9
 	// 5	~1e-18 (about 60 bits - limit of the algorithm)
-	y=multu64hi(y,0xC000000000000000u64-((multu64hi(multu64hi(y,y),x))))<<1;
-	y=multu64hi(y,0xC000000000000000u64-((multu64hi(multu64hi(y,y),x))))<<1;
-	y=multu64hi(y,0xC000000000000000u64-((multu64hi(multu64hi(y,y),x))))<<1;
-	y=multu64hi(y,0xC000000000000000u64-((multu64hi(multu64hi(y,y),x))))<<1;
-	y=multu64hi(y,0xC000000000000000u64-((multu64hi(multu64hi(y,y),x)))); // dont shift left on the last one
-	return y;
+	y=multu64hi(y,0xC000000000000000u64-(multu64hi(multu64hi(y,y),x)))<<1;
+	y=multu64hi(y,0xC000000000000000u64-(multu64hi(multu64hi(y,y),x)))<<1;
+	y=multu64hi(y,0xC000000000000000u64-(multu64hi(multu64hi(y,y),x)))<<1;
+	y=multu64hi(y,0xC000000000000000u64-(multu64hi(multu64hi(y,y),x)))<<1;
+	y=multu64hi(y,0xC000000000000000u64-(multu64hi(multu64hi(y,y),x))); // dont shift left on the last one
+	y
+}
+
+// one Newton-Raphson step y=y+y*(1-x*y^3)/3 for x^(-1/3), x 3.61 and y 1.63
+const fn inv_cbrt64_step(y:u64, x:u64) -> u64 {
+	let y2=multu64hi(y,y)<<1;
+	let y3=multu64hi(y2,y)<<1;
+	let t=multu64hi(x,y3)<<2; // x*y^3 as 2.62
+	if t<=1u64<<62 {
+		return y+(multu64hi(y,(1u64<<62)-t)<<2)/3;
+	}
+	y-(multu64hi(y,t-(1u64<<62))<<2)/3
+}
+
+// x is a 3.61 unsigned fixed in the range [1,8)
+// result is 1.63 unsigned fixed in the range (0.5,1]
+const fn inv_cbrt64_fixed(x:u64) -> u64 {
+	// start with a linear interpolation correct at the endpoints
+	// 15/14 - 1/14 x, so 1->1, 8->0.5
+	let mut y=((((15u128<<61)-x as u128)<<2)/14) as u64;
+	// Maximum error for #iterations:
+	// 0	~0.16
+	// 1	~0.09
+	// 2	~0.02
+	// 3	~2e-3
+	// 4	~1e-5
+	// 5	~3e-10
+	// 6	~5e-19 (about 60 bits - limit of the algorithm)
+	y=inv_cbrt64_step(y,x);
+	y=inv_cbrt64_step(y,x);
+	y=inv_cbrt64_step(y,x);
+	y=inv_cbrt64_step(y,x);
+	y=inv_cbrt64_step(y,x);
+	y=inv_cbrt64_step(y,x);
+	y
+}
+
+// a^3 for a<2^48 as a 256 bit (hi,lo) pair
+const fn cube_u256(a:u64) -> (u128,u128) {
+	let a2=a as u128*a as u128;
+	(mul_u128_hi(a2,a as u128),a2.wrapping_mul(a as u128))
+}
+
+// a<b for 256 bit (hi,lo) pairs
+const fn lt_u256(a:(u128,u128), b:(u128,u128)) -> bool {
+	a.0<b.0 || (a.0==b.0 && a.1<b.1)
 }
 
 // x is a 0.64 unsigned fixed in the range [0,1)
@@ -120,7 +284,7 @@ const fn exp2_64_fixed(x:u64) -> u64 {
 	u=multu64hi(u,x)+1023870086755462747u64;
 	u=multu64hi(u,x)+4431396893648852228u64;
 	u=multu64hi(u,x)+(12786308645201320706u64+0x2B5B);
-	return (multu64hi(u,x)>>2)+0x4000000000000000u64;
+	(multu64hi(u,x)>>2)+0x4000000000000000u64
 }
 
 // ./lolremez --stats --debug --long-double -d 18 -r "0:1" "log2(x+1)"
@@ -146,112 +310,2445 @@ const fn exp2_64_fixed(x:u64) -> u64 {
 //     return u * x;
 // }
 
-// x is a 1.63 unsigned fixed in the range [0,1)
-// calculate ln2(x+1)
-// result is 1.63 unsigned fixed in the range [0,1)
-const fn log2_64_fixed(xu:u64) -> u64 {
-	let x=xu as i64;
-	let mut u=              -866184866458461i64*256;
-	u=mults64hi(u,x)       +9096620059073819i64*128;
-	u=mults64hi(u,x)      -45229346966063088i64*64;
-	u=mults64hi(u,x)     +142648701962462304i64*32;
-	u=mults64hi(u,x)     -323869540712705594i64*16;
-	u=mults64hi(u,x)     +572750283281423541i64*8;
-	u=mults64hi(u,x)     -839494755772336399i64*4;
-	u=mults64hi(u,x)    +1078758785161816410i64*2;
-	u=mults64hi(u,x)    -1279749673020511097i64;
-	u=mults64hi(u<<2,x) +1462920026749624213i64*2;
-	u=mults64hi(u,x)    -1659624849656686669i64;
-	u=mults64hi(u<<2,x) +1900269450970511052i64*2;
-	u=mults64hi(u,x)    -2217665122870979542i64;
-	u=mults64hi(u<<1,x) +2661294517602797903i64;
-	u=mults64hi(u<<1,x) -3326627771183711640i64;
-	u=mults64hi(u<<1,x) +4435504346812152696i64;
-	u=mults64hi(u<<1,x) -6653256548536882955i64;
-	u=mults64hi(u,x)+   (6653256548920620560i64+0x1005);
-	return (mults64hi(u,x)<<2) as u64;
+// x is a 1.63 unsigned fixed in the range [0,1)
+// calculate ln2(x+1)
+// result is 1.63 unsigned fixed in the range [0,1)
+const fn log2_64_fixed(xu:u64) -> u64 {
+	let x=xu as i64;
+	let mut u=              -866184866458461i64*256;
+	u=mults64hi(u,x)       +9096620059073819i64*128;
+	u=mults64hi(u,x)      -45229346966063088i64*64;
+	u=mults64hi(u,x)     +142648701962462304i64*32;
+	u=mults64hi(u,x)     -323869540712705594i64*16;
+	u=mults64hi(u,x)     +572750283281423541i64*8;
+	u=mults64hi(u,x)     -839494755772336399i64*4;
+	u=mults64hi(u,x)    +1078758785161816410i64*2;
+	u=mults64hi(u,x)    -1279749673020511097i64;
+	u=mults64hi(u<<2,x) +1462920026749624213i64*2;
+	u=mults64hi(u,x)    -1659624849656686669i64;
+	u=mults64hi(u<<2,x) +1900269450970511052i64*2;
+	u=mults64hi(u,x)    -2217665122870979542i64;
+	u=mults64hi(u<<1,x) +2661294517602797903i64;
+	u=mults64hi(u<<1,x) -3326627771183711640i64;
+	u=mults64hi(u<<1,x) +4435504346812152696i64;
+	u=mults64hi(u<<1,x) -6653256548536882955i64;
+	u=mults64hi(u,x)+   (6653256548920620560i64+0x1005);
+	(mults64hi(u,x)<<2) as u64
+}
+
+// Taylor series (e^x-1)/x = sum x^k/(k+1)!, the last term used is below 2^-70 on the range
+// x is a 1.63 signed fixed in the range [-0.5,0.5]
+// calculate (e^x-1)/x
+// result is 2.62 unsigned fixed in the range (0.78,1.3)
+const fn expm1_ratio_64_fixed(x:i64) -> u64 {
+	let mut u=                             720i64;
+	u=(mults64hi(u,x)<<1)                +12966i64;
+	u=(mults64hi(u,x)<<1)               +220414i64;
+	u=(mults64hi(u,x)<<1)              +3526632i64;
+	u=(mults64hi(u,x)<<1)             +52899477i64;
+	u=(mults64hi(u,x)<<1)            +740592679i64;
+	u=(mults64hi(u,x)<<1)           +9627704831i64;
+	u=(mults64hi(u,x)<<1)         +115532457973i64;
+	u=(mults64hi(u,x)<<1)        +1270857037706i64;
+	u=(mults64hi(u,x)<<1)       +12708570377060i64;
+	u=(mults64hi(u,x)<<1)      +114377133393536i64;
+	u=(mults64hi(u,x)<<1)      +915017067148291i64;
+	u=(mults64hi(u,x)<<1)     +6405119470038039i64;
+	u=(mults64hi(u,x)<<1)    +38430716820228233i64;
+	u=(mults64hi(u,x)<<1)   +192153584101141163i64;
+	u=(mults64hi(u,x)<<1)   +768614336404564651i64;
+	u=(mults64hi(u,x)<<1)  +2305843009213693952i64;
+	u=(mults64hi(u,x)<<1)  +4611686018427387904i64;
+	u as u64
+}
+
+// ln(1+x)=2*atanh(s) with s=x/(2+x), and 2*atanh(s)/s = 2*sum s^2k/(2k+1), the last term used
+// is below 2^-70 on the range
+// x is a 1.63 signed fixed in the range [-0.25,0.5)
+// calculate ln(1+x)/x = 2*sum(s^2k/(2k+1))/(2+x)
+// result is 2.62 unsigned fixed in the range (0.81,1.16]
+const fn ln1p_ratio_64_fixed(x:i64) -> u64 {
+	let d=(2i128<<62)+(x>>1) as i128; // 2+x with 62 fraction bits
+	let s=(((x as i128)<<62)/d) as i64;
+	let s2=mults64hi(s,s)<<1;
+	let mut u=                 159023655807840962i64;
+	u=(mults64hi(u,s2)<<1)    +170803185867681033i64;
+	u=(mults64hi(u,s2)<<1)    +184467440737095516i64;
+	u=(mults64hi(u,s2)<<1)    +200508087757712518i64;
+	u=(mults64hi(u,s2)<<1)    +219604096115589900i64;
+	u=(mults64hi(u,s2)<<1)    +242720316759336205i64;
+	u=(mults64hi(u,s2)<<1)    +271275648142787524i64;
+	u=(mults64hi(u,s2)<<1)    +307445734561825860i64;
+	u=(mults64hi(u,s2)<<1)    +354745078340568300i64;
+	u=(mults64hi(u,s2)<<1)    +419244183493398900i64;
+	u=(mults64hi(u,s2)<<1)    +512409557603043100i64;
+	u=(mults64hi(u,s2)<<1)    +658812288346769701i64;
+	u=(mults64hi(u,s2)<<1)    +922337203685477581i64;
+	u=(mults64hi(u,s2)<<1)   +1537228672809129301i64;
+	u=(mults64hi(u,s2)<<1)   +4611686018427387904i64;
+	(((u as i128)<<63)/d) as u64
+}
+
+// x times a 2.62 unsigned fixed, rounded to nearest
+const fn mul_fixed_2_62(x:PseudoDouble, u:u64) -> PseudoDouble {
+	let p=(x.0&EXP_MASK_INV) as i128*u as i128;
+	let exponent=((x.0&EXP_MASK)-PSEUDO_DOUBLE_EXP_BIAS-64-62) as i32;
+	u128_to_pd_rounded(p.unsigned_abs(),p<0).ldexp(exponent)
+}
+
+// ln(2)*2^100
+const LN_2_FIXED_100:u128=878668439483319573618263538048;
+
+// e^a or e^-a for 0<=a<=2^15 as m*2^(k-125) with m in [2^124.5,2^125.5]
+const fn exp_fixed(a:PseudoDouble, negative:bool) -> (u128,i64) {
+	if a.0==0 {
+		return (1u128<<125,0);
+	}
+	let (_,sig,exp2)=pd_to_sig_exp(a);
+	let shift=exp2+100;
+	let x=if shift>=0 {(sig as u128)<<shift} else if shift>-64 {(sig as u128)>>-shift} else {0};
+	exp_fixed_100(x,negative)
+}
+
+// e^a or e^-a for a=x*2^-100 with x<2^116, as m*2^(k-125) with m in [2^124.5,2^125.5]
+// a=k*ln(2)+r with ln(2) to 100 bits, so the reduction stays exact enough over the whole range
+const fn exp_fixed_100(x:u128, negative:bool) -> (u128,i64) {
+	let k=(x+LN_2_FIXED_100/2)/LN_2_FIXED_100;
+	let mut r=((x as i128-(k*LN_2_FIXED_100) as i128)>>37) as i64; // 1.63 fixed in [-ln(2)/2,ln(2)/2]
+	if negative {
+		r= -r;
+	}
+	let m=((1i128<<125)+r as i128*expm1_ratio_64_fixed(r) as i128) as u128;
+	(m,if negative {-(k as i64)} else {k as i64})
+}
+
+// cosh(a)=c*2^(k-126) and sinh(a)=s*2^(k-126) for 0<=a<=2^15 as (c,s,k)
+const fn cosh_sinh_fixed(a:PseudoDouble) -> (u128,u128,i64) {
+	let (m1,k1)=exp_fixed(a,false);
+	let (m2,k2)=exp_fixed(a,true);
+	let shift=k1-k2;
+	let m2=if shift>127 {0} else {m2>>shift};
+	(m1+m2,m1-m2,k1)
+}
+
+// sinh(x)/x=sum(x^2k/(2k+1)!)
+// x is a 2.62 unsigned fixed in the range [0,1/2]
+// result is 2.62 unsigned fixed in the range [1,1.05]
+const fn sinh_ratio_64_fixed(xu:u64) -> u64 {
+	let x=xu as i64;
+	let x2=mults64hi(x,x)<<2;
+	let mut u=                                38i64;
+	u=(mults64hi(u,x2)<<2)                 +12966i64;
+	u=(mults64hi(u,x2)<<2)               +3526632i64;
+	u=(mults64hi(u,x2)<<2)             +740592679i64;
+	u=(mults64hi(u,x2)<<2)           +115532457973i64;
+	u=(mults64hi(u,x2)<<2)         +12708570377060i64;
+	u=(mults64hi(u,x2)<<2)        +915017067148291i64;
+	u=(mults64hi(u,x2)<<2)      +38430716820228233i64;
+	u=(mults64hi(u,x2)<<2)     +768614336404564651i64;
+	u=(mults64hi(u,x2)<<2)+(4611686018427387904i64+2);
+	u as u64
+}
+
+// chebyfit of asinh(x)/x as a polynomial in x^2 over x in [0,1/2]
+
+// x is a 2.62 unsigned fixed in the range [0,1/2]
+// calculate asinh(x)/x
+// result is 2.62 unsigned fixed in the range [0.96,1]
+const fn asinh_ratio_64_fixed(xu:u64) -> u64 {
+	let x=xu as i64;
+	let x2=mults64hi(x,x)<<2;
+	let mut u=                   4995238310148481i64;
+	u=(mults64hi(u,x2)<<2)     -14994531404681505i64;
+	u=(mults64hi(u,x2)<<2)     +24862442316051970i64;
+	u=(mults64hi(u,x2)<<2)     -32259731182616733i64;
+	u=(mults64hi(u,x2)<<2)     +38376523841939269i64;
+	u=(mults64hi(u,x2)<<2)     -44967435624538472i64;
+	u=(mults64hi(u,x2)<<2)     +53267518888354017i64;
+	u=(mults64hi(u,x2)<<2)     -64400995069559467i64;
+	u=(mults64hi(u,x2)<<2)     +80025472704447266i64;
+	u=(mults64hi(u,x2)<<2)    -103173372126668872i64;
+	u=(mults64hi(u,x2)<<2)    +140111988376299545i64;
+	u=(mults64hi(u,x2)<<2)    -205878840107870207i64;
+	u=(mults64hi(u,x2)<<2)    +345876451382049910i64;
+	u=(mults64hi(u,x2)<<2)    -768614336404564637i64;
+	u=(mults64hi(u,x2)<<2)+(4611686018427387904i64+2);
+	u as u64
+}
+
+// chebyfit of atanh(x)/x as a polynomial in x^2 over x in [0,1/2]
+
+// x is a 2.62 unsigned fixed in the range [0,1/2]
+// calculate atanh(x)/x
+// result is 2.62 unsigned fixed in the range [1,1.1]
+const fn atanh_ratio_64_fixed(xu:u64) -> u64 {
+	let x=xu as i64;
+	let x2=mults64hi(x,x)<<2;
+	let mut u=                1190304403292365331i64;
+	u=(mults64hi(u,x2)<<2)   -1118835665883437260i64;
+	u=(mults64hi(u,x2)<<2)    +976345971123107998i64;
+	u=(mults64hi(u,x2)<<2)    -135361974388946579i64;
+	u=(mults64hi(u,x2)<<2)    +287368471403825680i64;
+	u=(mults64hi(u,x2)<<2)    +202811097951041439i64;
+	u=(mults64hi(u,x2)<<2)    +245074438350424840i64;
+	u=(mults64hi(u,x2)<<2)    +271035283622039988i64;
+	u=(mults64hi(u,x2)<<2)    +307463488975003996i64;
+	u=(mults64hi(u,x2)<<2)    +354744146582737537i64;
+	u=(mults64hi(u,x2)<<2)    +419244217156121003i64;
+	u=(mults64hi(u,x2)<<2)    +512409556807140660i64;
+	u=(mults64hi(u,x2)<<2)    +658812288358119826i64;
+	u=(mults64hi(u,x2)<<2)    +922337203685392769i64;
+	u=(mults64hi(u,x2)<<2)   +1537228672809129551i64;
+	u=(mults64hi(u,x2)<<2)+(4611686018427387904i64+2);
+	u as u64
+}
+
+// ln(x+sqrt(x^2+1)), or ln(x+sqrt(x^2-1)) with minus, for x=sig*2^exp2 in [1/2,2^24)
+// x^2+-1 is exact in integers and the square root is taken to 63 bits
+const fn ln_fixed_sum_sqrt(sig:u64, exp2:i32, minus:bool) -> (i128,i64) {
+	let one=1u128<<(-2*exp2);
+	let s2=sig as u128*sig as u128;
+	let n=if minus {s2-one} else {s2+one}; // (x^2+-1)*2^(-2*exp2)
+	if n==0 {
+		return (0,0);
+	}
+	let j=(126-(128-n.leading_zeros() as i64))>>1;
+	let r=(n<<(2*j)).isqrt(); // sqrt(x^2+-1)*2^(j-exp2)
+	ln_fixed(((sig as u128)<<j)+r,exp2 as i64-j)
+}
+
+// ln(n*2^exp2) for n>0 as v*2^e, with n*2^exp2=m*2^k, m in [3/4,3/2)
+// m-1 is exact so the result keeps its relative precision when n*2^exp2 is close to 1.
+// For k!=0 the result is always at e=-110.
+const fn ln_fixed(n:u128, exp2:i64) -> (i128,i64) {
+	let lz=n.leading_zeros() as i64;
+	let n=(n<<lz)>>2; // [2^125,2^126)
+	let q=if n<(3u128<<124) {125} else {126};
+	let k=exp2-lz+2+q;
+	let t=n as i128-(1i128<<q); // (m-1)*2^q
+	if t==0 {
+		return (k as i128*((LN_2_FIXED_100 as i128)<<10),-110);
+	}
+	let shift=65-t.unsigned_abs().leading_zeros() as i64;
+	let shift=if shift<0 {0} else {shift};
+	let t64=(t>>shift) as i64; // (m-1)*2^(q-shift), at most 63 bits
+	let u=ln1p_ratio_64_fixed((t>>(q-63)) as i64);
+	let p=t64 as i128*u as i128; // ln(m)*2^(q-shift+62)
+	let e=shift-q-62;
+	if k==0 {
+		return (p,e);
+	}
+	let p=if e+110>=0 {p<<(e+110)} else {p>>-(e+110)};
+	(k as i128*((LN_2_FIXED_100 as i128)<<10)+p,-110)
+}
+
+// 1/sqrt(2) as a 0.64 unsigned fixed
+const FRAC_1_SQRT_2_FIXED_64:u64=13043817825332782212;
+
+// chebyfit of erf(x)/x as a polynomial in x^2 over x in [0,1/2]
+
+// x is a 2.62 unsigned fixed in the range [0,1/2]
+// calculate erf(x)/x
+// result is 2.62 unsigned fixed in the range [1.04,2/sqrt(pi)]
+const fn erf_ratio_64_fixed(xu:u64) -> u64 {
+	let x=xu as i64;
+	let x2=mults64hi(x,x)<<2;
+	let mut u=                       -674270619470i64;
+	u=(mults64hi(u,x2)<<2)         +7549899290572i64;
+	u=(mults64hi(u,x2)<<2)        -68820205985852i64;
+	u=(mults64hi(u,x2)<<2)       +555951948259304i64;
+	u=(mults64hi(u,x2)<<2)      -3942219785046172i64;
+	u=(mults64hi(u,x2)<<2)     +24091344560033059i64;
+	u=(mults64hi(u,x2)<<2)    -123898343532229526i64;
+	u=(mults64hi(u,x2)<<2)    +520373042837899826i64;
+	u=(mults64hi(u,x2)<<2)   -1734576809459705449i64;
+	u=(mults64hi(u,x2)<<2)+(5203730428379116615i64+1);
+	u as u64
+}
+
+// chebyfit of erfcx(y)*(1+2*y), with erfcx(y)=e^(y^2)*erfc(y), as a polynomial in t=(y-4)/(y+4)
+// over y in [1/4,inf). The function tends to 2/sqrt(pi) so one polynomial covers the half line.
+
+// t is a 1.63 signed fixed in the range [-15/17,1)
+// calculate erfcx(y)*(1+2*y)
+// result is 2.62 unsigned fixed in the range [1.12,1.29]
+const fn erfcx_64_fixed(t:i64) -> u64 {
+	let mut u=                          360423075i64;
+	u=(mults64hi(u,t)<<1)             -838154950i64;
+	u=(mults64hi(u,t)<<1)            -3353432198i64;
+	u=(mults64hi(u,t)<<1)            +9326314630i64;
+	u=(mults64hi(u,t)<<1)           +20240605355i64;
+	u=(mults64hi(u,t)<<1)           -70311563182i64;
+	u=(mults64hi(u,t)<<1)          -103579810425i64;
+	u=(mults64hi(u,t)<<1)          +511061152291i64;
+	u=(mults64hi(u,t)<<1)          +366164191223i64;
+	u=(mults64hi(u,t)<<1)         -3807141292336i64;
+	u=(mults64hi(u,t)<<1)         +1340332423400i64;
+	u=(mults64hi(u,t)<<1)        +26330725311845i64;
+	u=(mults64hi(u,t)<<1)        -51746103956183i64;
+	u=(mults64hi(u,t)<<1)       -112520923268901i64;
+	u=(mults64hi(u,t)<<1)       +694618046531138i64;
+	u=(mults64hi(u,t)<<1)       -918909514344204i64;
+	u=(mults64hi(u,t)<<1)      -3494612138197377i64;
+	u=(mults64hi(u,t)<<1)     +23205865630450866i64;
+	u=(mults64hi(u,t)<<1)     -74698863724826841i64;
+	u=(mults64hi(u,t)<<1)    +171404911739933498i64;
+	u=(mults64hi(u,t)<<1)    -305894820610575561i64;
+	u=(mults64hi(u,t)<<1)    +432266404629666298i64;
+	u=(mults64hi(u,t)<<1)    -465960448154687733i64;
+	u=(mults64hi(u,t)<<1)    +314042233002452322i64;
+	u=(mults64hi(u,t)<<1)     +70926126569930300i64;
+	u=(mults64hi(u,t)<<1)    -643888752410838821i64;
+	u=(mults64hi(u,t)<<1)+(5686186349354679083i64+15);
+	u as u64
+}
+
+// y*2^64 for y=x, or y=x/sqrt(2) with normal, where x=sig*2^exp2<2^8
+const fn erf_arg_fixed(sig:u64, exp2:i32, normal:bool) -> u128 {
+	let p=if normal {sig as u128*FRAC_1_SQRT_2_FIXED_64 as u128} else {(sig as u128)<<64};
+	if exp2<=-128 {0} else {p>>-exp2}
+}
+
+// erfcx(y)=g/d*4 for y*2^64 in [2^62,2^72)
+const fn erfcx_fixed(yf:u128) -> (u64,u128) {
+	let n=yf as i128-(4i128<<64);
+	let d=yf+(4u128<<64);
+	let shift=64-d.leading_zeros() as i32; // d>>shift is in [2^63,2^64)
+	let t=(((n>>shift)<<63)/(d>>shift) as i128) as i64;
+	(erfcx_64_fixed(t),(1u128<<64)+2*yf)
+}
+
+// erf(y)=v*2^e for y<1/2, with y*2^64 from erf_arg_fixed of x=sig*2^exp2
+// v keeps the full precision of x
+const fn erf_small_fixed(sig:u64, exp2:i32, yf:u128, normal:bool) -> (u128,i64) {
+	let u=erf_ratio_64_fixed((yf>>2) as u64);
+	let u=if normal {((u as u128*FRAC_1_SQRT_2_FIXED_64 as u128)>>64) as u64} else {u};
+	(sig as u128*u as u128,exp2 as i64-62)
+}
+
+// erfc(y)=n/d*2^e for 1/4<=y<2^8, with y*2^64 from erf_arg_fixed of x=sig*2^exp2
+// y^2 is exact so e^(-y^2) keeps its relative precision all the way to underflow
+const fn erfc_large_fixed(sig:u64, exp2:i32, yf:u128, normal:bool) -> (u128,u128,i64) {
+	let y2=(sig as u128*sig as u128)<<(2*exp2+if normal {99} else {100}); // y^2*2^100
+	let (m,k)=exp_fixed_100(y2,true);
+	let (g,d)=erfcx_fixed(yf);
+	((m>>62)*g as u128,d,k-61)
+}
+
+// u*2^shift truncated, zero when shifted out
+const fn shift_u128(u:u128, shift:i64) -> u128 {
+	if shift>=0 {u<<shift} else if shift>-128 {u>>-shift} else {0}
+}
+
+// erf(y), or erfc(y) with complement, for y=+-|x|, or y=+-|x|/sqrt(2) with the result halved with normal
+// erf and erfc near -1 and 2 are taken from the fixed values so there is only one rounding
+const fn erf_erfc(x:PseudoDouble, negative:bool, complement:bool, normal:bool) -> PseudoDouble {
+	let h=if normal {1} else {0};
+	if x.0==0 {
+		return if complement {PD_ONE.ldexp(-h)} else {PD_ZERO};
+	}
+	let (_,sig,exp2)=pd_to_sig_exp(x);
+	if exp2>-39 {
+		// |x|>=2^8, erfc has underflowed
+		if !complement {
+			return if negative {PD_NEG_ONE.ldexp(-h)} else {PD_ONE.ldexp(-h)};
+		}
+		return if negative {PD_ONE.ldexp(1-h)} else {PD_ZERO};
+	}
+	let yf=erf_arg_fixed(sig,exp2,normal);
+	if yf<(1u128<<63) {
+		// |y|<1/2, erf(y)=y*ratio(y^2)
+		let (v,e)=erf_small_fixed(sig,exp2,yf,normal);
+		if !complement {
+			return u128_scaled_to_pd(v,e-h as i64,false,negative);
+		}
+		let ef=shift_u128(v,e+126);
+		let r=if negative {(1u128<<126)+ef} else {(1u128<<126)-ef};
+		return u128_scaled_to_pd(r,-126-h as i64,false,false);
+	}
+	let (n,d,e)=erfc_large_fixed(sig,exp2,yf,normal);
+	if complement && !negative {
+		return ratio_u128_to_pd(n,d,e-h as i64,false);
+	}
+	let ef=shift_u128(n/d,e+126); // erfc(|y|)*2^126
+	if complement {
+		return u128_scaled_to_pd((1u128<<127)-ef,-126-h as i64,false,false);
+	}
+	u128_scaled_to_pd((1u128<<126)-ef,-126-h as i64,false,negative)
+}
+
+// |1-a| for 1/2<=a<=2, exact unlike the rounded subtraction
+const fn abs_one_minus_exact(a:PseudoDouble) -> PseudoDouble {
+	let (_,sig,exp2)=pd_to_sig_exp(a);
+	u128_scaled_to_pd((1u64<<-exp2).abs_diff(sig) as u128,exp2 as i64,false,false)
+}
+
+// x+c*2^e rounded once, for x>0 and |c*2^e| well below x
+const fn add_scaled(x:PseudoDouble, c:PseudoDouble, e:i64) -> PseudoDouble {
+	if c.0==0 {
+		return x;
+	}
+	let (_,sig,exp2)=pd_to_sig_exp(x);
+	let (cneg,csig,cexp)=pd_to_sig_exp(c);
+	let cv=shift_u128(csig as u128,cexp as i64+e-exp2 as i64+64) as i128;
+	let t=((sig as i128)<<64)+if cneg {-cv} else {cv};
+	u128_scaled_to_pd(t as u128,exp2 as i64-64,false,false)
+}
+
+// inverse of erf(y)=r, or of erf(x/sqrt(2))=r with normal, for 0<r<=1/2
+// Newton from a series, with the last step taking erf(y)-r from the unrounded fixed value
+const fn erf_inv_central(r:PseudoDouble, normal:bool) -> PseudoDouble {
+	// below 2^-32 the terms in y^2 are under the precision, and y^2 could underflow
+	let tiny=(r.0&EXP_MASK)<PSEUDO_DOUBLE_EXP_BIAS-30;
+	let z=r.const_div(consts::FRAC_2_SQRT_PI);
+	let mut y=z;
+	let mut i=if tiny {2} else {0};
+	if !tiny {
+		// erf_inv(r)=z+z^3/3+7*z^5/30+...
+		let z2=z.const_mul(z);
+		y=z.const_add(z.const_mul(z2).const_mul(PseudoDouble::pdc10(3333333333,-10).const_add(z2.const_mul(PseudoDouble::pdc10(2333333333,-10)))));
+	}
+	while i<2 {
+		y=y.const_sub(y.erf().const_sub(r).const_mul(y.const_mul(y).exp()).const_div(consts::FRAC_2_SQRT_PI));
+		i+=1;
+	}
+	let x=if normal {y.const_mul(consts::SQRT_2)} else {y};
+	let (_,sig,exp2)=pd_to_sig_exp(x);
+	let (v,e)=erf_small_fixed(sig,exp2,erf_arg_fixed(sig,exp2,normal),normal);
+	let (_,sr,er)=pd_to_sig_exp(r);
+	let shift=er as i64-e;
+	let rv=if shift>=0 {(sr as i128)<<shift} else {(sr as i128)>>-shift};
+	let res=v as i128-rv; // (erf(y)-r)*2^-e
+	if res==0 {
+		return x;
+	}
+	// dx=-(erf(y)-r)/(dy/dx*2/sqrt(pi)*e^(-y^2)), with erf(y)-r=c*2^e
+	let c=u128_to_pd_rounded(res.unsigned_abs(),res>0);
+	let f=if normal {consts::FRAC_1_SQRT_2PI.ldexp(1)} else {consts::FRAC_2_SQRT_PI};
+	if tiny {
+		return add_scaled(x,c.const_div(f),e);
+	}
+	let y2=if normal {x.const_mul(x).ldexp(-1)} else {x.const_mul(x)};
+	add_scaled(x,c.const_mul(y2.exp()).const_div(f),e)
+}
+
+// inverse of erfc(y)=q, or of erfc(x/sqrt(2))=q with normal, for 0<q<1/2
+// Newton on ln(erfc(y)) from the asymptotic y^2=-ln(q)-ln(-pi*ln(q))/2, with the last step
+// taking erfc(y)/q-1 from the unrounded fixed value
+const fn erf_inv_tail(q:PseudoDouble, normal:bool) -> PseudoDouble {
+	let lq=q.ln();
+	let l=lq.const_neg();
+	let mut y=l.const_sub(PD_PI.const_mul(l).ln().ldexp(-1)).sqrt();
+	let mut i=0;
+	while i<3 {
+		let (_,sig,exp2)=pd_to_sig_exp(y);
+		let (g,d)=erfcx_fixed(erf_arg_fixed(sig,exp2,false));
+		let ex=ratio_u128_to_pd(g as u128,d,2,false);
+		let f=ex.ln().const_sub(y.const_mul(y)).const_sub(lq); // ln(erfc(y))-ln(q)
+		y=y.const_add(f.const_mul(ex).const_div(consts::FRAC_2_SQRT_PI));
+		i+=1;
+	}
+	let x=if normal {y.const_mul(consts::SQRT_2)} else {y};
+	let (_,sig,exp2)=pd_to_sig_exp(x);
+	let yf=erf_arg_fixed(sig,exp2,normal);
+	let (n,d,e)=erfc_large_fixed(sig,exp2,yf,normal);
+	let (_,sq,eq)=pd_to_sig_exp(q);
+	let res=n as i128-shift_u128(sq as u128*d,eq as i64-e) as i128; // (erfc(y)-q)*d*2^-e
+	if res==0 {
+		return x;
+	}
+	// dx=(erfc(y)-q)/erfc(y)*erfcx(y)/(dy/dx*2/sqrt(pi))
+	let rho=ratio_u128_to_pd(res.unsigned_abs(),n,0,res<0);
+	let (g,d)=erfcx_fixed(yf);
+	let ex=ratio_u128_to_pd(g as u128,d,2,false);
+	let f=if normal {consts::FRAC_1_SQRT_2PI.ldexp(1)} else {consts::FRAC_2_SQRT_PI};
+	add_scaled(x,rho.const_mul(ex).const_div(f),0)
+}
+
+// ln(pi)*2^110
+const LN_PI_FIXED_110:u128=1485944347541693088886597785133328;
+
+// (ln(2*pi)-1)/2*2^110
+const HALF_LN_2PI_MINUS_HALF_FIXED_110:u128=543813307469452712569537782894854;
+
+// pi as a 2.62 unsigned fixed
+const PI_FIXED_62:u64=14488038916154245685;
+
+// chebyfit of lnΓ(1+x)/x over x in [-1/4,1/4]
+
+// x is a 1.63 signed fixed in the range [-1/4,1/4]
+// calculate lnΓ(1+x)/x
+// result is 2.62 signed fixed in the range [-0.82,-0.39]
+const fn lgamma1_ratio_64_fixed(x:i64) -> i64 {
+	let mut u=                289405323734856797i64;
+	u=(mults64hi(u,x)<<1)    -302802493187087052i64;
+	u=(mults64hi(u,x)<<1)    +218020726360940493i64;
+	u=(mults64hi(u,x)<<1)    -229621672096914465i64;
+	u=(mults64hi(u,x)<<1)    +257290621524353093i64;
+	u=(mults64hi(u,x)<<1)    -272408704173226415i64;
+	u=(mults64hi(u,x)<<1)    +288177307369741967i64;
+	u=(mults64hi(u,x)<<1)    -307395225119125342i64;
+	u=(mults64hi(u,x)<<1)    +329428263746968970i64;
+	u=(mults64hi(u,x)<<1)    -354790636823806271i64;
+	u=(mults64hi(u,x)<<1)    +384401698949597062i64;
+	u=(mults64hi(u,x)<<1)    -419451325328179774i64;
+	u=(mults64hi(u,x)<<1)    +461627269230330802i64;
+	u=(mults64hi(u,x)<<1)    -513438677873023100i64;
+	u=(mults64hi(u,x)<<1)    +578811188120258377i64;
+	u=(mults64hi(u,x)<<1)    -664312894880166921i64;
+	u=(mults64hi(u,x)<<1)    +781944462482983092i64;
+	u=(mults64hi(u,x)<<1)    -956397046102814000i64;
+	u=(mults64hi(u,x)<<1)   +1247833731081193499i64;
+	u=(mults64hi(u,x)<<1)   -1847836337885075161i64;
+	u=(mults64hi(u,x)<<1)   +3792959718659434054i64;
+	u=(mults64hi(u,x)<<1)+(-2661937411443667433i64+1);
+	u
+}
+
+// chebyfit of lnΓ(2+x)/x over x in [-1/4,1/4]
+
+// x is a 1.63 signed fixed in the range [-1/4,1/4]
+// calculate lnΓ(2+x)/x
+// result is 2.62 signed fixed in the range [0.33,0.50]
+const fn lgamma2_ratio_64_fixed(x:i64) -> i64 {
+	let mut u=                    -2199282361656i64;
+	u=(mults64hi(u,x)<<1)         +4673980112895i64;
+	u=(mults64hi(u,x)<<1)         -9390115038870i64;
+	u=(mults64hi(u,x)<<1)        +20145729828024i64;
+	u=(mults64hi(u,x)<<1)        -43532779486875i64;
+	u=(mults64hi(u,x)<<1)        +94574564594514i64;
+	u=(mults64hi(u,x)<<1)       -207185670522379i64;
+	u=(mults64hi(u,x)<<1)       +458666763487195i64;
+	u=(mults64hi(u,x)<<1)      -1029119680018873i64;
+	u=(mults64hi(u,x)<<1)      +2350435822358843i64;
+	u=(mults64hi(u,x)<<1)      -5500606538021397i64;
+	u=(mults64hi(u,x)<<1)     +13330126078389828i64;
+	u=(mults64hi(u,x)<<1)     -34059842417317853i64;
+	u=(mults64hi(u,x)<<1)     +94912226474346599i64;
+	u=(mults64hi(u,x)<<1)    -310607665075945888i64;
+	u=(mults64hi(u,x)<<1)   +1487116709445740102i64;
+	u=(mults64hi(u,x)<<1)+(1949748606983720471i64+1);
+	u
+}
+
+// Stirling series lnΓ(z)=(z-1/2)*ln(z)-z+ln(2*pi)/2+sum B_2k/(2k*(2k-1))*z^(1-2k), with the terms
+// scaled by 256^(k-1) so the sum is a polynomial in w=(16/z)^2. The first term left out is below
+// 2^-75 for z>=16.
+
+// w is a 2.62 unsigned fixed in the range (0,1]
+// calculate sum B_2k/(2k*(2k-1))*(w/256)^(k-1)
+// result is 2.62 unsigned fixed in the range [0.0833,1/12)
+const fn lgamma_series_64_fixed(w:i64) -> u64 {
+	let mut u=                               -2i64;
+	u=(mults64hi(u,w)<<2)                  +105i64;
+	u=(mults64hi(u,w)<<2)                 -8043i64;
+	u=(mults64hi(u,w)<<2)               +903823i64;
+	u=(mults64hi(u,w)<<2)            -163617802i64;
+	u=(mults64hi(u,w)<<2)          +55848209665i64;
+	u=(mults64hi(u,w)<<2)       -50039995859672i64;
+	u=(mults64hi(u,w)<<2)   +384307168202282325i64;
+	u as u64
+}
+
+// the same for ψ(z)=ln(z)-1/(2z)-sum B_2k/(2k)*z^-2k
+
+// w is a 2.62 unsigned fixed in the range (0,1]
+// calculate sum B_2k/(2k)*(w/256)^(k-1)
+// result is 2.62 unsigned fixed in the range [0.0833,1/12)
+const fn digamma_series_64_fixed(w:i64) -> u64 {
+	let mut u=                                1i64;
+	u=(mults64hi(u,w)<<2)                   -28i64;
+	u=(mults64hi(u,w)<<2)                 +1365i64;
+	u=(mults64hi(u,w)<<2)                -88470i64;
+	u=(mults64hi(u,w)<<2)              +8134408i64;
+	u=(mults64hi(u,w)<<2)           -1145324612i64;
+	u=(mults64hi(u,w)<<2)         +279241048324i64;
+	u=(mults64hi(u,w)<<2)      -150119987579017i64;
+	u=(mults64hi(u,w)<<2)   +384307168202282325i64;
+	u as u64
+}
+
+// u*2^shift truncated towards minus infinity
+const fn shift_i128(u:i128, shift:i64) -> i128 {
+	if shift>=0 {u<<shift} else if shift>-128 {u>>-shift} else if u<0 {-1} else {0}
+}
+
+// a*2^ea+b*2^eb at the coarser of the two scales
+const fn add_fixed(a:i128, ea:i64, b:i128, eb:i64) -> (i128,i64) {
+	if ea>=eb {
+		return (a+shift_i128(b,eb-ea),ea);
+	}
+	(shift_i128(a,ea-eb)+b,eb)
+}
+
+// the high 128 bits of a*b
+const fn mul_u128_hi(a:u128, b:u128) -> u128 {
+	let (ah,al)=(a>>64,a as u64 as u128);
+	let (bh,bl)=(b>>64,b as u64 as u128);
+	let mid=((al*bl)>>64)+(ah*bl) as u64 as u128+(al*bh) as u64 as u128;
+	ah*bh+((ah*bl)>>64)+((al*bh)>>64)+(mid>>64)
+}
+
+// n/d as q*2^e with q in (2^63,2^65), truncated, d nonzero
+const fn ratio_u128_fixed(n:u128, d:u128) -> (u128,i64) {
+	if n==0 {
+		return (0,0);
+	}
+	let ln=n.leading_zeros() as i64;
+	let ld=d.leading_zeros() as i64;
+	((n<<ln)/((d<<ld)>>64),ld-ln-64)
+}
+
+// 2^174/t truncated, for 2^62<=t<2^71
+const fn recip_fixed_174(t:u128) -> u128 {
+	let q=(1u128<<127)/t;
+	let r=(1u128<<127)%t;
+	(q<<47)+(r<<47)/t
+}
+
+// sig*2^exp2 as s*2^e with s in [2^62,2^63), truncated, sig nonzero
+const fn normalize_63(sig:u128, exp2:i64) -> (u64,i64) {
+	let shift=65-sig.leading_zeros() as i64;
+	((if shift>=0 {sig>>shift} else {sig<<-shift}) as u64,exp2+shift)
+}
+
+// 16/z as a 2.62 unsigned fixed for z=s*2^e>=16, s in [2^62,2^63)
+const fn sixteen_over(s:u64, e:i64) -> i64 {
+	shift_u128((1u128<<127)/s as u128,-61-e) as i64
+}
+
+// for |x|=sig*2^exp2 the distance to the nearest integer as f*2^exp2, zero for an integer, whether
+// that integer is above |x|, and whether the integer part of |x| is even
+const fn dist_to_integer(sig:u64, exp2:i32) -> (u64,bool,bool) {
+	if exp2>=0 {
+		return (0,false,true);
+	}
+	let r=sig&((1u64<<-exp2)-1);
+	let even=(sig>>-exp2)&1==0;
+	if r>(1u64<<(-exp2-1)) {
+		return ((1u64<<-exp2)-r,true,even);
+	}
+	(r,false,even)
+}
+
+// a*2^ea*b*2^eb for nonzero a and b as v*2^e with v<2^125
+const fn mul_fixed(a:u128, ea:i64, b:u128, eb:i64) -> (i128,i64) {
+	let la=a.leading_zeros();
+	let lb=b.leading_zeros();
+	((mul_u128_hi(a<<la,b<<lb)>>3) as i128,ea+eb+131-la as i64-lb as i64)
+}
+
+// the series part of the Stirling sum for z=s*2^e>=16, s in [2^62,2^63), times 2^110
+const fn lgamma_series_110(s:u64, e:i64) -> i128 {
+	let v=sixteen_over(s,e);
+	(v as i128*lgamma_series_64_fixed(mults64hi(v,v)<<2) as i128)>>18
+}
+
+// lnΓ(z) for z=sig*2^exp2>=16 as v*2^f with f>=-110, z is kept to 127 bits
+const fn lgamma_stirling(sig:u128, exp2:i64) -> (i128,i64) {
+	let lz=sig.leading_zeros() as i64-1;
+	let s=if lz>=0 {sig<<lz} else {sig>>1}; // [2^126,2^127)
+	let e=exp2-lz;
+	let (l,_)=ln_fixed(s,e); // ln(z)*2^110
+	// z-1/2=w*2^(e-1), above 2^126 the 1/2 is below the precision
+	let w=if e<=0 {(s<<1)-(1u128<<-e)} else {s<<1};
+	let (t,te)=mul_fixed(w,e-1,(l-(1i128<<110)) as u128,-110);
+	let (s,e)=normalize_63(s,e);
+	add_fixed(t,te,HALF_LN_2PI_MINUS_HALF_FIXED_110 as i128+lgamma_series_110(s,e),-110)
+}
+
+// lnΓ(a+b)-lnΓ(a) for a=sa*2^ea>=16 and b=sb*2^eb<2a, with a+b=sum*2^se, as v*2^e
+// (a-1/2)*ln(1+b/a)+b*(ln(a+b)-1) and the change in the series, so the large terms of the two
+// Stirling sums cancel exactly
+const fn lgamma_diff(sa:u64, ea:i64, sb:u64, eb:i64, sum:u128, se:i64) -> (i128,i64) {
+	let (q,qe)=ratio_u128_fixed(sb as u128,sa as u128);
+	let qe=qe+eb-ea; // b/a=q*2^qe
+	let r=shift_u128(q,qe+63);
+	let (l,le)=if r<=(1u128<<62) {
+		// b/a<=1/2, ln(1+b/a) keeps its relative precision
+		((q>>2) as i128*ln1p_ratio_64_fixed(r as i64) as i128,qe-60)
+	} else {
+		let (q,qe)=ratio_u128_fixed(sum,sa as u128);
+		ln_fixed(q,qe+se-ea)
+	};
+	let w=if ea<=0 {((sa as u128)<<1)-(1u128<<-ea)} else {(sa as u128)<<1};
+	let (d1,e1)=mul_fixed(w,ea-1,l as u128,le);
+	let (ls,_)=ln_fixed(sum,se);
+	let (d2,e2)=mul_fixed(sb as u128,eb,(ls-(1i128<<110)) as u128,-110);
+	let (s1,e1s)=normalize_63(sum,se);
+	let (s2,e2s)=normalize_63(sa as u128,ea);
+	let (v,e)=add_fixed(d1,e1,d2,e2);
+	add_fixed(v,e,lgamma_series_110(s1,e1s)-lgamma_series_110(s2,e2s),-110)
+}
+
+// lnΓ(x)=lnΓ(1+x)-ln|x| for x=+-s*2^e with |x|<1/4, s in [2^62,2^63), as v*2^-110
+const fn lgamma_small(s:u64, e:i64, negative:bool) -> (i128,i64) {
+	let xf=if e<=-127 {0} else {(s>>(-e-63)) as i64}; // |x| as 1.63 fixed
+	let a=s as i128*lgamma1_ratio_64_fixed(if negative {-xf} else {xf}) as i128; // +-lnΓ(1+x)*2^(62-e)
+	let (l,_)=ln_fixed(s as u128,e);
+	add_fixed(if negative {-a} else {a},e-62,-l,-110)
+}
+
+// lnΓ(x) for x=sig*2^exp2>0 as v*2^e
+// near 1 and 2 the argument of the ratio kernel is exact and the result keeps its relative precision,
+// elsewhere the error is about 2^-60 absolute, or relative once lnΓ(x) is large
+const fn lgamma_fixed(sig:u128, exp2:i64) -> (i128,i64) {
+	let (s,e)=normalize_63(sig,exp2);
+	if e>=-58 {
+		// x>=16
+		return lgamma_stirling(sig,exp2);
+	}
+	if e<=-65 {
+		// x<1/4
+		return lgamma_small(s,e,false);
+	}
+	let xf=s>>(-e-58); // x*2^58
+	let one=1u64<<58;
+	if xf.abs_diff(one)<=one/4 || xf.abs_diff(2*one)<=one/4 {
+		// lnΓ(1+t)=t*ratio(t) or lnΓ(2+t)=t*ratio(t), with t exact
+		let two=xf>one+one/2;
+		let t=s as i128-(if two {2i128} else {1i128}<<-e); // t*2^-e
+		let tf=(t<<(e+63)) as i64;
+		let u=if two {lgamma2_ratio_64_fixed(tf)} else {lgamma1_ratio_64_fixed(tf)};
+		return (t*u as i128,e-62);
+	}
+	// shift to z=x+n in [16,17), lnΓ(x)=lnΓ(z)-ln(x*(x+1)*...*(x+n-1)), the product kept to 127 bits
+	let n=16-(xf>>58);
+	let mut p=1u128<<127;
+	let mut pe=-127i64;
+	let mut i=0;
+	while i<n {
+		let t=s as u128+((i as u128)<<-e);
+		let lt=t.leading_zeros();
+		p=mul_u128_hi(p,t<<lt);
+		pe+=e+128-lt as i64;
+		if p<(1u128<<127) {
+			p<<=1;
+			pe-=1;
+		}
+		i+=1;
+	}
+	let (v,f)=lgamma_stirling(s as u128+((n as u128)<<-e),e);
+	let (lp,lpe)=ln_fixed(p,pe);
+	add_fixed(v,f,-lp,lpe)
+}
+
+// ln|Γ(x)| for x=-sig*2^exp2<=-1/4 at distance f*2^exp2>0 from the nearest integer, as v*2^e
+// ln|Γ(x)|=ln(pi)-ln(sin(pi*f))-lnΓ(1-x), with 1-x exact
+const fn lgamma_reflect(sig:u64, exp2:i32, f:u64) -> (i128,i64) {
+	let g=sin_rev_ratio_64_fixed(f<<(exp2+63)); // sin(pi*f)=f*g*2^(exp2-61)
+	let (ls,lse)=ln_fixed(f as u128*g as u128,exp2 as i64-61);
+	let (lg,lge)=lgamma_fixed((sig+(1u64<<-exp2)) as u128,exp2 as i64);
+	let (v,e)=add_fixed(LN_PI_FIXED_110 as i128,-110,-ls,lse);
+	add_fixed(v,e,-lg,lge)
+}
+
+// +-e^(v*2^e) rounded, underflowing to zero and overflowing like exp
+const fn exp_fixed_to_pd(v:i128, e:i64, neg:bool) -> PseudoDouble {
+	let a=v.unsigned_abs();
+	if 128-a.leading_zeros() as i64+e>15 {
+		// |v*2^e|>=2^15
+		if v<0 {
+			return PD_ZERO;
+		}
+		if cfg!(feature="panic_on_pseudodouble_overflow") {
+			panic!("Overflow in PseudoDouble exp");
+		}
+		return if neg {PseudoDouble::MIN} else {PseudoDouble::MAX};
+	}
+	let (m,k)=exp_fixed_100(shift_u128(a,e+100),v<0);
+	u128_scaled_to_pd(m,k-125,false,neg)
+}
+
+// ψ(z) for z=s*2^e>=16, s in [2^62,2^63), as v*2^-110
+const fn digamma_stirling(s:u64, e:i64) -> (i128,i64) {
+	let (l,_)=ln_fixed(s as u128,e);
+	let v=sixteen_over(s,e);
+	let w=mults64hi(v,v)<<2;
+	let c=((v as i128)<<43)+((w as i128*digamma_series_64_fixed(w) as i128)>>22);
+	(l-c,-110)
+}
+
+// ψ(x) for x=sig*2^exp2>=1/4 as v*2^e, with an error of about 2^-60 absolute, or relative for large x
+const fn digamma_fixed(sig:u128, exp2:i64) -> (i128,i64) {
+	let (s,e)=normalize_63(sig,exp2);
+	if e>=-58 {
+		return digamma_stirling(s,e);
+	}
+	// shift to z=x+n in [16,17), ψ(x)=ψ(z)-sum 1/(x+i)
+	let n=16-(s>>(-e-58)>>58);
+	let mut r=0u128;
+	let mut i=0;
+	while i<n {
+		r+=recip_fixed_174(s as u128+((i as u128)<<-e))>>(64+e); // 1/(x+i)*2^110
+		i+=1;
+	}
+	let (z,ze)=normalize_63(s as u128+((n as u128)<<-e),e);
+	let (v,f)=digamma_stirling(z,ze);
+	add_fixed(v,f,-(r as i128),-110)
+}
+
+// correctly rounded conversion of a big integer, overflowing like exp. c is used as scratch space
+const fn big_integer_to_pd(c:&mut BigUint<BIG_LIMBS>) -> PseudoDouble {
+	match big_ratio_to_pd(c,&mut BigUint::from_u64(1),0,false,false) {
+		Ok(x) => x,
+		Err(_) => {
+			if cfg!(feature="panic_on_pseudodouble_overflow") {
+				panic!("Overflow in PseudoDouble exp");
+			}
+			PseudoDouble::MAX
+		}
+	}
+}
+
+// 1/(2*pi)*2^256 truncated, most significant limb first
+const INV_TAU_FIXED_256:[u64;4]=[2935890503282001226,9154082963658192752,3952090531849364496,9193070505571053912];
+
+// 2/pi as a 0.64 unsigned fixed
+const TWO_OVER_PI_FIXED_64:u64=11743562013128004906;
+
+// Euler's constant*2^110
+const EULER_GAMMA_FIXED_110:u128=749268770891330209180221291118677;
+
+// a*2^ea*b*2^eb for signed a and b as v*2^e with |v|<2^125
+const fn mul_fixed_signed(a:i128, ea:i64, b:i128, eb:i64) -> (i128,i64) {
+	if a==0 || b==0 {
+		return (0,ea+eb);
+	}
+	let (v,e)=mul_fixed(a.unsigned_abs(),ea,b.unsigned_abs(),eb);
+	(if (a<0)!=(b<0) {-v} else {v},e)
+}
+
+// x/(2*pi) mod 1 as a 0.128 unsigned fixed for x=sig*2^exp2>=1/2
+// it is within 2^-127 up to x=2^128, above that the stored bits of 1/(2*pi) run out and the
+// fraction is only deterministic
+const fn rev_fraction_128(sig:u64, exp2:i32) -> u128 {
+	let mut p=[0u64;5]; // sig*INV_TAU_FIXED_256, least significant limb first
+	let mut carry=0u128;
+	let mut i=0;
+	while i<4 {
+		let t=sig as u128*INV_TAU_FIXED_256[3-i] as u128+carry;
+		p[i]=t as u64;
+		carry=t>>64;
+		i+=1;
+	}
+	p[4]=carry as u64;
+	let s=128-exp2 as i64; // the bit of p worth 2^-128 revolutions
+	if s<0 {
+		return if s<=-128 {0} else {(((p[1] as u128)<<64)|p[0] as u128)<<-s};
+	}
+	let (i,o)=((s>>6) as usize,s&63);
+	if o==0 {
+		return ((p[i+1] as u128)<<64)|p[i] as u128;
+	}
+	let lo=(p[i]>>o)|(p[i+1]<<(64-o));
+	let hi=(p[i+1]>>o)|(p[i+2]<<(64-o));
+	((hi as u128)<<64)|lo as u128
+}
+
+// sin and cos of r revolutions for a 0.64 unsigned fixed r, as 2.62 signed fixed
+const fn sin_cos_rev_fixed(r:u64) -> (i64,i64) {
+	let f=r&((1u64<<62)-1);
+	let s=sin_rev_64_fixed(f) as i64;
+	let c=sin_rev_64_fixed((1u64<<62)-f) as i64;
+	match r>>62 {
+		0 => (s,c),
+		1 => (c,-s),
+		2 => (-s,-c),
+		_ => (-c,s),
+	}
+}
+
+// sqrt(2/(pi*x)) as a*2^e for x=sig*2^exp2, a in [2^61,2^63)
+const fn bessel_amplitude(sig:u64, exp2:i32) -> (u128,i64) {
+	let n=(TWO_OVER_PI_FIXED_64 as u128)<<64;
+	let d=sig as u128;
+	let q=((n/d)<<44)+(((n%d)<<44)/d); // 2/(pi*x)*2^(172+exp2)
+	let e=-172-exp2 as i64;
+	let (q,e)=if e&1!=0 {(q>>1,e+1)} else {(q,e)};
+	(q.isqrt(),e/2)
+}
+
+// the power series for J_nu and Y_nu, nu=0 or 1, at u=x^2/4=un*2^ue with un in [2^127,2^128)
+// with t_k=(-u)^k/(k!(k+nu)!) and the harmonic numbers H_k, returns the sums of t_k, H_k*t_k and
+// t_k/(k+1) as v*2^-84. Below x=25 the terms stay under 2^36, so the error is about 2^-78 absolute.
+const fn bessel_series_fixed(un:u128, ue:i64, nu:u64) -> (i128,i128,i128) {
+	let mut t=1u128<<84;
+	let mut w=0u128; // H_k*t_k, H_k=H_(k-1)+1/k
+	let mut s=t as i128;
+	let mut h=0i128;
+	let mut r=t as i128;
+	let mut k=1u64;
+	while w!=0 || k==1 {
+		let d=(k*(k+nu)) as u128;
+		t=shift_u128(mul_u128_hi(t,un),ue+128)/d;
+		w=shift_u128(mul_u128_hi(w,un),ue+128)/d+t/k as u128;
+		let (tk,wk)=(t as i128,w as i128);
+		if k&1==1 {
+			s-=tk;
+			h-=wk;
+			r-=tk/(k+1) as i128;
+		} else {
+			s+=tk;
+			h+=wk;
+			r+=tk/(k+1) as i128;
+		}
+		k+=1;
+	}
+	(s,h,r)
+}
+
+// the Hankel asymptotic sums P and Q for J_nu and Y_nu, nu=0 or 1, at x=sig*2^exp2>=25, as 2.62 signed
+// fixed. The terms a_k/x^k are summed up to the smallest one, which is below 2^-75 for x>=25.
+const fn bessel_hankel_fixed(sig:u64, exp2:i32, nu:u64) -> (i64,i64) {
+	let rx=recip_fixed_174((sig as u128)<<16)<<15; // 1/x=rx*2^(-173-exp2)
+	let mu=4*nu*nu;
+	let mut t=1u128<<120;
+	let mut negative=false;
+	let mut p=1i128<<120;
+	let mut q=0i128;
+	let mut k=1u64;
+	loop {
+		// t_k=t_(k-1)*(mu-(2k-1)^2)/(8k*x)
+		let c=(2*k-1)*(2*k-1);
+		let a=shift_u128(mul_u128_hi(t,rx),-45-exp2 as i64);
+		let next=a*c.abs_diff(mu) as u128/(8*k) as u128;
+		if next==0 || next>=t {
+			break;
+		}
+		t=next;
+		negative=negative!=(c>mu);
+		let v=if negative {-(t as i128)} else {t as i128};
+		// P=1-t_2+t_4-..., Q=t_1-t_3+...
+		match k&3 {
+			0 => p+=v,
+			1 => q+=v,
+			2 => p-=v,
+			_ => q-=v,
+		}
+		k+=1;
+	}
+	((p>>58) as i64,(q>>58) as i64)
+}
+
+// J_nu(x), or Y_nu(x) with y, for nu=0 or 1 and x=sig*2^exp2>0, as v*2^e
+// below 25 from the power series, with the log term ln(x/2)+γ for Y, above from the Hankel form
+// sqrt(2/(pi*x))*(P*cos(χ)-Q*sin(χ)) for J and sqrt(2/(pi*x))*(P*sin(χ)+Q*cos(χ)) for Y,
+// χ=x-(2*nu+1)*pi/4 reduced in revolutions
+const fn bessel_fixed(sig:u64, exp2:i32, nu:u64, y:bool) -> (i128,i64) {
+	if exp2>=-41 || (exp2==-42 && sig>=25u64<<42) {
+		let (p,q)=bessel_hankel_fixed(sig,exp2,nu);
+		let r=((rev_fraction_128(sig,exp2)>>64) as u64).wrapping_sub((2*nu+1)<<61);
+		let (s,c)=sin_cos_rev_fixed(r);
+		let v=if y {p as i128*s as i128+q as i128*c as i128} else {p as i128*c as i128-q as i128*s as i128};
+		let (a,ae)=bessel_amplitude(sig,exp2);
+		return mul_fixed_signed(v,-124,a as i128,ae);
+	}
+	let s2=sig as u128*sig as u128;
+	let lz=s2.leading_zeros() as i64;
+	let (un,ue)=(s2<<lz,2*exp2 as i64-2-lz); // x^2/4
+	let (s,h,r)=bessel_series_fixed(un,ue,nu);
+	if !y {
+		return if nu==0 {(s,-84)} else {mul_fixed_signed(s,-84,sig as i128,exp2 as i64-1)};
+	}
+	let (l,le)=ln_fixed(sig as u128,exp2 as i64-1);
+	let (l,le)=add_fixed(l,le,EULER_GAMMA_FIXED_110 as i128,-110); // ln(x/2)+γ
+	let (a,ae)=mul_fixed_signed(l,le,s,-84);
+	if nu==0 {
+		// Y0=2/pi*((ln(x/2)+γ)*sum t_k-sum H_k*t_k)
+		let (v,e)=add_fixed(a,ae,-h,-84);
+		return mul_fixed_signed(v,e,TWO_OVER_PI_FIXED_64 as i128,-64);
+	}
+	// Y1=2/(pi*x)*(2u*(ln(x/2)+γ)*sum t_k-1-u*sum (2H_k+1/(k+1))*t_k), the bracket stays small as x->0
+	let (a,ae)=mul_fixed_signed(a,ae+1,(un>>1) as i128,ue+1);
+	let (b,be)=mul_fixed_signed(2*h+r,-84,(un>>1) as i128,ue+1);
+	let (v,e)=add_fixed(a,ae,-b,be);
+	let (v,e)=add_fixed(v,e,-(1i128<<84),-84);
+	let (n,ne)=mul_fixed_signed(v,e,TWO_OVER_PI_FIXED_64 as i128,-64);
+	let (q,qe)=ratio_u128_fixed(n.unsigned_abs(),sig as u128);
+	(if n<0 {-(q as i128)} else {q as i128},qe+ne-exp2 as i64)
+}
+
+// 2k/x*b-a for 1/x=r*2^re, one step of the three term recurrence of J and Y, as v*2^e with |v|<2^125
+const fn bessel_step(k:u64, r:i128, re:i64, b:i128, be:i64, a:i128, ae:i64) -> (i128,i64) {
+	let (v,e)=mul_fixed_signed(b,be+1,k as i128,0);
+	let (v,e)=mul_fixed_signed(v,e,r,re);
+	if a==0 {
+		return (v,e);
+	}
+	let (v,e)=add_fixed(v,e,-a,ae);
+	if v.unsigned_abs()>=1u128<<125 {(v>>2,e+2)} else {(v,e)}
+}
+
+// J_m(x) for u=x^2/4<=m+1, x=sig*2^exp2, as v*2^e
+// (x/2)^m/m! times sum (-u)^k*m!/(k!(m+k)!), whose terms decrease from 1 so there is no cancellation
+const fn bessel_jn_series(m:u64, sig:u64, exp2:i32) -> (i128,i64) {
+	let s2=sig as u128*sig as u128;
+	let lz=s2.leading_zeros() as i64;
+	let (un,ue)=(s2<<lz,2*exp2 as i64-2-lz);
+	let mut t=1u128<<124;
+	let mut s=t as i128;
+	let mut k=1u64;
+	while t!=0 {
+		// u/(k(m+k))=qn*2^(ue-lq)
+		let d=(k*(m+k)) as u128;
+		let lq=(un/d).leading_zeros();
+		let qn=((un/d)<<lq)+(((un%d)<<lq)/d);
+		t=shift_u128(mul_u128_hi(t,qn),ue+128-lq as i64);
+		s+=if k&1==1 {-(t as i128)} else {t as i128};
+		k+=1;
+	}
+	// (x/2)^m/m!=e^(m*ln(x/2)-lnΓ(m+1))
+	let (l,le)=ln_fixed(sig as u128,exp2 as i64-1);
+	let (a,ae)=mul_fixed_signed(l,le,m as i128,0);
+	let (g,ge)=lgamma_fixed(m as u128+1,0);
+	let (v,e)=add_fixed(a,ae,-g,ge);
+	if 128-v.unsigned_abs().leading_zeros() as i64+e>15 {
+		// below e^-2^15, underflowed
+		return (0,0);
+	}
+	let (p,pk)=exp_fixed_100(shift_u128(v.unsigned_abs(),e+100),v<0);
+	mul_fixed_signed(s,-124,p as i128,pk-125)
+}
+
+// J_m(x) for m>=2 and x=sig*2^exp2>0 as v*2^e
+// the series while x^2/4<=m+1, forward recurrence from J0 and J1 for x>=m, and in between Miller's
+// backward recurrence normalized by the larger of J0 and J1
+const fn bessel_jn_fixed(m:u64, sig:u64, exp2:i32) -> (i128,i64) {
+	if exp2<=-46 || (exp2<0 && (sig as u128*sig as u128)>>(-2*exp2)<4*(m as u128+1)) {
+		return bessel_jn_series(m,sig,exp2);
+	}
+	let (r,re)=(recip_fixed_174((sig as u128)<<16) as i128,-158-exp2 as i64); // 1/x
+	let (j0,j0e)=bessel_fixed(sig,exp2,0,false);
+	let (j1,j1e)=bessel_fixed(sig,exp2,1,false);
+	if exp2>=0 || sig>>-exp2>=m {
+		let (mut a,mut ae,mut b,mut be)=(j0,j0e,j1,j1e);
+		let mut k=1;
+		while k<m {
+			let (c,ce)=bessel_step(k,r,re,b,be,a,ae);
+			(a,ae,b,be)=(b,be,c,ce);
+			k+=1;
+		}
+		return (b,be);
+	}
+	let mut k=(m+(160*m).isqrt()+1)&!1;
+	let (mut a,mut ae,mut b,mut be)=(0i128,0i64,1i128<<120,-120i64); // b_(k+1) and b_k
+	let (mut v,mut ve)=(0i128,0i64);
+	while k>0 {
+		let (c,ce)=bessel_step(k,r,re,b,be,a,ae);
+		(a,ae,b,be)=(b,be,c,ce);
+		k-=1;
+		if k==m {
+			(v,ve)=(b,be);
+		}
+	}
+	// b=b_0 and a=b_1 are J0 and J1 up to the same factor
+	let use_j0=128-j0.unsigned_abs().leading_zeros() as i64+j0e>=128-j1.unsigned_abs().leading_zeros() as i64+j1e;
+	let (j,je,d,de)=if use_j0 {(j0,j0e,b,be)} else {(j1,j1e,a,ae)};
+	let (n,ne)=mul_fixed_signed(v,ve,j,je);
+	let (q,qe)=ratio_u128_fixed(n.unsigned_abs(),d.unsigned_abs());
+	(if (n<0)!=(d<0) {-(q as i128)} else {q as i128},qe+ne-de)
+}
+
+// ./lolremez --stats --debug --long-double -d 15 -r "-1:1" "sin(x*pi/2)"
+// long double f(long double x) {
+//     x2 = x * x;
+//     long double u = -6.4348418488777433292e-10;
+//     u = u * x2 + 5.6877693955975479363e-8;
+//     u = u * x2 + -3.5988024751262635033e-6;
+//     u = u * x2 + 1.6044116327434520208e-4;
+//     u = u * x2 + -4.6817541288738726714e-3;
+//     u = u * x2 + 7.9692626245142861354e-2;
+//     u = u * x2 + -6.4596409750617316605e-1;
+//     u = u * x2 + 1.5707963267948950979l;
+//     return u * x;
+// }
+
+// x is a 2.62 unsigned fixed in the range [0,1]
+// calculate sin_rev_64_fixed(x)
+// result is 2.62 unsigned fixed in the range [0,1]
+const fn sin_rev_64_fixed(xu:u64) -> u64 {
+	(mults64hi(sin_rev_ratio_64_fixed(xu) as i64,xu as i64)<<2) as u64
+}
+
+// x is a 2.62 unsigned fixed in the range [0,1]
+// calculate sin(x*pi/2)/x
+// result is 2.62 unsigned fixed in the range [1,pi/2]
+const fn sin_rev_ratio_64_fixed(xu:u64) -> u64 {
+	let x=xu as i64;
+	let x2=mults64hi(x,x)<<2;
+    let mut u=                      -2967547018i64;
+    u=mults64hi(u<<2,x2)          +262302065977i64;
+    u=mults64hi(u<<2,x2)        -16596547057622i64;
+    u=mults64hi(u<<2,x2)       +739904269452523i64;
+    u=mults64hi(u<<2,x2)     -21590780057842334i64;
+    u=mults64hi(u<<2,x2)    +367517370226484839i64;
+    u=mults64hi(u<<2,x2)   -2978983596875284700i64;
+    u=(mults64hi(u,x2)<<2)+(7244019458077115826i64+415);
+    u as u64
+}
+
+// minimax fit of g(w)=tan(2*pi*y)*(1-16*y^2)/y with w=16*y^2 over y in [0,1/8]
+// the factor (1-16*y^2) removes the pole at y=1/4 so a degree 8 polynomial is enough
+
+// x is 1.63 unsigned fixed 4*y in the range [0,1/2]
+// returns (g,1-16*y^2), g is 4.60 fixed in the range [6,2*pi], 1-16*y^2 is 1.63 fixed in [3/4,1]
+// tan(2*pi*y)=y*g/(1-16*y^2)
+const fn tan_rev_64_fixed(xu:u64) -> (u64,u64) {
+	let x=xu as i64;
+	let w=mults64hi(x,x)<<1;
+	let mut u=                      -137613486382i64;
+	u=(mults64hi(u,w)<<1)         -1084331917592i64;
+	u=(mults64hi(u,w)<<1)         -9846680200605i64;
+	u=(mults64hi(u,w)<<1)        -88989564612665i64;
+	u=(mults64hi(u,w)<<1)       -811114127338876i64;
+	u=(mults64hi(u,w)<<1)      -7585739880715992i64;
+	u=(mults64hi(u,w)<<1)     -77689270051945395i64;
+	u=(mults64hi(u,w)<<1)   -1286052264325879039i64;
+	u=(mults64hi(u,w)<<1)+(7244019458077122842i64+1);
+	(u as u64,(1u64<<63)-w as u64)
+}
+
+// n/d*2^exp2 rounded to nearest, n and d nonzero
+const fn ratio_u128_to_pd(n:u128, d:u128, exp2:i64, neg:bool) -> PseudoDouble {
+	let ln=n.leading_zeros() as i64;
+	let ld=d.leading_zeros() as i64;
+	let nn=(n<<ln)>>2;
+	let dd=((d<<ld)>>64) as u64 as u128;
+	let q=nn/dd;
+	let r=nn-q*dd;
+	let sticky=r!=0 || (d<<ld) as u64!=0;
+	match round_u64_to_pd(q as u64,exp2+2-ln-64+ld,sticky,neg) {
+		Ok(x) => x,
+		Err(ParsePdError::Underflow) => PD_ZERO,
+		Err(_) => panic!("Overflow in PseudoDouble division"),
+	}
+}
+
+// chebyfit of atan(x)*4/(pi*x) as a polynomial in x^2 over x in [0,1/2]
+// the C atan_rev_64_fixed is minimax in absolute error over [0,1], which is not enough relative precision near 0
+
+// x is a 2.62 unsigned fixed in the range [0,1/2]
+// calculate atan(x)*4/(pi*x)
+// result is 2.62 fixed in the range [1.18,4/pi]
+const fn atan_rev_ratio_64_fixed(xu:u64) -> i64 {
+	let x=xu as i64;
+	let x2=mults64hi(x,x)<<2;
+	let mut u=                  40388472590229660i64;
+	u=(mults64hi(u,x2)<<2)    -119451630339286274i64;
+	u=(mults64hi(u,x2)<<2)    +193529897998212425i64;
+	u=(mults64hi(u,x2)<<2)    -242918260579836743i64;
+	u=(mults64hi(u,x2)<<2)    +276933265933681911i64;
+	u=(mults64hi(u,x2)<<2)    -308619541776180293i64;
+	u=(mults64hi(u,x2)<<2)    +345350417265416808i64;
+	u=(mults64hi(u,x2)<<2)    -391448034899518093i64;
+	u=(mults64hi(u,x2)<<2)    +451675223703085032i64;
+	u=(mults64hi(u,x2)<<2)    -533798263624760681i64;
+	u=(mults64hi(u,x2)<<2)    +652420111582013399i64;
+	u=(mults64hi(u,x2)<<2)    -838825858076414360i64;
+	u=(mults64hi(u,x2)<<2)   +1174356201312765387i64;
+	u=(mults64hi(u,x2)<<2)   -1957260335521334034i64;
+	u=(mults64hi(u,x2)<<2)+(5871781006564002453i64+2);
+	u
+}
+
+// chebyfit of asin(x)*4/(pi*x) as a polynomial in x^2 over x in [0,1/2]
+
+// x is a 2.62 unsigned fixed in the range [0,1/2]
+// calculate asin(x)*4/(pi*x)
+// result is 2.62 fixed in the range [4/pi,4/3]
+const fn asin_rev_ratio_64_fixed(xu:u64) -> i64 {
+	let x=xu as i64;
+	let x2=mults64hi(x,x)<<2;
+	let mut u=                 204687944468886641i64;
+	u=(mults64hi(u,x2)<<2)    -185796818239096680i64;
+	u=(mults64hi(u,x2)<<2)    +169436626661961889i64;
+	u=(mults64hi(u,x2)<<2)     -15919253184275463i64;
+	u=(mults64hi(u,x2)<<2)     +57520733836525906i64;
+	u=(mults64hi(u,x2)<<2)     +46449826291178632i64;
+	u=(mults64hi(u,x2)<<2)     +57712527250989742i64;
+	u=(mults64hi(u,x2)<<2)     +67789391129176525i64;
+	u=(mults64hi(u,x2)<<2)     +82001476168680166i64;
+	u=(mults64hi(u,x2)<<2)    +101891476645809060i64;
+	u=(mults64hi(u,x2)<<2)    +131364424456699808i64;
+	u=(mults64hi(u,x2)<<2)    +178396124198268983i64;
+	u=(mults64hi(u,x2)<<2)    +262133080652076499i64;
+	u=(mults64hi(u,x2)<<2)    +440383575492286004i64;
+	u=(mults64hi(u,x2)<<2)    +978630167760667117i64;
+	u=(mults64hi(u,x2)<<2)+(5871781006564002453i64+2);
+	u
+}
+
+// |x|<=1 as a 2.62 unsigned fixed
+const fn pd_abs_to_fixed_2_62(x:PseudoDouble) -> u64 {
+	let shift=PSEUDO_DOUBLE_EXP_BIAS+2-(x.0&EXP_MASK);
+	if shift>63 {
+		return 0;
+	}
+	(x.0&EXP_MASK_INV).unsigned_abs()>>shift
+}
+
+// TAU as a 3.61 unsigned fixed
+const TAU_FIXED_61:u64=0xC90FDAA22168C235;
+
+// split a nonzero PseudoDouble into sign and magnitude sig*2^exp2, with sig in [2^62,2^63)
+const fn pd_to_sig62_exp(x:PseudoDouble) -> (bool, u64, i32) {
+	let (negative,sig,exp2)=pd_to_sig_exp(x);
+	(negative,sig<<16,exp2-16)
+}
+
+// round an inverse trig result of q*2^exp2 revolutions, converting to radians first if asked so
+// that there is a single rounding. Results too small to represent underflow to zero
+const fn rev_fixed_to_pd(q:u64, exp2:i32, neg:bool, radians:bool) -> PseudoDouble {
+	if q==0 {
+		return PD_ZERO;
+	}
+	let lz=q.leading_zeros() as i32;
+	let mut q=q<<lz;
+	let mut exp2=exp2-lz;
+	if radians {
+		q=multu64hi(q,TAU_FIXED_61);
+		exp2+=3;
+	}
+	match round_u64_to_pd(q,exp2 as i64,false,neg) {
+		Ok(x) => x,
+		Err(_) => PD_ZERO, // only underflow is possible
+	}
+}
+
+// asin_rev(x) for |x|=sig*2^exp2<1/2 as m*2^(exp2-3), keeping the full precision of x
+const fn asin_rev_small_fixed(sig:u64, exp2:i32) -> u64 {
+	let shift=-62-exp2;
+	let xf=if shift>63 {0} else {sig>>shift};
+	multu64hi(sig<<1,(asin_rev_ratio_64_fixed(xf) as u64)<<1)
+}
+
+// 2*asin_rev(sqrt(d*2^-64)) for 0<d<=2^62 as m*2^(-64-k)
+// the square root is taken in fixed point so no rounding is added before the kernel
+const fn two_asin_rev_sqrt_fixed(d:u64) -> (u64,i32) {
+	let sh=(d.leading_zeros()&!1) as i32;
+	let dn=d<<sh;
+	// one Newton-Raphson step from the integer square root, sqrt(d)*2^(31+sh/2)
+	let s0=dn.isqrt();
+	let s=(s0<<31)+((dn-s0*s0)<<30)/s0;
+	let u=asin_rev_ratio_64_fixed(s>>(1+sh/2)) as u64;
+	(multu64hi(s,u<<1),sh/2)
+}
+
+// 1-|x| for 1/2<=|x|=sig*2^exp2<=1 as a 1.63 unsigned fixed, which is exact
+const fn one_minus_sig62(sig:u64, exp2:i32) -> u64 {
+	(1u64<<63)-if exp2== -63 {sig} else {sig<<1}
+}
+
+// tan_rev(x), or tan(x) if radians is set, with a domain error at the poles
+// the argument is reduced to y in [-1/4,1/4) revolutions as a 1.127 signed fixed 4*y, exactly for
+// tan_rev and from 128 bits of x/(2*pi) mod 1 for tan, or kept as is when it is already small
+const fn pd_tan_scaled(x:PseudoDouble, radians:bool) -> Result<PseudoDouble, PdDomainError> {
+	if x.0==0 {
+		return Ok(PD_ZERO);
+	}
+	let (negx,sig,exp2)=pd_to_sig_exp(x);
+	let negative;
+	let reciprocal;
+	let t; // reduced |y| in [0,1/8] turns as 1.63 fixed 4*|y|
+	let ym; // reduced |y|=ym*2^ye, ym normalized
+	let ye;
+	if exp2<=-50 || (radians && exp2<=-48) {
+		// |y|<1/8, keep the full mantissa so tiny arguments lose nothing
+		let (p,pe)=if radians {
+			(sig as u128*INV_TAU_FIXED_256[0] as u128+((sig as u128*INV_TAU_FIXED_256[1] as u128)>>64),exp2 as i64-64)
+		} else {
+			(sig as u128,exp2 as i64)
+		};
+		let lz=p.leading_zeros() as i64;
+		negative=negx;
+		reciprocal=false;
+		ym=((p<<lz)>>64) as u64;
+		ye=pe+64-lz;
+		let sh=-(pe+65);
+		t=if sh<=0 {(p<<-sh) as u64} else if sh>=128 {0} else {(p>>sh) as u64};
+	} else {
+		// x mod 1/2 revolutions
+		let mut r=if radians {rev_fraction_128(sig,exp2)<<1} else if exp2>=-1 {0} else {(sig as u128)<<(exp2+129)};
+		if negx {
+			r=r.wrapping_neg();
+		}
+		if r==0 {
+			return Ok(PD_ZERO);
+		}
+		if r==1u128<<127 {
+			return Err(PdDomainError{function:if radians {"tan"} else {"tan_rev"}});
+		}
+		negative=(r as i128)<0;
+		let a=(r as i128).unsigned_abs();
+		// above 1/8 turn use tan(y)=1/tan(1/4-y)
+		reciprocal=a>(1u128<<126);
+		let tt=if reciprocal {(1u128<<127)-a} else {a};
+		let lz=tt.leading_zeros() as i64;
+		ym=((tt<<lz)>>64) as u64;
+		ye=-65-lz;
+		t=(tt>>64) as u64;
+	}
+	let (g,d)=tan_rev_64_fixed(t);
+	let n=ym as u128*g as u128;
+	if reciprocal {
+		return Ok(ratio_u128_to_pd(d as u128,n,-3-ye,negative));
+	}
+	Ok(ratio_u128_to_pd(n,d as u128,ye+3,negative))
+}
+
+// the result of pd_tan_scaled, a pole overflows with the sign of the argument x
+const fn tan_or_pole(t:Result<PseudoDouble, PdDomainError>, x:PseudoDouble) -> PseudoDouble {
+	match t {
+		Ok(v) => v,
+		Err(_) => {
+			if cfg!(feature="panic_on_pseudodouble_overflow") {
+				panic!("PseudoDouble tan at a pole");
+			}
+			if x.0<0 {PseudoDouble::MIN} else {PseudoDouble::MAX}
+		}
+	}
+}
+
+// asin_rev(x), or asin(x) if radians is set
+const fn pd_asin_scaled(x:PseudoDouble, radians:bool) -> PseudoDouble {
+	if x.const_less_than(PD_NEG_ONE) || PD_ONE.const_less_than(x) {
+		panic!("asin of number not between -1 and 1");
+	}
+	if x.0==0 {
+		return PD_ZERO;
+	}
+	let (negative,sig,exp2)=pd_to_sig62_exp(x);
+	if exp2< -63 {
+		// |x|<1/2
+		return rev_fixed_to_pd(asin_rev_small_fixed(sig,exp2),exp2-3,negative,radians);
+	}
+	// asin(x)=pi/2-2*asin(sqrt((1-x)/2))
+	let d=one_minus_sig62(sig,exp2);
+	if d==0 {
+		return rev_fixed_to_pd(1u64<<62,-64,negative,radians);
+	}
+	let (m,k)=two_asin_rev_sqrt_fixed(d);
+	rev_fixed_to_pd((1u64<<62)-(m>>k),-64,negative,radians)
+}
+
+// acos_rev(x), or acos(x) if radians is set
+const fn pd_acos_scaled(x:PseudoDouble, radians:bool) -> PseudoDouble {
+	if x.const_less_than(PD_NEG_ONE) || PD_ONE.const_less_than(x) {
+		panic!("acos of number not between -1 and 1");
+	}
+	if x.0==0 {
+		return rev_fixed_to_pd(1u64<<62,-64,false,radians);
+	}
+	let (negative,sig,exp2)=pd_to_sig62_exp(x);
+	if exp2< -63 {
+		// |x|<1/2, acos(x)=pi/2-asin(x)
+		let shift=-61-exp2;
+		let a=if shift>63 {0} else {asin_rev_small_fixed(sig,exp2)>>shift};
+		return rev_fixed_to_pd(if negative {(1u64<<62)+a} else {(1u64<<62)-a},-64,false,radians);
+	}
+	// acos(x)=2*asin(sqrt((1-x)/2)), acos(-x)=pi-acos(x)
+	let d=one_minus_sig62(sig,exp2);
+	if d==0 {
+		return rev_fixed_to_pd(if negative {1u64<<63} else {0},-64,false,radians);
+	}
+	let (m,k)=two_asin_rev_sqrt_fixed(d);
+	if negative {
+		return rev_fixed_to_pd((1u64<<63)-(m>>k),-64,false,radians);
+	}
+	rev_fixed_to_pd(m,-64-k,false,radians)
+}
+
+// atan2_rev(y,x), or atan2(y,x) if radians is set
+const fn pd_atan2_scaled(y:PseudoDouble, x:PseudoDouble, radians:bool) -> PseudoDouble {
+	if y.0==0 {
+		// 0 or 1/2
+		return rev_fixed_to_pd(if x.0>=0 {0} else {1u64<<63},-64,false,radians);
+	}
+	if x.0==0 {
+		// 1/4 or 3/4
+		return rev_fixed_to_pd(if y.0>0 {1u64<<62} else {3u64<<62},-64,false,radians);
+	}
+	let (yneg,ys,ye)=pd_to_sig62_exp(y);
+	let (xneg,xs,xe)=pd_to_sig62_exp(x);
+	// reduce to a/b with a<=b, the result is then +-(base+atan_rev(a/b)) or +-(base-atan_rev(a/b))
+	let swap=ye>xe || (ye==xe && ys>xs);
+	let (a,ae,b,be)=if swap {(xs,xe,ys,ye)} else {(ys,ye,xs,xe)};
+	let base=if swap {1u64<<62} else if xneg {1u64<<63} else {0};
+	let subtract=if swap {!xneg} else {xneg};
+	// a/b in 2.62 fixed is rq>>k
+	let rq=divs64hi((a>>2) as i64,b as i64) as u64;
+	let k=be-ae;
+	let (r,exp2)=if k>=2 || (k==1 && rq<=1u64<<62) {
+		// a/b<=1/2, atan(t)=t*ratio(t^2) as m*2^(-65-k)
+		let tf=if k>63 {0} else {rq>>k};
+		let m=multu64hi(rq<<1,(atan_rev_ratio_64_fixed(tf) as u64)<<1);
+		if base==0 {
+			(m,-65-k)
+		} else {
+			let v=if k>62 {0} else {m>>(k+1)};
+			(if subtract {base-v} else {base+v},-64)
+		}
+	} else {
+		// 1/2<a/b<=1, atan(t)=pi/4-atan((1-t)/(1+t)) with the ratio w in 0.64 fixed
+		let t=rq>>k;
+		let w=if t==1u64<<62 {0} else {divs64hi(((1u64<<62)-t) as i64,(t+(1u64<<62)) as i64) as u64};
+		let u=(1u64<<61)-(multu64hi(w,atan_rev_ratio_64_fixed(w>>2) as u64)>>1);
+		(if subtract {base-u} else {base+u},-64)
+	};
+	rev_fixed_to_pd(r,exp2,yneg,radians)
+}
+
+// convert the bits of an IEEE binary floating point format with mant_bits stored mantissa bits
+// and exp_bits exponent bits. Every such format up to f32 is exactly representable.
+const fn ieee_bits_to_pd(bits:u64, mant_bits:i32, exp_bits:i32) -> PseudoDouble {
+	let exp_max=(1i64<<exp_bits)-1;
+	let bias=(1i32<<(exp_bits-1))-1;
+	let raw_exponent=((bits>>mant_bits) as i64)&exp_max;
+	let mut mantissa=(bits&((1u64<<mant_bits)-1)) as i64;
+	let negative=((bits>>(mant_bits+exp_bits))&1)!=0;
+	if raw_exponent==exp_max {
+		panic!("Cannot convert NaN or infinity to PseudoDouble");
+	}
+	if raw_exponent==0 {
+		if mantissa==0 {
+			return PD_ZERO;
+		}
+		// subnormal
+		return PseudoDouble::pdc2(if negative {-mantissa} else {mantissa},1-bias-mant_bits);
+	}
+	mantissa+=1i64<<mant_bits; // add in the implied bit
+	PseudoDouble::pdc2(if negative {-mantissa} else {mantissa},raw_exponent as i32-bias-mant_bits)
+}
+
+// convert to the bits of an IEEE binary floating point format with mant_bits stored mantissa bits
+// and exp_bits exponent bits, rounding to nearest even. Handles subnormals, overflows to infinity.
+const fn pd_to_ieee_bits(x:PseudoDouble, mant_bits:i32, exp_bits:i32) -> u64 {
+	if x.0==0 {
+		return 0;
+	}
+	let negative=x.0<0;
+	let sgn=if negative {1u64<<(mant_bits+exp_bits)} else {0};
+	let vx=x.0&EXP_MASK_INV;
+	let mut mag=if negative {(-(vx as i128)) as u64} else {vx as u64};
+	let lead_bits=mag.leading_zeros() as i32;
+	mag<<=lead_bits;
+	// x=mag*2^(unbiased-63) with the top bit of mag set
+	let unbiased=(x.0&EXP_MASK) as i32-PSEUDO_DOUBLE_EXP_BIAS as i32-1-lead_bits;
+	let inf=((1u64<<exp_bits)-1)<<mant_bits;
+	let mut biased=unbiased+(1i32<<(exp_bits-1))-1;
+	let mut shift=63-mant_bits;
+	if biased<=0 {
+		shift+=1-biased; // subnormal
+		biased=1;
+	}
+	if shift>64 {
+		return sgn;
+	}
+	let q=((mag as u128)>>shift) as u64;
+	let rem=(mag as u128)&((1u128<<shift)-1);
+	let half=1u128<<(shift-1);
+	let q=if rem>half || (rem==half && (q&1)!=0) {q+1} else {q};
+	// q includes the implied bit, so rounding up into the next binade carries into the exponent
+	let bits=(((biased-1) as u64)<<mant_bits)+q;
+	if bits>=inf {
+		return inf+sgn;
+	}
+	bits+sgn
+}
+
+// Big unsigned integer used for correctly rounded decimal conversion. It is fixed size so
+// that it can be used in const fns. BIG_LIMBS is enough for 23000 significant decimal digits
+// divided by 5^32900, which covers every halfway point between two PseudoDoubles. SMALL_LIMBS
+// is used when the numbers are known to fit, which is much faster. It is about 10KB at BIG_LIMBS, so it
+// is not Copy and is always passed by reference and worked on in place.
+const BIG_LIMBS: usize = 1216;
+const SMALL_LIMBS: usize = 160;
+const PARSE_MAX_DIGITS: usize = 23000;
+
+struct BigUint<const N: usize> {
+	limbs: [u64; N],
+	len: usize,
+}
+
+impl<const N: usize> BigUint<N> {
+	const fn from_u64(x:u64) -> BigUint<N> {
+		let mut r=BigUint { limbs: [0u64; N], len: 0 };
+		if x!=0 {
+			r.limbs[0]=x;
+			r.len=1;
+		}
+		r
+	}
+
+	const fn is_zero(&self) -> bool {
+		self.len==0
+	}
+
+	const fn mul_small(&mut self, m:u64) {
+		let mut carry=0u64;
+		let mut i=0;
+		while i<self.len {
+			let t=self.limbs[i] as u128*m as u128+carry as u128;
+			self.limbs[i]=t as u64;
+			carry=(t>>64) as u64;
+			i+=1;
+		}
+		if carry!=0 {
+			if self.len==N {
+				panic!("PseudoDouble decimal conversion out of range");
+			}
+			self.limbs[self.len]=carry;
+			self.len+=1;
+		}
+		if m==0 {
+			self.len=0;
+		}
+	}
+
+	const fn add_small(&mut self, a:u64) {
+		let mut carry=a;
+		let mut i=0;
+		while carry!=0 && i<self.len {
+			let (t,c)=self.limbs[i].overflowing_add(carry);
+			self.limbs[i]=t;
+			carry=c as u64;
+			i+=1;
+		}
+		if carry!=0 {
+			if self.len==N {
+				panic!("PseudoDouble decimal conversion out of range");
+			}
+			self.limbs[self.len]=carry;
+			self.len+=1;
+		}
+	}
+
+	const fn mul_pow5(&mut self, n:u32) {
+		let mut n=n;
+		while n>=27 {
+			self.mul_small(7450580596923828125u64); // 5^27
+			n-=27;
+		}
+		if n>0 {
+			self.mul_small(5u64.pow(n));
+		}
+	}
+
+	const fn shl(&mut self, bits:usize) {
+		if self.len==0 || bits==0 {
+			return;
+		}
+		let words=bits/64;
+		let b=(bits%64) as u32;
+		let new_len=self.len+words+1;
+		if new_len>N {
+			panic!("PseudoDouble decimal conversion out of range");
+		}
+		let mut i=new_len;
+		while i>0 {
+			i-=1;
+			let hi=if i>=words && i-words<self.len {self.limbs[i-words]} else {0};
+			let lo=if b!=0 && i>words && i-words-1<self.len {self.limbs[i-words-1]>>(64-b)} else {0};
+			self.limbs[i]=(hi<<b)|lo;
+		}
+		self.len=new_len;
+		while self.len>0 && self.limbs[self.len-1]==0 {
+			self.len-=1;
+		}
+	}
+
+	// divides in place, returning the remainder
+	const fn div_small(&mut self, m:u64) -> u64 {
+		let mut rem=0u64;
+		let mut i=self.len;
+		while i>0 {
+			i-=1;
+			let t=((rem as u128)<<64)|self.limbs[i] as u128;
+			self.limbs[i]=(t/m as u128) as u64;
+			rem=(t%m as u128) as u64;
+		}
+		while self.len>0 && self.limbs[self.len-1]==0 {
+			self.len-=1;
+		}
+		rem
+	}
+
+	const fn bit_len(&self) -> usize {
+		if self.len==0 {
+			return 0;
+		}
+		self.len*64-self.limbs[self.len-1].leading_zeros() as usize
+	}
+
+	// the low 128 bits of self>>shift
+	const fn shr_u128(&self, shift:usize) -> u128 {
+		let words=shift/64;
+		let b=(shift%64) as u32;
+		let w0=if words<self.len {self.limbs[words] as u128} else {0};
+		let w1=if words+1<self.len {self.limbs[words+1] as u128} else {0};
+		let w2=if words+2<self.len {self.limbs[words+2] as u128} else {0};
+		let r=(w0|(w1<<64))>>b;
+		if b==0 {r} else {r|(w2<<(128-b))}
+	}
+
+	const fn cmp(&self, other:&BigUint<N>) -> i32 {
+		if self.len!=other.len {
+			return if self.len<other.len {-1} else {1};
+		}
+		let mut i=self.len;
+		while i>0 {
+			i-=1;
+			if self.limbs[i]!=other.limbs[i] {
+				return if self.limbs[i]<other.limbs[i] {-1} else {1};
+			}
+		}
+		0
+	}
+
+	// self-=other*m, other*m must not be larger than self
+	const fn sub_mul_small(&mut self, other:&BigUint<N>, m:u64) {
+		let mut carry=0u64;
+		let mut borrow=false;
+		let mut i=0;
+		while i<self.len {
+			let p=if i<other.len {other.limbs[i] as u128*m as u128} else {0}+carry as u128;
+			carry=(p>>64) as u64;
+			let (t1,b1)=self.limbs[i].overflowing_sub(p as u64);
+			let (t2,b2)=t1.overflowing_sub(borrow as u64);
+			self.limbs[i]=t2;
+			borrow=b1||b2;
+			i+=1;
+		}
+		while self.len>0 && self.limbs[self.len-1]==0 {
+			self.len-=1;
+		}
+	}
+
+	// self-=other, other must not be larger than self
+	const fn sub_assign(&mut self, other:&BigUint<N>) {
+		let mut borrow=false;
+		let mut i=0;
+		while i<self.len {
+			let o=if i<other.len {other.limbs[i]} else {0};
+			let (t1,b1)=self.limbs[i].overflowing_sub(o);
+			let (t2,b2)=t1.overflowing_sub(borrow as u64);
+			self.limbs[i]=t2;
+			borrow=b1||b2;
+			i+=1;
+		}
+		while self.len>0 && self.limbs[self.len-1]==0 {
+			self.len-=1;
+		}
+	}
+}
+
+// n/d, leaving the remainder in n, the quotient must be less than 2^62
+const fn big_div_u64<const N: usize>(n:&mut BigUint<N>, d:&BigUint<N>) -> u64 {
+	// estimate the quotient from the top 64 bits of the divisor, rounded up so that the estimate is
+	// never too large and at most 2 too small
+	let t=d.bit_len().saturating_sub(64);
+	let mut q=(n.shr_u128(t)/(d.shr_u128(t)+(t>0) as u128)) as u64;
+	n.sub_mul_small(d,q);
+	while n.cmp(d)>=0 {
+		n.sub_assign(d);
+		q+=1;
+	}
+	q
+}
+
+// round n/d*2^exp2 to a PseudoDouble, sticky is set if there are nonzero bits below n. n and d are
+// used as scratch space
+const fn big_ratio_to_pd<const N: usize>(n:&mut BigUint<N>, d:&mut BigUint<N>, exp2:i64, sticky:bool, neg:bool) -> Result<PseudoDouble, ParsePdError> {
+	let mut binexp=exp2;
+	// scale so that the quotient is in (2^49,2^51)
+	let s=50-(n.bit_len() as i64-d.bit_len() as i64);
+	if s>0 {
+		n.shl(s as usize);
+	} else {
+		d.shl((-s) as usize);
+	}
+	binexp-=s;
+	let q=big_div_u64(n,d);
+	round_u64_to_pd(q,binexp,sticky || !n.is_zero(),neg)
+}
+
+// round q*2^exp2 to a PseudoDouble, sticky is set if there are nonzero bits below q
+const fn round_u64_to_pd(q:u64, exp2:i64, sticky:bool, neg:bool) -> Result<PseudoDouble, ParsePdError> {
+	if q==0 {
+		return Ok(PD_ZERO);
+	}
+	// round to the 47 bits of the mantissa, nearest even
+	let mut drop=(64-q.leading_zeros()) as i64-47;
+	let mut sig;
+	if drop<=0 {
+		sig=q<<-drop;
+	} else {
+		sig=q>>drop;
+		let rem=q&((1u64<<drop)-1);
+		let half=1u64<<(drop-1);
+		if rem>half || (rem==half && (sticky || (sig&1)!=0)) {
+			sig+=1;
+			if sig==(1u64<<47) {
+				sig>>=1;
+				drop+=1;
+			}
+		}
+	}
+	let new_exponent=PSEUDO_DOUBLE_EXP_BIAS+48+exp2+drop;
+	if neg && sig==(1u64<<46) {
+		// a negative power of 2 has the most negative mantissa and one less exponent
+		if new_exponent-1>EXP_MASK {
+			return Err(ParsePdError::Overflow);
+		}
+		if new_exponent-1<0 {
+			return Err(ParsePdError::Underflow);
+		}
+		return Ok(PseudoDouble(i64::MIN+new_exponent-1));
+	}
+	if new_exponent>EXP_MASK {
+		return Err(ParsePdError::Overflow);
+	}
+	if new_exponent<0 {
+		return Err(ParsePdError::Underflow);
+	}
+	let mantissa=(sig as i64)<<PSEUDO_DOUBLE_EXP_BITS;
+	Ok(PseudoDouble(if neg {-mantissa} else {mantissa}+new_exponent))
+}
+
+// correctly rounded conversion from a 128 bit integer
+const fn u128_to_pd_rounded(u:u128, neg:bool) -> PseudoDouble {
+	let shift=if u>>64!=0 {128-(u>>64).leading_zeros() as i64} else {0};
+	let q=(u>>shift) as u64;
+	let sticky=shift>0 && (u&((1u128<<shift)-1))!=0;
+	match round_u64_to_pd(q,shift,sticky,neg) {
+		Ok(x) => x,
+		Err(_) => panic!("integer out of PseudoDouble range"),
+	}
+}
+
+// u*2^exp2 rounded to nearest, underflowing to zero and overflowing like exp
+// sticky is set if there are nonzero bits below u
+const fn u128_scaled_to_pd(u:u128, exp2:i64, sticky:bool, neg:bool) -> PseudoDouble {
+	let shift=if u>>64!=0 {128-(u>>64).leading_zeros() as i64} else {0};
+	let q=(u>>shift) as u64;
+	let sticky=sticky || (shift>0 && (u&((1u128<<shift)-1))!=0);
+	match round_u64_to_pd(q,exp2+shift,sticky,neg) {
+		Ok(x) => x,
+		Err(ParsePdError::Underflow) => PD_ZERO,
+		Err(_) => {
+			if cfg!(feature="panic_on_pseudodouble_overflow") {
+				panic!("Overflow in PseudoDouble exp");
+			}
+			if neg {PseudoDouble::MIN} else {PseudoDouble::MAX}
+		}
+	}
+}
+
+// the square root of the sum of squares of xs, correctly rounded
+// the squares are exact integers scaled by the largest exponent, smaller ones are shifted down into a
+// sticky bit, so nothing overflows or underflows before the result does
+const fn norm2_rounded(xs:&[PseudoDouble]) -> PseudoDouble {
+	let mut emax=i32::MIN;
+	let mut i=0;
+	while i<xs.len() {
+		if xs[i].0!=0 {
+			let (_,_,exp2)=pd_to_sig_exp(xs[i]);
+			if exp2>emax {
+				emax=exp2;
+			}
+		}
+		i+=1;
+	}
+	if emax==i32::MIN {
+		return PD_ZERO;
+	}
+	// the sum of (x*2^-emax)^2*2^4 is s*2^sh, the largest square is in [2^96,2^98)
+	let mut s=0u128;
+	let mut sh=0i64;
+	let mut sticky=false;
+	i=0;
+	while i<xs.len() {
+		if xs[i].0!=0 {
+			let (_,sig,exp2)=pd_to_sig_exp(xs[i]);
+			if s>=1u128<<127 {
+				sticky=sticky || s&3!=0;
+				s>>=2;
+				sh+=2;
+			}
+			let q=(sig as u128*sig as u128)<<4;
+			let shift=2*(emax-exp2) as i64+sh;
+			if shift>=128 {
+				sticky=true;
+			} else {
+				sticky=sticky || q&((1u128<<shift)-1)!=0;
+				s+=q>>shift;
+			}
+		}
+		i+=1;
+	}
+	// s is at least 2^96, so r has more than 47 bits and r*r!=s with the sticky bit decides the rounding
+	let r=s.isqrt();
+	match round_u64_to_pd(r as u64,emax as i64+(sh-4)/2,sticky || r*r!=s,false) {
+		Ok(x) => x,
+		Err(_) => {
+			if cfg!(feature="panic_on_pseudodouble_overflow") {
+				panic!("Overflow in PseudoDouble hypot");
+			}
+			PseudoDouble::MAX
+		}
+	}
+}
+
+// the cube root of |x|=sig*2^exp2 correctly rounded, with the sign of neg
+// the Newton estimate is made exact with 256 bit cubes, so perfect cubes come out exactly
+const fn cbrt_rounded(sig:u64, exp2:i32, neg:bool) -> PseudoDouble {
+	// |x|=n*2^(3q), n=sig*2^s in [2^138,2^141)
+	let s=92+(exp2 as i64-92).rem_euclid(3);
+	let q=(exp2 as i64-s)/3;
+	let n=((sig as u128)>>(128-s),(sig as u128)<<s);
+	let x=sig<<(s-77); // n*2^-138 as 3.61
+	let y=inv_cbrt64_fixed(x);
+	let mut r=multu64hi(x,multu64hi(y,y)<<1)>>14; // x*y^2*2^46, about cbrt(n)
+	while lt_u256(n,cube_u256(r)) {
+		r-=1;
+	}
+	while !lt_u256(n,cube_u256(r+1)) {
+		r+=1;
+	}
+	// r=floor(cbrt(n)), round up if (r+1/2)^3<n, which is never equal
+	let n8=((n.0<<3)|(n.1>>125),n.1<<3);
+	if lt_u256(cube_u256(2*r+1),n8) {
+		r+=1;
+	}
+	match round_u64_to_pd(r,q,false,neg) {
+		Ok(x) => x,
+		Err(_) => unreachable!(),
+	}
+}
+
+// the n-th root of |x|=sig*2^exp2 for n>=2 correctly rounded, with the sign of neg
+// |x|=m*2^(n*q) with m in [1,2^n), the root of m is e^(ln(m)/n) to about 60 bits, and when that
+// is close to a rounding midpoint for n<=1600 the midpoint to the n is compared exactly
+const fn root_n_rounded(sig:u64, exp2:i32, n:u64, neg:bool) -> PseudoDouble {
+	let e=exp2 as i64+46;
+	let q=e.div_euclid(n as i64);
+	let j=e.rem_euclid(n as i64); // m=sig*2^(j-46)
+	let (l,le)=ln_fixed(sig as u128,j-46);
+	let (mm,k)=exp_fixed_100(shift_u128((l/n as i128) as u128,le+100),false);
+	let w=shift_u128(mm,k-125+62) as u64; // m^(1/n)*2^62
+	let mut r=w>>16;
+	let rem=w&0xffff;
+	if rem.abs_diff(0x8000)<=256 && n<=1600 {
+		// round up if ((2r+1)/2^47)^n<m, that is (2r+1)^n<sig*2^(47n+j-46), which is never equal
+		let mut a=BigUint::<BIG_LIMBS>::from_u64(1);
+		let mut i=0;
+		while i<n {
+			a.mul_small(2*r+1);
+			i+=1;
+		}
+		let mut b=BigUint::<BIG_LIMBS>::from_u64(sig);
+		b.shl((47*n as i64+j-46) as usize);
+		if a.cmp(&b)<0 {
+			r+=1;
+		}
+	} else if rem>0x8000 {
+		r+=1;
+	}
+	match round_u64_to_pd(r,q-46,false,neg) {
+		Ok(x) => x,
+		Err(_) => unreachable!(),
+	}
+}
+
+// a*2^ea*b*2^eb for a and b in [2^127,2^128) as m*2^e with m in [2^127,2^128), truncated
+const fn mul_norm_128(a:u128, ea:i64, b:u128, eb:i64) -> (u128,i64) {
+	let p=mul_u128_hi(a,b);
+	if p>>127==0 {
+		return (p<<1,ea+eb+127);
+	}
+	(p,ea+eb+128)
+}
+
+// 0^y for y<0
+const fn pow_zero_pole() -> PseudoDouble {
+	if cfg!(feature="panic_on_pseudodouble_overflow") {
+		panic!("PseudoDouble pow of zero to a negative power");
+	}
+	PseudoDouble::MAX
+}
+
+// |x|^n, or |x|^-n with invert, for |x|=sig*2^exp2 and n>=1, with the sign of neg
+// if the odd part of sig to the n fits in 128 bits it is exact, so results that fit and halfway cases
+// are rounded correctly. Otherwise the result is neither, and squaring with 128 bit mantissas is good to
+// n*2^-125, which is still 12 bits past the rounding bit. An exact 1/x^n is only a power of 2
+const fn pow_rounded(sig:u64, exp2:i32, n:u64, invert:bool, neg:bool) -> PseudoDouble {
+	// log2|x^n| is in [n*lo,n*(lo+1)), stop early well outside the range. For 1/2<=|x|<1 the upper
+	// bound is 0, so the squaring below also stops once the exponents pass 2^20
+	let lo=exp2 as i128+46;
+	let (low,high)=if invert {(-(n as i128)*(lo+1),-(n as i128)*lo)} else {(n as i128*lo,n as i128*(lo+1))};
+	if low>=32800 {
+		return u128_scaled_to_pd(1,32800,false,neg); // overflows
+	}
+	if high<=-32800 {
+		return PD_ZERO;
+	}
+	let tz=sig.trailing_zeros();
+	let odd=sig>>tz;
+	let bits=(64-odd.leading_zeros()) as u64;
+	if !invert && n<=128 && bits*n<=128 {
+		let mut p=1u128;
+		let mut i=0;
+		while i<n {
+			p*=odd as u128;
+			i+=1;
+		}
+		return u128_scaled_to_pd(p,n as i64*(exp2 as i64+tz as i64),false,neg);
+	}
+	let (mut b,mut be)=((sig as u128)<<81,exp2 as i64-81);
+	let (mut r,mut re)=(1u128<<127,-127i64);
+	let mut k=n;
+	loop {
+		if k&1!=0 {
+			(r,re)=mul_norm_128(r,re,b,be);
+		}
+		k>>=1;
+		if k==0 {
+			break;
+		}
+		(b,be)=mul_norm_128(b,be,b,be);
+		if be.abs()>(1<<20) {
+			// r is still to be multiplied by b, taking the result far outside the exponent range
+			return if (be>0)!=invert {u128_scaled_to_pd(1,1<<20,false,neg)} else {PD_ZERO};
+		}
+	}
+	if invert {
+		// 1/(r*2^re) with r kept to 71 bits
+		return u128_scaled_to_pd(recip_fixed_174(r>>57),-174-re-57,r!=1u128<<127,neg);
+	}
+	u128_scaled_to_pd(r,re,false,neg)
+}
+
+// correctly rounded conversion from a finite f64
+const fn f64_to_pd_rounded(f:f64) -> PseudoDouble {
+	let bits=f.to_bits();
+	let raw_exponent=((bits>>52)&0x7FF) as i64;
+	let mantissa=bits&0xFFFFFFFFFFFFF;
+	let q=if raw_exponent==0 {mantissa} else {mantissa+(1u64<<52)};
+	let exp2=if raw_exponent==0 {-1074} else {raw_exponent-1075};
+	// every finite f64 is in range
+	match round_u64_to_pd(q,exp2,false,(bits>>63)!=0) {
+		Ok(x) => x,
+		Err(_) => panic!("f64 out of PseudoDouble range"),
+	}
+}
+
+// round d*10^e10 to a PseudoDouble, d is used as scratch space
+const fn decimal_to_pd<const N: usize>(d:&mut BigUint<N>, e10:i64, sticky:bool, neg:bool) -> Result<PseudoDouble, ParsePdError> {
+	if d.is_zero() {
+		return Ok(PD_ZERO);
+	}
+	// d*10^e10 = d*5^e10*2^e10
+	let mut den=BigUint::<N>::from_u64(1);
+	if e10>=0 {
+		d.mul_pow5(e10 as u32);
+	} else {
+		den.mul_pow5((-e10) as u32);
+	}
+	big_ratio_to_pd(d,&mut den,e10,sticky,neg)
+}
+
+// second pass of string_to_pd, reads the first `digits` significant digits of bytes[start..end]
+const fn decimal_digits_to_pd<const N: usize>(bytes:&[u8], start:usize, end:usize, digits:usize, e10:i64, sticky:bool, neg:bool) -> Result<PseudoDouble, ParsePdError> {
+	let mut d=BigUint::<N>::from_u64(0);
+	let mut chunk=0u64;
+	let mut chunk_digits=0u32;
+	let mut count=0usize;
+	let mut i=start;
+	while i<end && count<digits {
+		let c=bytes[i];
+		if c.is_ascii_digit() && (count>0 || c!=b'0') {
+			chunk=chunk*10+(c-b'0') as u64;
+			chunk_digits+=1;
+			count+=1;
+			if chunk_digits==19 {
+				d.mul_small(10u64.pow(19));
+				d.add_small(chunk);
+				chunk=0;
+				chunk_digits=0;
+			}
+		}
+		i+=1;
+	}
+	if chunk_digits>0 {
+		d.mul_small(10u64.pow(chunk_digits));
+		d.add_small(chunk);
+	}
+	decimal_to_pd(&mut d,e10,sticky,neg)
+}
+
+const fn hex_digit_value(c:u8) -> Option<u64> {
+	match c {
+		b'0'..=b'9' => Some((c-b'0') as u64),
+		b'a'..=b'f' => Some((c-b'a'+10) as u64),
+		b'A'..=b'F' => Some((c-b'A'+10) as u64),
+		_ => None,
+	}
+}
+
+// hexadecimal digits with an optional point and an optional binary exponent, bytes[i..end]
+const fn parse_hex_float(bytes:&[u8], start:usize, end:usize, neg:bool) -> Result<PseudoDouble, ParsePdError> {
+	let mut i=start;
+	let mut mantissa=0u64;
+	let mut exp_adj=0i64;
+	let mut any_digits=false;
+	let mut seen_point=false;
+	let mut sticky=false;
+	while i<end {
+		let c=bytes[i];
+		if let Some(v)=hex_digit_value(c) {
+			any_digits=true;
+			if mantissa<(1u64<<60) {
+				mantissa=mantissa*16+v;
+				if seen_point {
+					exp_adj-=4;
+				}
+			} else {
+				// more digits than can affect the rounding
+				if v!=0 {
+					sticky=true;
+				}
+				if !seen_point {
+					exp_adj+=4;
+				}
+			}
+		} else if c==b'.' && !seen_point {
+			seen_point=true;
+		} else {
+			break;
+		}
+		i+=1;
+	}
+	if !any_digits {
+		return Err(ParsePdError::InvalidDigit(i));
+	}
+	let mut exp=0i64;
+	if i<end && (bytes[i]==b'p' || bytes[i]==b'P') {
+		i+=1;
+		let exp_neg=i<end && bytes[i]==b'-';
+		if i<end && (bytes[i]==b'-' || bytes[i]==b'+') {
+			i+=1;
+		}
+		if i==end || !bytes[i].is_ascii_digit() {
+			return Err(ParsePdError::InvalidDigit(i));
+		}
+		while i<end && bytes[i].is_ascii_digit() {
+			if exp<1_000_000_000 {
+				exp=exp*10+(bytes[i]-b'0') as i64;
+			}
+			i+=1;
+		}
+		if exp_neg {
+			exp= -exp;
+		}
+	}
+	if i<end {
+		return Err(ParsePdError::InvalidDigit(i));
+	}
+	round_u64_to_pd(mantissa,exp+exp_adj,sticky,neg)
+}
+
+// `0x` followed by up to 16 hexadecimal digits giving the raw bits, bytes[i..end]
+const fn parse_raw_bits(bytes:&[u8], start:usize, end:usize) -> Result<PseudoDouble, ParsePdError> {
+	let mut i=start;
+	if i+2>end || bytes[i]!=b'0' || bytes[i+1]!=b'x' {
+		return Err(ParsePdError::InvalidDigit(if i<end && bytes[i]==b'0' {i+1} else {i}));
+	}
+	i+=2;
+	if i==end {
+		return Err(ParsePdError::InvalidDigit(i));
+	}
+	let mut bits=0u64;
+	while i<end {
+		match hex_digit_value(bytes[i]) {
+			Some(v) => {
+				if (bits>>60)!=0 {
+					return Err(ParsePdError::Overflow);
+				}
+				bits=bits*16+v;
+			}
+			None => return Err(ParsePdError::InvalidDigit(i)),
+		}
+		i+=1;
+	}
+	Ok(PseudoDouble(bits as i64))
+}
+
+// x as an exact hexadecimal floating point number without the sign, 0x1.8p+3
+fn pd_to_hex_float_string(x:PseudoDouble, upper:bool) -> String {
+	if x.0==0 {
+		return String::from(if upper {"0X0P+0"} else {"0x0p+0"});
+	}
+	let (_negative,sig,exp2)=pd_to_sig_exp(x);
+	// 46 bits after the point, shifted up to make 12 hex digits
+	let fraction=(sig-(1u64<<46))<<2;
+	let mut s=format!("{:012x}",fraction);
+	while s.ends_with('0') {
+		s.pop();
+	}
+	let s=if s.is_empty() {format!("0x1p{:+}",exp2+46)} else {format!("0x1.{}p{:+}",s,exp2+46)};
+	if upper {s.to_uppercase()} else {s}
+}
+
+// split a nonzero PseudoDouble into sign and magnitude sig*2^exp2, with sig in [2^46,2^47)
+const fn pd_to_sig_exp(x:PseudoDouble) -> (bool, u64, i32) {
+	let vx=x.0&EXP_MASK_INV;
+	let negative=vx<0;
+	let mag=if negative {(-(vx as i128)) as u64} else {vx as u64};
+	let shift=17-mag.leading_zeros() as i32;
+	let sig=if shift>=0 {mag>>shift} else {mag<<-shift};
+	(negative,sig,(x.0&EXP_MASK) as i32-PSEUDO_DOUBLE_EXP_BIAS as i32-64+shift)
+}
+
+// exact division of |x| by |y| giving the truncated quotient as a mantissa q_top*2^shift with
+// sticky bits, the remainder r*2^exp2 with r<sy, and whether the quotient is odd. rest_all_ones
+// is set when the quotient bits below q_top are all ones, for pd_div_rem_plus_one.
+struct PdDivRem {
+	q_top:u64,
+	shift:i64,
+	sticky:bool,
+	rest_all_ones:bool,
+	r:u64,
+	exp2:i32,
+	sy:u64,
+	odd:bool,
+}
+
+const fn pd_div_rem(x:PseudoDouble, y:PseudoDouble) -> PdDivRem {
+	if y.0==0 {
+		panic!("Division by zero");
+	}
+	let (_negative_y,sy,ey)=pd_to_sig_exp(y);
+	if x.0==0 {
+		return PdDivRem{q_top:0,shift:0,sticky:false,rest_all_ones:false,r:0,exp2:ey,sy,odd:false};
+	}
+	let (_negative_x,sx,ex)=pd_to_sig_exp(x);
+	if ex<ey || (ex==ey && sx<sy) {
+		// |x|<|y|, the remainder is x itself
+		return PdDivRem{q_top:0,shift:0,sticky:false,rest_all_ones:false,r:sx,exp2:ex,sy:0,odd:false};
+	}
+	let d=(ex-ey) as i64;
+	// the remainder, reducing up to 64 bits at a time
+	let mut r=sx%sy;
+	let mut odd=((sx/sy)&1)!=0;
+	let mut remaining=d;
+	while remaining>0 {
+		let k=if remaining>64 {64} else {remaining};
+		let t=(r as u128)<<k;
+		odd=((t/sy as u128)&1)!=0;
+		r=(t%sy as u128) as u64;
+		remaining-=k;
+	}
+	// the quotient, to at least 60 bits
+	let top=if d>60 {60} else {d};
+	let t=(sx as u128)<<top;
+	let q_top=(t/sy as u128) as u64;
+	let rem_top=(t%sy as u128) as u64;
+	let shift=d-top;
+	let mut sticky=false;
+	let mut rest_all_ones=false;
+	if shift>0 {
+		// the rest of the quotient is floor(rem_top*2^shift/sy), which is nonzero when
+		// rem_top*2^shift>=sy and all ones when (sy-rem_top)*2^shift<=sy
+		sticky=rem_top!=0 && (shift>=47 || ((rem_top as u128)<<shift)>=sy as u128);
+		rest_all_ones=shift<47 && (((sy-rem_top) as u128)<<shift)<=sy as u128;
+	}
+	PdDivRem{q_top,shift,sticky,rest_all_ones,r,exp2:ey,sy,odd}
+}
+
+// the quotient of d increased by one before it is rounded
+const fn pd_div_rem_plus_one(d:PdDivRem) -> PdDivRem {
+	let mut d=d;
+	if d.shift==0 || d.rest_all_ones {
+		d.q_top+=1;
+		d.sticky=false;
+	} else {
+		d.sticky=true;
+	}
+	d
+}
+
+// r*2^exp2 which is exact apart from underflow
+const fn pd_from_remainder(r:u64, exp2:i32, neg:bool) -> PseudoDouble {
+	match round_u64_to_pd(r,exp2 as i64,false,neg) {
+		Ok(x) => x,
+		Err(_) => PD_ZERO,
+	}
+}
+
+const fn pd_from_quotient(d:&PdDivRem, neg:bool) -> PseudoDouble {
+	match round_u64_to_pd(d.q_top,d.shift,d.sticky,neg) {
+		Ok(x) => x,
+		Err(ParsePdError::Underflow) => PD_ZERO,
+		Err(_) => panic!("Overflow in PseudoDouble division"),
+	}
+}
+
+// mantissa, exponent and sign for Float::integer_decode; below 2^-32722 the exponent
+// no longer fits in an i16 so low mantissa bits are dropped
+fn pd_integer_decode(x:PseudoDouble) -> (u64, i16, i8) {
+	if x.0==0 {
+		return (0,0,1);
+	}
+	let (negative,mut sig,mut exp2)=pd_to_sig_exp(x);
+	if exp2<i16::MIN as i32 {
+		sig>>=i16::MIN as i32-exp2;
+		exp2=i16::MIN as i32;
+	}
+	(sig,exp2 as i16,if negative {-1} else {1})
+}
+
+// floor(sig*2^exp2*10^exp10) which must be less than 2^62, and the remainder
+// compared with one half: 0 = zero, 1 = less than a half, 2 = a half, 3 = more than a half
+const fn scaled_decimal_digits(sig:u64, exp2:i32, exp10:i32) -> (u64, u32) {
+	// the larger of the numerator and denominator has about 64+|exp2+exp10|+|exp10|*log2(5) bits
+	if (exp2+exp10).abs()+exp10.abs()*3+192<(SMALL_LIMBS*64) as i32 {
+		return scaled_decimal_digits_n::<SMALL_LIMBS>(sig,exp2,exp10);
+	}
+	scaled_decimal_digits_n::<BIG_LIMBS>(sig,exp2,exp10)
+}
+
+const fn scaled_decimal_digits_n<const N: usize>(sig:u64, exp2:i32, exp10:i32) -> (u64, u32) {
+	let mut num=BigUint::<N>::from_u64(sig);
+	let mut den=BigUint::<N>::from_u64(1);
+	if exp10>=0 {
+		num.mul_pow5(exp10 as u32);
+	} else {
+		den.mul_pow5((-exp10) as u32);
+	}
+	let e=exp2+exp10;
+	if e>=0 {
+		num.shl(e as usize);
+	} else {
+		den.shl((-e) as usize);
+	}
+	let q=big_div_u64(&mut num,&den);
+	if num.is_zero() {
+		return (q,0);
+	}
+	num.shl(1);
+	let c=num.cmp(&den);
+	(q,(c+2) as u32)
+}
+
+// the shortest decimal digits that round trip through string_to_pd. Returns the sign, the digits,
+// the number of digits and the decimal exponent of the first digit
+const fn pd_to_shortest_decimal(x:PseudoDouble) -> (bool, u64, i32, i32) {
+	if x.0==0 {
+		return (false,0,1,0);
+	}
+	let (negative,sig,exp2)=pd_to_sig_exp(x);
+	// 17 significant digits, k is the decimal exponent of the first digit. x is in [2^(exp2+46),2^(exp2+47))
+	let mut k=(((exp2+46) as i64*78913)>>18) as i32; // floor(log10(2)*(exp2+46)), can be one too small
+	let (mut digits17,mut rem)=scaled_decimal_digits(sig,exp2,16-k);
+	if digits17>=100_000_000_000_000_000u64 {
+		k+=1;
+		(digits17,rem)=scaled_decimal_digits(sig,exp2,16-k);
+	}
+	// everything strictly between the halfway points to the neighbours rounds to x, and the
+	// halfway points themselves if the mantissa is even. Just above a power of 2 the gap below is half the size.
+	let (lo,lo_rem)=if sig==(1u64<<46) {
+		scaled_decimal_digits(4*sig-1,exp2-2,16-k)
+	} else {
+		scaled_decimal_digits(2*sig-1,exp2-1,16-k)
+	};
+	let (hi,hi_rem)=scaled_decimal_digits(2*sig+1,exp2-1,16-k);
+	let inclusive=(sig&1)==0;
+	let mut n=1;
+	while n<17 {
+		let div=10u64.pow((17-n) as u32);
+		let c=digits17/div;
+		let r=digits17%div;
+		// try the nearest n digit number first, then the one on the other side of x
+		let round_up=r*2>div || (r*2==div && (rem!=0 || (c&1)!=0));
+		let mut i=0;
+		while i<2 {
+			let candidate=if round_up==(i==0) {c+1} else {c};
+			let m=candidate*div;
+			let above_lo=m>lo || (m==lo && lo_rem==0 && inclusive);
+			let below_hi=m<hi || (m==hi && (hi_rem!=0 || inclusive));
+			if above_lo && below_hi {
+				return (negative,candidate,n,k);
+			}
+			i+=1;
+		}
+		n+=1;
+	}
+	(negative,digits17,17,k)
+}
+
+// floor(log10(sig*2^exp2)) for sig in [2^46,2^47)
+const fn decimal_exponent(sig:u64, exp2:i32) -> i32 {
+	let k=(((exp2+46) as i64*78913)>>18) as i32; // floor(log10(2)*(exp2+46)), can be one too small
+	let (digits17,_rem)=scaled_decimal_digits(sig,exp2,16-k);
+	if digits17>=100_000_000_000_000_000u64 {k+1} else {k}
+}
+
+// the decimal digits of x, which is used as scratch space
+fn big_to_decimal_string<const N: usize>(x:&mut BigUint<N>) -> String {
+	let mut chunks=Vec::new();
+	while !x.is_zero() {
+		chunks.push(x.div_small(10_000_000_000_000_000_000u64));
+	}
+	let mut s=chunks.pop().unwrap_or(0).to_string();
+	while let Some(c)=chunks.pop() {
+		s.push_str(&format!("{:019}",c));
+	}
+	s
+}
+
+// the exact decimal expansion of sig*2^exp2, as digits*10^exp10
+fn exact_decimal_expansion(sig:u64, exp2:i32) -> (String, i32) {
+	if exp2>=0 {
+		if exp2+256<(SMALL_LIMBS*64) as i32 {
+			let mut d=BigUint::<SMALL_LIMBS>::from_u64(sig);
+			d.shl(exp2 as usize);
+			return (big_to_decimal_string(&mut d),0);
+		}
+		let mut d=BigUint::<BIG_LIMBS>::from_u64(sig);
+		d.shl(exp2 as usize);
+		return (big_to_decimal_string(&mut d),0);
+	}
+	// sig*2^exp2 = sig*5^-exp2*10^exp2
+	if -exp2*3+256<(SMALL_LIMBS*64) as i32 {
+		let mut d=BigUint::<SMALL_LIMBS>::from_u64(sig);
+		d.mul_pow5((-exp2) as u32);
+		return (big_to_decimal_string(&mut d),exp2);
+	}
+	let mut d=BigUint::<BIG_LIMBS>::from_u64(sig);
+	d.mul_pow5((-exp2) as u32);
+	(big_to_decimal_string(&mut d),exp2)
 }
 
-// ./lolremez --stats --debug --long-double -d 15 -r "-1:1" "sin(x*pi/2)"
-// long double f(long double x) {
-//     x2 = x * x;
-//     long double u = -6.4348418488777433292e-10;
-//     u = u * x2 + 5.6877693955975479363e-8;
-//     u = u * x2 + -3.5988024751262635033e-6;
-//     u = u * x2 + 1.6044116327434520208e-4;
-//     u = u * x2 + -4.6817541288738726714e-3;
-//     u = u * x2 + 7.9692626245142861354e-2;
-//     u = u * x2 + -6.4596409750617316605e-1;
-//     u = u * x2 + 1.5707963267948950979l;
-//     return u * x;
-// }
+// round digits*10^exp10 to a multiple of 10^target, nearest even. Returns the multiple as digits
+fn round_decimal_string(digits:&str, exp10:i32, target:i32) -> String {
+	if target<=exp10 {
+		return format!("{}{}",digits,"0".repeat((exp10-target) as usize));
+	}
+	let drop=(target-exp10) as usize;
+	let bytes=digits.as_bytes();
+	if drop>bytes.len() {
+		return String::from("0");
+	}
+	let kept=&bytes[..bytes.len()-drop];
+	let first_dropped=bytes[bytes.len()-drop];
+	let rest_nonzero=bytes[bytes.len()-drop+1..].iter().any(|&c| c!=b'0');
+	let last_odd=kept.last().is_some_and(|&c| ((c-b'0')&1)!=0);
+	let mut r=kept.to_vec();
+	if first_dropped>b'5' || (first_dropped==b'5' && (rest_nonzero || last_odd)) {
+		let mut i=r.len();
+		loop {
+			if i==0 {
+				r.insert(0,b'1');
+				break;
+			}
+			i-=1;
+			if r[i]==b'9' {
+				r[i]=b'0';
+			} else {
+				r[i]+=1;
+				break;
+			}
+		}
+	}
+	if r.is_empty() {
+		return String::from("0");
+	}
+	String::from_utf8(r).unwrap()
+}
 
-// x is a 2.62 unsigned fixed in the range [0,1]
-// calculate sin_rev_64_fixed(x)
-// result is 2.62 unsigned fixed in the range [0,1]
-const fn sin_rev_64_fixed(xu:u64) -> u64 {
-	let x=xu as i64;
-	let x2=mults64hi(x,x)<<2;
-    let mut u=                      -2967547018i64;
-    u=mults64hi(u<<2,x2)          +262302065977i64;
-    u=mults64hi(u<<2,x2)        -16596547057622i64;
-    u=mults64hi(u<<2,x2)       +739904269452523i64;
-    u=mults64hi(u<<2,x2)     -21590780057842334i64;
-    u=mults64hi(u<<2,x2)    +367517370226484839i64;
-    u=mults64hi(u<<2,x2)   -2978983596875284700i64;
-    u=(mults64hi(u,x2)<<2)+(7244019458077115826i64+415);
-    return (mults64hi(u,x)<<2) as u64;
+// round sig*2^exp2 to a multiple of 10^target, nearest even, digits_estimate is an upper bound
+// on the number of digits in the result
+fn round_to_decimal(sig:u64, exp2:i32, target:i32, digits_estimate:i32) -> String {
+	if digits_estimate<=18 {
+		// fits in a u64
+		let (q,rem)=scaled_decimal_digits(sig,exp2,-target);
+		let round_up=rem==3 || (rem==2 && (q&1)!=0);
+		return (if round_up {q+1} else {q}).to_string();
+	}
+	let (digits,exp10)=exact_decimal_expansion(sig,exp2);
+	round_decimal_string(&digits,exp10,target)
 }
 
-// ./lolremez --stats --debug --long-double -d 35 -r "-1:1" "atan(x)*4/pi"
-// long double f(long double x) {
-//     x2 = x * x;
-//     u = -5.1448538374132849052e-5;
-//     u = u * x2 + 5.275833184610939186e-4;
-//     u = u * x2 + -2.5593891803515647842e-3;
-//     u = u * x2 + 7.8734531693353488079e-3;
-//     u = u * x2 + -1.744662472591652788e-2;
-//     u = u * x2 + 3.0173042794079607078e-2;
-//     u = u * x2 + -4.3417549325196383149e-2;
-//     u = u * x2 + 5.5090280539729516217e-2;
-//     u = u * x2 + -6.4974658268428946492e-2;
-//     u = u * x2 + 7.4312120990007820621e-2;
-//     u = u * x2 + -8.475459097263842221e-2;
-//     u = u * x2 + 9.7920586959909512452e-2;
-//     u = u * x2 + -1.1574658642078503641e-1;
-//     u = u * x2 + 1.4147086102567709565e-1;
-//     u = u * x2 + -1.8189135316383373911e-1;
-//     u = u * x2 + 2.5464790863720206134e-1;
-//     u = u * x2 + -4.2441318157402211219e-1;
-//     u = u * x2 + 1.2732395447351443229;
-//     return u * x;
-// }
+// x with exactly precision digits after the decimal point, without the sign
+fn pd_to_fixed_string(x:PseudoDouble, precision:usize) -> String {
+	let p=precision.min(100_000) as i32;
+	let r=if x.0==0 {
+		String::from("0")
+	} else {
+		let (_negative,sig,exp2)=pd_to_sig_exp(x);
+		let k=(((exp2+46) as i64*78913)>>18) as i32; // x<10^(k+2)
+		if k+2< -p {
+			String::from("0") // less than half of the last digit
+		} else {
+			round_to_decimal(sig,exp2,-p,k+2+p)
+		}
+	};
+	let mut s=String::new();
+	if r.len()<=p as usize {
+		s.push('0');
+	} else {
+		s.push_str(&r[..r.len()-p as usize]);
+	}
+	if precision>0 {
+		s.push('.');
+		if r.len()<p as usize {
+			s.push_str(&"0".repeat(p as usize-r.len()));
+			s.push_str(&r);
+		} else {
+			s.push_str(&r[r.len()-p as usize..]);
+		}
+		s.push_str(&"0".repeat(precision-p as usize));
+	}
+	s
+}
 
-// x is a 2.62 unsigned fixed in the range [0,1]
-// calculate atan_rev_64_fixed(x)
-// result is 2.62 unsigned fixed in the range [0,1]
-const fn atan_rev_64_fixed(xu:u64) -> u64 {
-	let x=xu as i64;
-	let x2=mults64hi(x,x)<<2;
-	let mut u=                 -237264505088513i64;
-	u=mults64hi(u<<2,x2)      +2433048613302551i64;
-	u=mults64hi(u<<2,x2)     -11803099298741644i64;
-	u=mults64hi(u<<2,x2)     +36309893897766633i64;
-	u=mults64hi(u<<2,x2)     -80458355317258810i64;
-	u=mults64hi(u<<2,x2)    +139148599586868171i64;
-	u=mults64hi(u<<2,x2)    -200228105177389631i64;
-	u=mults64hi(u<<2,x2)    +254059076516313023i64;
-	u=mults64hi(u<<2,x2)    -299642723088611246i64;
-	u=mults64hi(u<<2,x2)    +342704169369303486i64;
-	u=mults64hi(u<<2,x2)    -390861562186048719i64;
-	u=mults64hi(u<<2,x2)    +451579001799217900i64;
-	u=mults64hi(u<<2,x2)    -533786914277431708i64;
-	u=mults64hi(u<<2,x2)    +652419191806999136i64;
-	u=mults64hi(u<<2,x2)    -838825810258490282i64;
-	u=mults64hi(u<<2,x2)   +1174356199883959617i64;
-	u=mults64hi(u<<2,x2)   -1957260335501202067i64;
-	u=(mults64hi(u,x2)<<2)+(5871781006563917768i64+2243);
-    return (mults64hi(u,x)<<2) as u64;
+// x in scientific notation without the sign, with the shortest round trip digits if there is no precision
+fn pd_to_exp_string(x:PseudoDouble, precision:Option<usize>, upper:bool) -> String {
+	let (digits,k)=if x.0==0 {
+		(String::from("0").repeat(precision.unwrap_or(0)+1),0)
+	} else {
+		match precision {
+			None => {
+				let (_negative,c,n,k)=pd_to_shortest_decimal(x);
+				let digits=c.to_string();
+				let k=k+digits.len() as i32-n; // rounding can carry into a new digit
+				(String::from(digits.trim_end_matches('0')),k)
+			}
+			Some(precision) => {
+				let p=precision.min(100_000) as i32;
+				let (_negative,sig,exp2)=pd_to_sig_exp(x);
+				let k=decimal_exponent(sig,exp2);
+				let mut r=round_to_decimal(sig,exp2,k-p,p+1);
+				let mut k=k;
+				if r.len()>(p+1) as usize {
+					// rounded up to a power of 10
+					r.pop();
+					k+=1;
+				}
+				r.push_str(&"0".repeat(precision-p as usize));
+				(r,k)
+			}
+		}
+	};
+	let mut s=String::from(&digits[..1]);
+	if digits.len()>1 {
+		s.push('.');
+		s.push_str(&digits[1..]);
+	}
+	s.push(if upper {'E'} else {'e'});
+	s.push_str(&k.to_string());
+	s
 }
 
 impl SubsetOf<PseudoDouble> for PseudoDouble {
@@ -271,14 +2768,14 @@ impl SubsetOf<PseudoDouble> for f64 {
 		PseudoDouble::double_to_pseudodouble_implicit_safe(*self)
 	}
 
-    fn is_in_subset(superset: &PseudoDouble) -> bool { true }
+    fn is_in_subset(_superset: &PseudoDouble) -> bool { true }
 
     fn from_superset_unchecked(superset: &PseudoDouble) -> f64 { f64::from(*superset) }
 }
 
 impl SubsetOf<PseudoDouble> for f32 {
 
-    fn to_superset(&self) -> PseudoDouble {panic!("implicit conversion from f32 to PseudoDouble not allowed")}
+    fn to_superset(&self) -> PseudoDouble { PseudoDouble::from_f32(*self) }
 
     fn is_in_subset(_superset: &PseudoDouble) -> bool {true}
 
@@ -288,10 +2785,10 @@ impl SubsetOf<PseudoDouble> for f32 {
 impl From<i64> for PseudoDouble {
 	fn from(x : i64) -> Self {
 		if x==0 {
-			return PD_ZERO;
+			PD_ZERO
 		} else {
 			let lead_bits=(if x<0 {!x} else {x}).leading_zeros() as i32;
-			return PseudoDouble(((shift_left_signed(x,PSEUDO_DOUBLE_TOTAL_BITS+lead_bits-65))&EXP_MASK_INV)+PSEUDO_DOUBLE_EXP_BIAS+65-lead_bits as i64);
+			PseudoDouble(((shift_left_signed(x,PSEUDO_DOUBLE_TOTAL_BITS+lead_bits-65))&EXP_MASK_INV)+PSEUDO_DOUBLE_EXP_BIAS+65-lead_bits as i64)
 		}
     }
 }
@@ -299,15 +2796,16 @@ impl From<i64> for PseudoDouble {
 impl From<u64> for PseudoDouble {
     fn from(x : u64) -> Self {
 		if x==0 {
-			return PD_ZERO;
+			PD_ZERO
 		} else {
 			let lead_bits=x.leading_zeros() as i32;
-			return PseudoDouble(((shift_left_unsigned(x,PSEUDO_DOUBLE_TOTAL_BITS+lead_bits-65)) as i64&EXP_MASK_INV) +PSEUDO_DOUBLE_EXP_BIAS+65-lead_bits as i64);
+			PseudoDouble(((shift_left_unsigned(x,PSEUDO_DOUBLE_TOTAL_BITS+lead_bits-65)) as i64&EXP_MASK_INV) +PSEUDO_DOUBLE_EXP_BIAS+65-lead_bits as i64)
 		}
     }
 }
 
 impl From<PseudoDouble> for i64 {
+    #[allow(clippy::collapsible_if)]
     fn from(x: PseudoDouble) -> Self {
 		if x.0==0 {
 			return 0;
@@ -321,11 +2819,12 @@ impl From<PseudoDouble> for i64 {
 				panic!("Overflow converting PseudoDouble to i64");
 			}
 		}
-		return (x.0&EXP_MASK_INV)>>(PSEUDO_DOUBLE_TOTAL_BITS-exponent);
+		(x.0&EXP_MASK_INV)>>(PSEUDO_DOUBLE_TOTAL_BITS-exponent)
     }
 }
 
 impl From<PseudoDouble> for i128 {
+    #[allow(clippy::collapsible_if)]
     fn from(x: PseudoDouble) -> Self {
 		if x.0==0 {
 			return 0;
@@ -339,11 +2838,12 @@ impl From<PseudoDouble> for i128 {
 				panic!("Overflow converting PseudoDouble to i64");
 			}
 		}
-		return (((x.0&EXP_MASK_INV) as i128)<<64)>>(PSEUDO_DOUBLE_TOTAL_BITS+64-exponent);
+		(((x.0&EXP_MASK_INV) as i128)<<64)>>(PSEUDO_DOUBLE_TOTAL_BITS+64-exponent)
     }
 }
 
 impl From<PseudoDouble> for u64 {
+    #[allow(clippy::collapsible_if)]
     fn from(x: PseudoDouble) -> Self {
 		if x.0<0 {
 			panic!("Overflow converting negative PseudoDouble to u64");
@@ -364,7 +2864,7 @@ impl From<PseudoDouble> for u64 {
 				panic!("Overflow converting PseudoDouble to u64");
 			}
 		}
-		return vx>>(PSEUDO_DOUBLE_TOTAL_BITS-exponent);
+		vx>>(PSEUDO_DOUBLE_TOTAL_BITS-exponent)
     }
 }
 
@@ -387,7 +2887,7 @@ impl From<PseudoDouble> for f64 {
 				}
 				return f64::from_bits((((exponent+1) as u64)<<52)+sgn);
 			}
-			vx=-vx;
+			vx= -vx;
 		} else {
 			sgn=0;
 		}
@@ -397,7 +2897,7 @@ impl From<PseudoDouble> for f64 {
 		if exponent>0x7FF {
 			return f64::NAN;
 		}
-		return f64::from_bits(((vx&0x3FFFFFFFFFFFFFFF)>>10) as u64+((exponent as u64)<<52)+sgn);
+		f64::from_bits(((vx&0x3FFFFFFFFFFFFFFF)>>10) as u64+((exponent as u64)<<52)+sgn)
 	}
 }
 
@@ -420,7 +2920,7 @@ impl From<PseudoDouble> for f32 {
 				}
 				return f32::from_bits((((exponent+1) as u32)<<23)+sgn);
 			}
-			vx=-vx;
+			vx= -vx;
 		} else {
 			sgn=0;
 		}
@@ -430,79 +2930,85 @@ impl From<PseudoDouble> for f32 {
 		if exponent>0xFF {
 			return f32::NAN;
 		}
-		return f32::from_bits((((vx&0x3FFFFFFFFFFFFFFF)+0x4000000000)>>(32+7)) as u32+((exponent as u32)<<23)+sgn);
+		f32::from_bits((((vx&0x3FFFFFFFFFFFFFFF)+0x4000000000)>>(32+7)) as u32+((exponent as u32)<<23)+sgn)
 	}
 }
 
+impl From<f32> for PseudoDouble {
+    fn from(x : f32) -> Self {
+		PseudoDouble::from_f32(x)
+    }
+}
+
 impl From<i32> for PseudoDouble {
     fn from(x : i32) -> Self {
-		return PseudoDouble::from(x as i64);
+		PseudoDouble::from(x as i64)
     }
 }
 
 impl From<i16> for PseudoDouble {
     fn from(x : i16) -> Self {
-		return PseudoDouble::from(x as i64);
+		PseudoDouble::from(x as i64)
     }
 }
 
 impl From<i8> for PseudoDouble {
     fn from(x : i8) -> Self {
-		return PseudoDouble::from(x as i64);
+		PseudoDouble::from(x as i64)
     }
 }
 
 impl From<u32> for PseudoDouble {
     fn from(x : u32) -> Self {
-		return PseudoDouble::from(x as u64);
+		PseudoDouble::from(x as u64)
     }
 }
 
 impl From<u16> for PseudoDouble {
     fn from(x : u16) -> Self {
-		return PseudoDouble::from(x as u64);
+		PseudoDouble::from(x as u64)
     }
 }
 
 impl From<u8> for PseudoDouble {
     fn from(x : u8) -> Self {
-		return PseudoDouble::from(x as u64);
+		PseudoDouble::from(x as u64)
     }
 }
 
 impl From<PseudoDouble> for u32 {
     fn from(x: PseudoDouble) -> Self {
-		return u64::from(x) as u32;
+		u64::from(x) as u32
 	}
 }
 
 impl From<PseudoDouble> for u16 {
     fn from(x: PseudoDouble) -> Self {
-		return u64::from(x) as u16;
+		u64::from(x) as u16
 	}
 }
 
 impl From<PseudoDouble> for u8 {
     fn from(x: PseudoDouble) -> Self {
-		return u64::from(x) as u8;
+		u64::from(x) as u8
 	}
 }
 
 impl From<PseudoDouble> for i32 {
     fn from(x: PseudoDouble) -> Self {
-		return i64::from(x) as i32;
+		i64::from(x) as i32
 	}
 }
 
 impl From<PseudoDouble> for i16 {
     fn from(x: PseudoDouble) -> Self {
-		return i64::from(x) as i16;
+		i64::from(x) as i16
 	}
 }
 
 impl From<PseudoDouble> for i8 {
     fn from(x: PseudoDouble) -> Self {
-		return i64::from(x) as i8;
+		i64::from(x) as i8
 	}
 }
 
@@ -515,26 +3021,294 @@ impl FromPrimitive for PseudoDouble {
 	fn from_u64(n: u64) -> Option<Self> {
 		Some(PseudoDouble::from(n))
 	}
+
+	fn from_i128(n: i128) -> Option<Self> {
+		Some(u128_to_pd_rounded(n.unsigned_abs(),n<0))
+	}
+
+	fn from_u128(n: u128) -> Option<Self> {
+		Some(u128_to_pd_rounded(n,false))
+	}
+
+	fn from_f32(n: f32) -> Option<Self> {
+		if !n.is_finite() {
+			return None;
+		}
+		Some(PseudoDouble::from_f32(n))
+	}
+
+	fn from_f64(n: f64) -> Option<Self> {
+		if !n.is_finite() {
+			return None;
+		}
+		Some(f64_to_pd_rounded(n))
+	}
+}
+
+/// Conversions truncate towards zero and return `None` when the result is out of range.
+/// `to_f64` and `to_f32` return an infinity when out of range.
+impl ToPrimitive for PseudoDouble {
+
+	fn to_i64(&self) -> Option<i64> {
+		let t=self.trunc();
+		if t<PseudoDouble::pdc2(-1,63) || t>=PseudoDouble::pdc2(1,63) {
+			return None;
+		}
+		Some(i64::from(t))
+	}
+
+	fn to_u64(&self) -> Option<u64> {
+		let t=self.trunc();
+		if t.0<0 || t>=PseudoDouble::pdc2(1,64) {
+			return None;
+		}
+		Some(u64::from(t))
+	}
+
+	fn to_i128(&self) -> Option<i128> {
+		let t=self.trunc();
+		if t<PseudoDouble::pdc2(-1,127) || t>=PseudoDouble::pdc2(1,127) {
+			return None;
+		}
+		if t.0==0 {
+			return Some(0);
+		}
+		let (negative,sig,exp2)=pd_to_sig_exp(t);
+		let magnitude=if exp2>=0 {(sig as u128)<<exp2} else {(sig>>-exp2) as u128};
+		Some(if negative {(magnitude as i128).wrapping_neg()} else {magnitude as i128})
+	}
+
+	fn to_u128(&self) -> Option<u128> {
+		let t=self.trunc();
+		if t.0<0 || t>=PseudoDouble::pdc2(1,128) {
+			return None;
+		}
+		if t.0==0 {
+			return Some(0);
+		}
+		let (_negative,sig,exp2)=pd_to_sig_exp(t);
+		Some(if exp2>=0 {(sig as u128)<<exp2} else {(sig>>-exp2) as u128})
+	}
+
+	fn to_f64(&self) -> Option<f64> {
+		if num_traits::Signed::abs(self)>=PseudoDouble::pdc2(1,1024) {
+			return Some(if self.0<0 {f64::NEG_INFINITY} else {f64::INFINITY});
+		}
+		Some(f64::from(*self))
+	}
+
+	fn to_f32(&self) -> Option<f32> {
+		Some(self.to_f64()? as f32)
+	}
+}
+
+/// Integers are converted exactly when they fit in 47 bits and otherwise rounded to nearest even,
+/// as are floating point values. NaN and infinities give `None`.
+impl num_traits::NumCast for PseudoDouble {
+	fn from<T: ToPrimitive>(n: T) -> Option<Self> {
+		let f=n.to_f64()?;
+		if !f.is_finite() {
+			return None;
+		}
+		if f.fract()==0.0 {
+			if let Some(i)=n.to_i64() {
+				return round_u64_to_pd(i.unsigned_abs(),0,false,i<0).ok();
+			}
+			if let Some(u)=n.to_u64() {
+				return round_u64_to_pd(u,0,false,false).ok();
+			}
+		}
+		Some(f64_to_pd_rounded(f))
+	}
+}
+
+macro_rules! impl_as_primitive_int {
+	($($t:ty)*) => {$(
+		/// Truncates towards zero and saturates, like `as` from `f64`.
+		impl AsPrimitive<$t> for PseudoDouble {
+			fn as_(self) -> $t {
+				match self.to_i128() {
+					Some(i) => return i.clamp(<$t>::MIN as i128,<$t>::MAX as i128) as $t,
+					None => return if self.0<0 {<$t>::MIN} else {<$t>::MAX},
+				}
+			}
+		}
+
+		/// Rounds to nearest even when the integer needs more than 47 bits.
+		impl AsPrimitive<PseudoDouble> for $t {
+			fn as_(self) -> PseudoDouble {
+				return u128_to_pd_rounded((self as i128).unsigned_abs(),(self as i128)<0);
+			}
+		}
+	)*}
+}
+
+impl_as_primitive_int!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 usize);
+
+impl AsPrimitive<u128> for PseudoDouble {
+	fn as_(self) -> u128 {
+		if self.0<0 {
+			return 0;
+		}
+		self.to_u128().unwrap_or(u128::MAX)
+	}
+}
+
+impl AsPrimitive<PseudoDouble> for u128 {
+	fn as_(self) -> PseudoDouble {
+		u128_to_pd_rounded(self,false)
+	}
+}
+
+impl AsPrimitive<f64> for PseudoDouble {
+	fn as_(self) -> f64 {
+		self.to_f64().unwrap()
+	}
+}
+
+impl AsPrimitive<f32> for PseudoDouble {
+	fn as_(self) -> f32 {
+		self.to_f32().unwrap()
+	}
+}
+
+/// NaN gives zero and infinities give `MAX` or `MIN`, the same as the `Float` implementation.
+impl AsPrimitive<PseudoDouble> for f64 {
+	fn as_(self) -> PseudoDouble {
+		if self.is_nan() {
+			return PD_ZERO;
+		}
+		if self.is_infinite() {
+			return if self>0.0 {PseudoDouble::MAX} else {PseudoDouble::MIN};
+		}
+		f64_to_pd_rounded(self)
+	}
+}
+
+/// NaN gives zero and infinities give `MAX` or `MIN`, the same as the `Float` implementation.
+impl AsPrimitive<PseudoDouble> for f32 {
+	fn as_(self) -> PseudoDouble {
+		(self as f64).as_()
+	}
+}
+
+impl AsPrimitive<PseudoDouble> for PseudoDouble {
+	fn as_(self) -> PseudoDouble {
+		self
+	}
+}
+
+impl Pow<i32> for PseudoDouble {
+	type Output = Self;
+	fn pow(self, n: i32) -> Self {
+		self.powi(n)
+	}
+}
+
+impl Pow<PseudoDouble> for PseudoDouble {
+	type Output = Self;
+	fn pow(self, n: PseudoDouble) -> Self {
+		self.powf(n)
+	}
+}
+
+/// The same definitions as `f64::div_euclid` and `f64::rem_euclid`.
+impl Euclid for PseudoDouble {
+	fn div_euclid(&self, v: &Self) -> Self {
+		PseudoDouble::div_euclid(*self,*v)
+	}
+
+	fn rem_euclid(&self, v: &Self) -> Self {
+		PseudoDouble::rem_euclid(*self,*v)
+	}
+}
+
+impl CheckedAdd for PseudoDouble {
+	fn checked_add(&self, v: &Self) -> Option<Self> {
+		PseudoDouble::checked_add(*self,*v)
+	}
+}
+
+impl CheckedSub for PseudoDouble {
+	fn checked_sub(&self, v: &Self) -> Option<Self> {
+		PseudoDouble::checked_sub(*self,*v)
+	}
+}
+
+impl CheckedMul for PseudoDouble {
+	fn checked_mul(&self, v: &Self) -> Option<Self> {
+		PseudoDouble::checked_mul(*self,*v)
+	}
+}
+
+impl CheckedDiv for PseudoDouble {
+	fn checked_div(&self, v: &Self) -> Option<Self> {
+		PseudoDouble::checked_div(*self,*v)
+	}
+}
+
+/// Not fused: the product is rounded before the addition.
+impl MulAdd for PseudoDouble {
+	type Output = Self;
+	fn mul_add(self, a: Self, b: Self) -> Self {
+		self.const_mul(a).const_add(b)
+	}
+}
+
+impl MulAddAssign for PseudoDouble {
+	fn mul_add_assign(&mut self, a: Self, b: Self) {
+		*self=self.const_mul(a).const_add(b);
+	}
+}
+
+impl Inv for PseudoDouble {
+	type Output = Self;
+	fn inv(self) -> Self {
+		PD_ONE/self
+	}
+}
+
+impl FloatConst for PseudoDouble {
+	fn E() -> Self { consts::E }
+	fn FRAC_1_PI() -> Self { consts::FRAC_1_PI }
+	fn FRAC_1_SQRT_2() -> Self { consts::FRAC_1_SQRT_2 }
+	fn FRAC_2_PI() -> Self { consts::FRAC_2_PI }
+	fn FRAC_2_SQRT_PI() -> Self { consts::FRAC_2_SQRT_PI }
+	fn FRAC_PI_2() -> Self { consts::FRAC_PI_2 }
+	fn FRAC_PI_3() -> Self { consts::FRAC_PI_3 }
+	fn FRAC_PI_4() -> Self { consts::FRAC_PI_4 }
+	fn FRAC_PI_6() -> Self { consts::FRAC_PI_6 }
+	fn FRAC_PI_8() -> Self { consts::FRAC_PI_8 }
+	fn LN_10() -> Self { consts::LN_10 }
+	fn LN_2() -> Self { consts::LN_2 }
+	fn LOG10_E() -> Self { consts::LOG10_E }
+	fn LOG2_E() -> Self { consts::LOG2_E }
+	fn PI() -> Self { consts::PI }
+	fn SQRT_2() -> Self { consts::SQRT_2 }
+	fn TAU() -> Self { consts::TAU }
+	fn LOG10_2() -> Self { consts::LOG10_2 }
+	fn LOG2_10() -> Self { consts::LOG2_10 }
 }
 
 impl Zero for PseudoDouble {
 	fn zero() -> Self {
-		return PD_ZERO;
+		PD_ZERO
 	}
 
 	fn is_zero(&self) -> bool {
-		return self.0==0;
+		self.0==0
 	}
 }
 
 impl One for PseudoDouble {
 	fn one() -> Self {
-		return PD_ONE;
+		PD_ONE
 	}
 }
 
 impl Neg for PseudoDouble {
     type Output = Self;
+	#[allow(clippy::collapsible_if)]
 	fn neg(self) -> Self {
 		let expx=self.0&EXP_MASK;
 		let vx=self.0&EXP_MASK_INV;
@@ -557,12 +3331,13 @@ impl Neg for PseudoDouble {
 				return PseudoDouble((vx<<1)+expx-1);
 			}
 		}
-		return PseudoDouble(-vx+expx as i64);
+		PseudoDouble(-vx+expx)
 	}
 }
 
 impl Add for PseudoDouble {
     type Output = Self;
+	#[allow(clippy::collapsible_if)]
 	fn add(self, other: Self) -> Self {
 		let expx=(self.0&EXP_MASK) as i32;
 		let expy=(other.0&EXP_MASK) as i32;
@@ -573,8 +3348,8 @@ impl Add for PseudoDouble {
 		if ydiffx<=-(PSEUDO_DOUBLE_TOTAL_BITS-1) {
 			return self;
 		}
-		let mut vx=((self.0&EXP_MASK_INV)>>1) as i64;
-		let mut vy=((other.0&EXP_MASK_INV)>>1) as i64;
+		let mut vx=(self.0&EXP_MASK_INV)>>1;
+		let mut vy=(other.0&EXP_MASK_INV)>>1;
 		let exp_max;
 		if ydiffx>=0 {
 			exp_max=expy+1;
@@ -583,10 +3358,10 @@ impl Add for PseudoDouble {
 			exp_max=expx+1;
 			vy>>=-ydiffx;
 		}
-		let vr=((vx+vy+PSEUDO_DOUBLE_HALF_ULP)&!PSEUDO_DOUBLE_HALF_ULP) as i64;
+		let vr=(vx+vy+PSEUDO_DOUBLE_HALF_ULP)&!PSEUDO_DOUBLE_HALF_ULP;
 		if vr==0 {
 			// special case - a mantissa of zero will always make the whole word zero. Makes comparisons much easier
-			return PD_ZERO
+			PD_ZERO
 		} else {
 			let mut leading_bits=(if vr>0 {vr} else {!vr}).leading_zeros() as i32 - 1;
 			if leading_bits>exp_max {
@@ -603,13 +3378,14 @@ impl Add for PseudoDouble {
 					panic!("Overflow in PseudoDouble add");
 				}
 			}
-			return PseudoDouble(((vr<<leading_bits)&EXP_MASK_INV)+new_exponent as i64);
+			PseudoDouble(((vr<<leading_bits)&EXP_MASK_INV)+new_exponent as i64)
 		}
 	}
 }
 
 impl Sub for PseudoDouble {
     type Output = Self;
+	#[allow(clippy::collapsible_if)]
 	fn sub(self, other: Self) -> Self {
 		let expx=(self.0&EXP_MASK) as i32;
 		let expy=(other.0&EXP_MASK) as i32;
@@ -620,8 +3396,8 @@ impl Sub for PseudoDouble {
 		if ydiffx<=-(PSEUDO_DOUBLE_TOTAL_BITS-1) {
 			return self;
 		}
-		let mut vx=((self.0&EXP_MASK_INV)>>1) as i64;
-		let mut vy=((other.0&EXP_MASK_INV)>>1) as i64;
+		let mut vx=(self.0&EXP_MASK_INV)>>1;
+		let mut vy=(other.0&EXP_MASK_INV)>>1;
 		let exp_max;
 		if ydiffx>=0 {
 			exp_max=expy+1;
@@ -630,10 +3406,10 @@ impl Sub for PseudoDouble {
 			exp_max=expx+1;
 			vy>>=-ydiffx;
 		}
-		let vr=((vx-vy+PSEUDO_DOUBLE_HALF_ULP)&!PSEUDO_DOUBLE_HALF_ULP) as i64;
+		let vr=(vx-vy+PSEUDO_DOUBLE_HALF_ULP)&!PSEUDO_DOUBLE_HALF_ULP;
 		if vr==0 {
 			// special case - a mantissa of zero will always make the whole word zero. Makes comparisons much easier
-			return PD_ZERO
+			PD_ZERO
 		} else {
 			let mut leading_bits=(if vr>0 {vr} else {!vr}).leading_zeros() as i32 - 1;
 			if leading_bits>exp_max {
@@ -650,13 +3426,14 @@ impl Sub for PseudoDouble {
 					panic!("Overflow in PseudoDouble add");
 				}
 			}
-			return PseudoDouble(((vr<<leading_bits)&EXP_MASK_INV)+new_exponent as i64);
+			PseudoDouble(((vr<<leading_bits)&EXP_MASK_INV)+new_exponent as i64)
 		}
 	}
 }
 
 impl Mul for PseudoDouble {
     type Output = Self;
+	#[allow(clippy::collapsible_if)]
 	fn mul(self, other: Self) -> Self {
 		let expx=(self.0&EXP_MASK) as i32;
 		let expy=(other.0&EXP_MASK) as i32;
@@ -678,12 +3455,13 @@ impl Mul for PseudoDouble {
 				return PD_ZERO;
 			}
 		}
-		return PseudoDouble(((vr<<leading_bits)&EXP_MASK_INV)+new_exponent as i64);
+		PseudoDouble(((vr<<leading_bits)&EXP_MASK_INV)+new_exponent as i64)
 	}
 }
 
 impl Div for PseudoDouble {
     type Output = Self;
+	#[allow(clippy::collapsible_if)]
 	fn div(self, other: Self) -> Self {
 		let expx=(self.0&EXP_MASK) as i32;
 		let expy=(other.0&EXP_MASK) as i32;
@@ -692,51 +3470,147 @@ impl Div for PseudoDouble {
 		if vy==0 { // leave this one in to avoid division by zero signal
 			panic!("Division by zero");
 		}
-		let vxb=((vx>>2) as i128)<<64;
-		let vyb=vy as i128;
+		let vxb=(vx>>2)<<64;
+		let vyb=vy;
 		let vrb=vxb/vyb;
 		let vr=vrb as i64;
 		if vr==0 {
 			return PD_ZERO;
 		}
-		let leading_bits=(if vr>0 {vr} else {!vr}).leading_zeros() as i32 - 1;
-		let new_exponent=2+expx-expy+PSEUDO_DOUBLE_EXP_BIAS as i32-leading_bits;
-		if cfg!(feature="panic_on_pseudodouble_overflow") {
-			if new_exponent as u32>EXP_MASK as u32 {
-				panic!("Overflow in PseudoDouble add");
+		let leading_bits=(if vr>0 {vr} else {!vr}).leading_zeros() as i32 - 1;
+		let new_exponent=2+expx-expy+PSEUDO_DOUBLE_EXP_BIAS as i32-leading_bits;
+		if cfg!(feature="panic_on_pseudodouble_overflow") {
+			if new_exponent as u32>EXP_MASK as u32 {
+				panic!("Overflow in PseudoDouble add");
+			}
+		}
+		if cfg!(feature="check_on_pseudodouble_underflow") {
+			if new_exponent<0 {
+				return PD_ZERO;
+			}
+		}
+		PseudoDouble(((vr<<leading_bits)&EXP_MASK_INV)+new_exponent as i64)
+	}
+}
+
+impl AddAssign for PseudoDouble {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for PseudoDouble {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl MulAssign for PseudoDouble {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl DivAssign for PseudoDouble {
+    fn div_assign(&mut self, other: Self) {
+        *self = *self / other;
+    }
+}
+
+macro_rules! impl_ref_ops {
+	($($op:ident $f:ident $op_assign:ident $f_assign:ident),*) => {$(
+		impl<'a> $op<&'a PseudoDouble> for PseudoDouble {
+			type Output = PseudoDouble;
+			fn $f(self, other: &'a PseudoDouble) -> PseudoDouble {
+				return $op::$f(self,*other);
+			}
+		}
+
+		impl<'a> $op<PseudoDouble> for &'a PseudoDouble {
+			type Output = PseudoDouble;
+			fn $f(self, other: PseudoDouble) -> PseudoDouble {
+				return $op::$f(*self,other);
+			}
+		}
+
+		impl<'a, 'b> $op<&'b PseudoDouble> for &'a PseudoDouble {
+			type Output = PseudoDouble;
+			fn $f(self, other: &'b PseudoDouble) -> PseudoDouble {
+				return $op::$f(*self,*other);
+			}
+		}
+
+		impl<'a> $op_assign<&'a PseudoDouble> for PseudoDouble {
+			fn $f_assign(&mut self, other: &'a PseudoDouble) {
+				*self=$op::$f(*self,*other);
+			}
+		}
+	)*}
+}
+
+impl_ref_ops!(Add add AddAssign add_assign, Sub sub SubAssign sub_assign, Mul mul MulAssign mul_assign,
+	Div div DivAssign div_assign, Rem rem RemAssign rem_assign);
+
+impl Neg for &PseudoDouble {
+	type Output = PseudoDouble;
+	fn neg(self) -> PseudoDouble {
+		-*self
+	}
+}
+
+// The integer is converted with From, so x*n is the same as x*PseudoDouble::from(n)
+macro_rules! impl_int_ops {
+	($($t:ty)*) => {$(
+		impl_int_ops!(@op $t, Add add AddAssign add_assign, Sub sub SubAssign sub_assign, Mul mul MulAssign mul_assign,
+			Div div DivAssign div_assign, Rem rem RemAssign rem_assign);
+	)*};
+	(@op $t:ty, $($op:ident $f:ident $op_assign:ident $f_assign:ident),*) => {$(
+		impl $op<$t> for PseudoDouble {
+			type Output = PseudoDouble;
+			fn $f(self, other: $t) -> PseudoDouble {
+				return $op::$f(self,PseudoDouble::from(other));
+			}
+		}
+
+		impl $op<PseudoDouble> for $t {
+			type Output = PseudoDouble;
+			fn $f(self, other: PseudoDouble) -> PseudoDouble {
+				return $op::$f(PseudoDouble::from(self),other);
 			}
 		}
-		if cfg!(feature="check_on_pseudodouble_underflow") {
-			if new_exponent<0 {
-				return PD_ZERO;
+
+		impl $op_assign<$t> for PseudoDouble {
+			fn $f_assign(&mut self, other: $t) {
+				*self=$op::$f(*self,other);
 			}
 		}
-		return PseudoDouble(((vr<<leading_bits)&EXP_MASK_INV)+new_exponent as i64);
-	}
+	)*};
 }
 
-impl AddAssign for PseudoDouble {
-    fn add_assign(&mut self, other: Self) {
-        *self = *self + other;
-    }
+impl_int_ops!(i32 i64 u32);
+
+impl Sum for PseudoDouble {
+	fn sum<I: Iterator<Item = PseudoDouble>>(iter: I) -> Self {
+		iter.fold(PD_ZERO,|a,b| a+b)
+	}
 }
 
-impl SubAssign for PseudoDouble {
-    fn sub_assign(&mut self, other: Self) {
-        *self = *self - other;
-    }
+impl<'a> Sum<&'a PseudoDouble> for PseudoDouble {
+	fn sum<I: Iterator<Item = &'a PseudoDouble>>(iter: I) -> Self {
+		iter.fold(PD_ZERO,|a,b| a+*b)
+	}
 }
 
-impl MulAssign for PseudoDouble {
-    fn mul_assign(&mut self, other: Self) {
-        *self = *self * other;
-    }
+impl Product for PseudoDouble {
+	fn product<I: Iterator<Item = PseudoDouble>>(iter: I) -> Self {
+		iter.fold(PD_ONE,|a,b| a*b)
+	}
 }
 
-impl DivAssign for PseudoDouble {
-    fn div_assign(&mut self, other: Self) {
-        *self = *self / other;
-    }
+impl<'a> Product<&'a PseudoDouble> for PseudoDouble {
+	fn product<I: Iterator<Item = &'a PseudoDouble>>(iter: I) -> Self {
+		iter.fold(PD_ONE,|a,b| a**b)
+	}
 }
 
 impl Ord for PseudoDouble {
@@ -748,28 +3622,17 @@ impl Ord for PseudoDouble {
 		// signs are the same, check exponent
 		let expdiff=(other.0&EXP_MASK)-(self.0&EXP_MASK);
 		if expdiff!=0 {
-			return  if (expdiff>0)^neg {Ordering::Less} else {Ordering::Greater};
+			if (expdiff>0)^neg {Ordering::Less} else {Ordering::Greater}
 		} else {
 			// exponents are the same so don't need to mask off, check mantissa
-			return self.0.cmp(&other.0);
+			self.0.cmp(&other.0)
 		}
     }
 }
 
 impl PartialOrd for PseudoDouble {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-		let neg=(self.0>>(PSEUDO_DOUBLE_TOTAL_BITS-1))!=0;
-		if ((self.0^other.0)>>(PSEUDO_DOUBLE_TOTAL_BITS-1))!=0 {
-			return if neg {Some(Ordering::Less)} else {Some(Ordering::Greater)};
-		}
-		// signs are the same, check exponent
-		let expdiff=(other.0&EXP_MASK)-(self.0&EXP_MASK);
-		if expdiff!=0 {
-			return  if (expdiff>0)^neg {Some(Ordering::Less)} else {Some(Ordering::Greater)};
-		} else {
-			// exponents are the same so don't need to mask off, check mantissa
-			return Some(self.0.cmp(&other.0));
-		}
+		Some(self.cmp(other))
     }
 }
 
@@ -779,37 +3642,98 @@ impl Default for PseudoDouble {
 
 impl Bounded for PseudoDouble {
     fn min_value() -> Self {
-        PseudoDouble(-0x8000000000000000i64+EXP_MASK)
+        PseudoDouble::MIN
     }
 
     fn max_value() -> Self {
-        PseudoDouble(-1i64)
+        PseudoDouble::MAX
     }
 }
 
-fn simulated_parse_error() -> std::num::ParseFloatError {
-    "NaNxyz".parse::<f64>().unwrap_err()
+/// The parts of a `PseudoDouble`, as returned by `describe`. The value is exactly `mantissa*2^exponent`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PseudoDoubleParts {
+	/// The shortest decimal that parses back to the same value
+	pub decimal: String,
+	/// The signed 48 bit mantissa
+	pub mantissa: i64,
+	/// The unbiased exponent
+	pub exponent: i32,
+	/// The raw bits
+	pub bits: u64,
+}
+
+/// An error which can be returned when parsing a `PseudoDouble`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ParsePdError {
+	/// The string was empty or only contained whitespace.
+	Empty,
+	/// An unexpected character (or the end of the string) at the given byte position.
+	InvalidDigit(usize),
+	/// The value is too large in magnitude to be represented.
+	Overflow,
+	/// The value is nonzero but too small in magnitude to be represented.
+	Underflow,
+	/// `from_str_radix` was called with a radix other than 10 or 16.
+	UnsupportedRadix(u32),
+}
+
+impl Display for ParsePdError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			ParsePdError::Empty => write!(f, "cannot parse PseudoDouble from empty string"),
+			ParsePdError::InvalidDigit(pos) => write!(f, "invalid digit found in string at position {}", pos),
+			ParsePdError::Overflow => write!(f, "number too large to fit in PseudoDouble"),
+			ParsePdError::Underflow => write!(f, "number too small to fit in PseudoDouble"),
+			ParsePdError::UnsupportedRadix(radix) => write!(f, "radix {} is not supported, only 10 and 16", radix),
+		}
+	}
+}
+
+impl std::error::Error for ParsePdError {}
+
+/// An error returned by the `try_` functions when the argument is outside the domain of the function.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct PdDomainError {
+	/// The name of the function.
+	pub function: &'static str,
+}
+
+impl Display for PdDomainError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(f, "argument outside the domain of PseudoDouble {}", self.function)
+	}
 }
 
+impl std::error::Error for PdDomainError {}
+
 impl Num for PseudoDouble {
 
-	type FromStrRadixErr = std::num::ParseFloatError;
+	type FromStrRadixErr = ParsePdError;
 
-    fn from_str_radix(str: &str, radix: u32) -> Result<PseudoDouble, std::num::ParseFloatError> {
-        // For floating point, radix != 10 isn't usually supported
-        if radix != 10 {
-			return Err(simulated_parse_error())
-        }
-		let result=PseudoDouble::string_to_pd(str);
-		match result {
-			Some(x) => { return Ok(x) }
-			None    => { return Err(simulated_parse_error()) }
+    fn from_str_radix(str: &str, radix: u32) -> Result<PseudoDouble, ParsePdError> {
+        // hexadecimal is exact, other radixes aren't supported
+        match radix {
+			10 => PseudoDouble::string_to_pd(str),
+			16 => PseudoDouble::hex_string_to_pd(str),
+			_  => Err(ParsePdError::UnsupportedRadix(radix)),
 		}
     }
 }
 
+/// Parses with `string_to_pd`, so `"1.5".parse::<PseudoDouble>()` works.
+impl FromStr for PseudoDouble {
+
+	type Err = ParsePdError;
+
+	fn from_str(s: &str) -> Result<PseudoDouble, ParsePdError> {
+		PseudoDouble::string_to_pd(s)
+	}
+}
+
 impl Signed for PseudoDouble {
 
+	#[allow(clippy::collapsible_if)]
 	fn abs(&self) -> Self {
 		if self.0>=0 {
 			return *self;
@@ -824,40 +3748,40 @@ impl Signed for PseudoDouble {
 						panic!("Overflow in PseudoDouble abs");
 					}
 				}
-				return PseudoDouble((vx>>1)+expx+1);
+				return PseudoDouble(((vx as u64)>>1) as i64+expx+1);
 			}
 		}
-		return PseudoDouble(-vx+expx as i64);
+		PseudoDouble(-vx+expx)
 	}
 
 	fn abs_sub(&self, other:&Self) -> Self {
 		let t=self.const_sub(*other);
-		return num_traits::Signed::abs(&t)
+		num_traits::Signed::abs(&t)
 	}
 
 	fn signum(&self) -> Self {
 		if self.0>0 {
-			return PD_ONE;
+			PD_ONE
 		} else if self.0<0 {
-			return -PD_ONE;
+			-PD_ONE
 		} else {
-			return PD_ZERO;
+			PD_ZERO
 		}
 	}
 
 	fn is_positive(&self) -> bool {
-		return self.0>0;
+		self.0>0
 	}
 
 	fn is_negative(&self) -> bool {
-		return self.0<0;
+		self.0<0
 	}
 }
 
 impl Rem for PseudoDouble {
     type Output = Self;
 	fn rem(self, other: Self) -> Self {
-		return self - other * (self / other).trunc();
+		self.fmod(other)
 	}
 }
 
@@ -871,7 +3795,7 @@ impl AbsDiffEq for PseudoDouble {
 
 	type Epsilon = PseudoDouble;
 
-	fn default_epsilon() -> Self::Epsilon {return PD_EPSILON;}
+	fn default_epsilon() -> Self::Epsilon {PseudoDouble::EPSILON}
 
 	fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
 		(*self-*other).abs()<=epsilon
@@ -880,7 +3804,7 @@ impl AbsDiffEq for PseudoDouble {
 
 impl RelativeEq for PseudoDouble {
 
-	fn default_max_relative() -> Self::Epsilon {return PD_EPSILON;}
+	fn default_max_relative() -> Self::Epsilon {PseudoDouble::EPSILON}
 
 	fn relative_eq(
 		&self,
@@ -910,7 +3834,7 @@ impl RelativeEq for PseudoDouble {
 
 impl UlpsEq for PseudoDouble {
 
-	fn default_max_ulps() -> u32 {return 4;}
+	fn default_max_ulps() -> u32 {4}
 
 	fn ulps_eq(&self, other: &Self, epsilon: Self::Epsilon, max_ulps: u32) -> bool {
 		if AbsDiffEq::abs_diff_eq(self, other, epsilon) {
@@ -952,12 +3876,12 @@ impl SimdValue for PseudoDouble {
         *self
     }
 
-    fn replace(&mut self, i: usize, val: Self) -> () {
+    fn replace(&mut self, i: usize, val: Self) {
         assert_eq!(i, 0);
         *self=val
     }
 
-    unsafe fn replace_unchecked(&mut self, _i: usize, val: Self) -> () {
+    unsafe fn replace_unchecked(&mut self, _i: usize, val: Self) {
         *self=val
     }
 
@@ -973,6 +3897,21 @@ impl SimdValue for PseudoDouble {
 impl Field for PseudoDouble {}
 
 impl PseudoDouble {
+	/// Number of significant bits in the mantissa, not counting the sign.
+	pub const MANTISSA_DIGITS: u32 = (PSEUDO_DOUBLE_TOTAL_BITS-PSEUDO_DOUBLE_EXP_BITS-1) as u32;
+	/// One greater than the minimum possible power of 2 exponent, with the same meaning as `f64::MIN_EXP`.
+	pub const MIN_EXP: i32 = -(PSEUDO_DOUBLE_EXP_BIAS as i32)-1;
+	/// Maximum possible power of 2 exponent, with the same meaning as `f64::MAX_EXP`.
+	pub const MAX_EXP: i32 = (EXP_MASK-PSEUDO_DOUBLE_EXP_BIAS) as i32-1;
+	/// Largest finite value.
+	pub const MAX: PseudoDouble = PseudoDouble(i64::MAX);
+	/// Smallest finite value, `-2^MAX_EXP`. It is one ulp below `-MAX`, so its negation overflows.
+	pub const MIN: PseudoDouble = PseudoDouble(i64::MIN+EXP_MASK);
+	/// Smallest positive normalized value. Its negation is not representable, and underflows to zero
+	/// with the `check_on_pseudodouble_underflow` feature.
+	pub const MIN_POSITIVE: PseudoDouble = PseudoDouble(1i64<<(PSEUDO_DOUBLE_TOTAL_BITS-2));
+	/// The difference between 1 and the next larger value.
+	pub const EPSILON: PseudoDouble = PseudoDouble::pdc2(1,1-PseudoDouble::MANTISSA_DIGITS as i32);
 
 	// pub const fn abs(&self) -> Self {
 	// 	if self.0>=0 {
@@ -1046,7 +3985,7 @@ impl PseudoDouble {
 		}
 		let lead_bits=(if negative {!d} else {d}).leading_zeros() as i32;
 		let exp=nexp as i64+PSEUDO_DOUBLE_EXP_BIAS+65-lead_bits as i64;
-		return PseudoDouble(((shift_left_signed(d,PSEUDO_DOUBLE_TOTAL_BITS+lead_bits-65))&EXP_MASK_INV)+exp as i64);
+		PseudoDouble(((shift_left_signed(d,PSEUDO_DOUBLE_TOTAL_BITS+lead_bits-65))&EXP_MASK_INV)+exp)
 	}
 
 	pub const fn pdc2(d: i64, e: i32) -> PseudoDouble {
@@ -1054,25 +3993,26 @@ impl PseudoDouble {
 			return PD_ZERO;
 		}
 		let lead_bits=(if d<0 {!d} else {d}).leading_zeros() as i32;
-		return PseudoDouble(((shift_left_signed(d,PSEUDO_DOUBLE_TOTAL_BITS+lead_bits-65))&EXP_MASK_INV)+PSEUDO_DOUBLE_EXP_BIAS+65-lead_bits as i64+e as i64);
+		PseudoDouble(((shift_left_signed(d,PSEUDO_DOUBLE_TOTAL_BITS+lead_bits-65))&EXP_MASK_INV)+PSEUDO_DOUBLE_EXP_BIAS+65-lead_bits as i64+e as i64)
 	}
 
 	pub const fn gt_zero(x : PseudoDouble) -> bool {
-		return x.0>0;
+		x.0>0
 	}
 
 	pub const fn gte_zero(x : PseudoDouble) -> bool {
-		return x.0>=0;
+		x.0>=0
 	}
 
 	pub const fn lt_zero(x : PseudoDouble) -> bool {
-		return x.0<0;
+		x.0<0
 	}
 
 	pub const fn lte_zero(x : PseudoDouble) -> bool {
-		return x.0<=0;
+		x.0<=0
 	}
 
+	#[allow(clippy::collapsible_if)]
 	pub fn double_to_pseudodouble_unsafe(f:f64) -> PseudoDouble {
 		if f==0.0 {
 			return PD_ZERO;
@@ -1080,7 +4020,7 @@ impl PseudoDouble {
 		let i=f64::to_bits(f) as i64;
 		let negative=i<0;
 		let raw_exponent=(((i as u64)>>52)&0x7FF) as i64;
-		let exponent=raw_exponent+PSEUDO_DOUBLE_EXP_BIAS as i64-0x3FF+2;
+		let exponent=raw_exponent+PSEUDO_DOUBLE_EXP_BIAS-0x3FF+2;
 		let old_mantissa=i&0xFFFFFFFFFFFFFi64;
 		let mantissa=old_mantissa+0x10000000000000i64; // add in the implied bit
 		if negative {
@@ -1103,12 +4043,13 @@ impl PseudoDouble {
 		let mantissa=shift_left_signed(mantissa,PSEUDO_DOUBLE_TOTAL_BITS-54);
 		//mantissa=(mantissa+PSEUDO_DOUBLE_HALF_ULP)&~PSEUDO_DOUBLE_HALF_ULP;
 		if negative {
-			return PseudoDouble(-(mantissa&EXP_MASK_INV)+exponent);
+			PseudoDouble(-(mantissa&EXP_MASK_INV)+exponent)
 		} else {
-			return PseudoDouble((mantissa&EXP_MASK_INV)+exponent);
+			PseudoDouble((mantissa&EXP_MASK_INV)+exponent)
 		}
 	}
 
+	#[allow(clippy::collapsible_if)]
 	pub fn double_to_pseudodouble_implicit_safe(f:f64) -> PseudoDouble {
 		if f==0.0 {
 			return PD_ZERO;
@@ -1116,7 +4057,7 @@ impl PseudoDouble {
 		let i=f64::to_bits(f) as i64;
 		let negative=i<0;
 		let raw_exponent=(((i as u64)>>52)&0x7FF) as i64;
-		let exponent=raw_exponent+PSEUDO_DOUBLE_EXP_BIAS as i64-0x3FF+2;
+		let exponent=raw_exponent+PSEUDO_DOUBLE_EXP_BIAS-0x3FF+2;
 		let old_mantissa=i&0xFFFFFFFFFFFFFi64;
 		if (old_mantissa&0xFFFFFFFFFFFFi64)!=0 {panic!("unsafe to convert the following f64 to PseudoDouble: {}",f)}
 		let mantissa=old_mantissa+0x10000000000000i64; // add in the implied bit
@@ -1140,12 +4081,46 @@ impl PseudoDouble {
 		let mantissa=shift_left_signed(mantissa,PSEUDO_DOUBLE_TOTAL_BITS-54);
 		//mantissa=(mantissa+PSEUDO_DOUBLE_HALF_ULP)&~PSEUDO_DOUBLE_HALF_ULP;
 		if negative {
-			return PseudoDouble(-(mantissa&EXP_MASK_INV)+exponent);
+			PseudoDouble(-(mantissa&EXP_MASK_INV)+exponent)
 		} else {
-			return PseudoDouble((mantissa&EXP_MASK_INV)+exponent);
+			PseudoDouble((mantissa&EXP_MASK_INV)+exponent)
 		}
 	}
 
+	/// Exact conversion from `f32`. Panics on NaN or infinity.
+	pub const fn from_f32(f:f32) -> PseudoDouble {
+		ieee_bits_to_pd(f.to_bits() as u64,23,8)
+	}
+
+	/// Exact conversion from the bits of an IEEE half precision (binary16) number. Panics on NaN or infinity.
+	pub const fn from_f16_bits(bits:u16) -> PseudoDouble {
+		ieee_bits_to_pd(bits as u64,10,5)
+	}
+
+	/// Exact conversion from the bits of a bfloat16 number. Panics on NaN or infinity.
+	pub const fn from_bf16_bits(bits:u16) -> PseudoDouble {
+		ieee_bits_to_pd(bits as u64,7,8)
+	}
+
+	/// Converts to the bits of an IEEE half precision (binary16) number, rounding to nearest even.
+	/// Values too large for the format become infinity, values too small become subnormals or zero.
+	pub const fn to_f16_bits(self) -> u16 {
+		pd_to_ieee_bits(self,10,5) as u16
+	}
+
+	/// Converts to the bits of a bfloat16 number, rounding to nearest even.
+	/// Values too large for the format become infinity, values too small become subnormals or zero.
+	pub const fn to_bf16_bits(self) -> u16 {
+		pd_to_ieee_bits(self,7,8) as u16
+	}
+
+	/// Formats with exactly `digits` digits after the decimal point, correctly rounded using
+	/// only integer arithmetic. The same as `format!("{:.*}",digits,x)`.
+	pub fn to_string_fixed(self, digits:usize) -> String {
+		format!("{:.*}",digits,self)
+	}
+
+	#[allow(clippy::collapsible_if)]
 	pub const fn const_neg(self) -> Self {
 		let expx=self.0&EXP_MASK;
 		let vx=self.0&EXP_MASK_INV;
@@ -1168,9 +4143,10 @@ impl PseudoDouble {
 				return PseudoDouble((vx<<1)+expx-1);
 			}
 		}
-		return PseudoDouble(-vx+expx as i64);
+		PseudoDouble(-vx+expx)
 	}
 
+	#[allow(clippy::collapsible_if)]
 	pub const fn const_add(self, other: Self) -> Self {
 		let expx=(self.0&EXP_MASK) as i32;
 		let expy=(other.0&EXP_MASK) as i32;
@@ -1181,8 +4157,8 @@ impl PseudoDouble {
 		if ydiffx<=-(PSEUDO_DOUBLE_TOTAL_BITS-1) {
 			return self;
 		}
-		let mut vx=((self.0&EXP_MASK_INV)>>1) as i64;
-		let mut vy=((other.0&EXP_MASK_INV)>>1) as i64;
+		let mut vx=(self.0&EXP_MASK_INV)>>1;
+		let mut vy=(other.0&EXP_MASK_INV)>>1;
 		let exp_max;
 		if ydiffx>=0 {
 			exp_max=expy+1;
@@ -1191,10 +4167,10 @@ impl PseudoDouble {
 			exp_max=expx+1;
 			vy>>=-ydiffx;
 		}
-		let vr=((vx+vy+PSEUDO_DOUBLE_HALF_ULP)&!PSEUDO_DOUBLE_HALF_ULP) as i64;
+		let vr=(vx+vy+PSEUDO_DOUBLE_HALF_ULP)&!PSEUDO_DOUBLE_HALF_ULP;
 		if vr==0 {
 			// special case - a mantissa of zero will always make the whole word zero. Makes comparisons much easier
-			return PD_ZERO
+			PD_ZERO
 		} else {
 			let mut leading_bits=(if vr>0 {vr} else {!vr}).leading_zeros() as i32 - 1;
 			if leading_bits>exp_max {
@@ -1211,10 +4187,11 @@ impl PseudoDouble {
 					panic!("Overflow in PseudoDouble add");
 				}
 			}
-			return PseudoDouble(((vr<<leading_bits)&EXP_MASK_INV)+new_exponent as i64);
+			PseudoDouble(((vr<<leading_bits)&EXP_MASK_INV)+new_exponent as i64)
 		}
 	}
 
+	#[allow(clippy::collapsible_if)]
 	pub const fn const_sub(self, other: Self) -> Self {
 		let expx=(self.0&EXP_MASK) as i32;
 		let expy=(other.0&EXP_MASK) as i32;
@@ -1225,8 +4202,8 @@ impl PseudoDouble {
 		if ydiffx<=-(PSEUDO_DOUBLE_TOTAL_BITS-1) {
 			return self;
 		}
-		let mut vx=((self.0&EXP_MASK_INV)>>1) as i64;
-		let mut vy=((other.0&EXP_MASK_INV)>>1) as i64;
+		let mut vx=(self.0&EXP_MASK_INV)>>1;
+		let mut vy=(other.0&EXP_MASK_INV)>>1;
 		let exp_max;
 		if ydiffx>=0 {
 			exp_max=expy+1;
@@ -1235,10 +4212,10 @@ impl PseudoDouble {
 			exp_max=expx+1;
 			vy>>=-ydiffx;
 		}
-		let vr=((vx-vy+PSEUDO_DOUBLE_HALF_ULP)&!PSEUDO_DOUBLE_HALF_ULP) as i64;
+		let vr=(vx-vy+PSEUDO_DOUBLE_HALF_ULP)&!PSEUDO_DOUBLE_HALF_ULP;
 		if vr==0 {
 			// special case - a mantissa of zero will always make the whole word zero. Makes comparisons much easier
-			return PD_ZERO
+			PD_ZERO
 		} else {
 			let mut leading_bits=(if vr>0 {vr} else {!vr}).leading_zeros() as i32 - 1;
 			if leading_bits>exp_max {
@@ -1255,10 +4232,11 @@ impl PseudoDouble {
 					panic!("Overflow in PseudoDouble add");
 				}
 			}
-			return PseudoDouble(((vr<<leading_bits)&EXP_MASK_INV)+new_exponent as i64);
+			PseudoDouble(((vr<<leading_bits)&EXP_MASK_INV)+new_exponent as i64)
 		}
 	}
 
+	#[allow(clippy::collapsible_if)]
 	pub const fn const_mul(self, other: Self) -> Self {
 		let expx=(self.0&EXP_MASK) as i32;
 		let expy=(other.0&EXP_MASK) as i32;
@@ -1280,9 +4258,10 @@ impl PseudoDouble {
 				return PD_ZERO;
 			}
 		}
-		return PseudoDouble(((vr<<leading_bits)&EXP_MASK_INV)+new_exponent as i64);
+		PseudoDouble(((vr<<leading_bits)&EXP_MASK_INV)+new_exponent as i64)
 	}
 
+	#[allow(clippy::collapsible_if)]
 	pub const fn const_div(self, other: Self) -> Self {
 		let expx=(self.0&EXP_MASK) as i32;
 		let expy=(other.0&EXP_MASK) as i32;
@@ -1291,8 +4270,8 @@ impl PseudoDouble {
 		if vy==0 { // leave this one in to avoid division by zero signal
 			panic!("Division by zero");
 		}
-		let vxb=((vx>>2) as i128)<<64;
-		let vyb=vy as i128;
+		let vxb=(vx>>2)<<64;
+		let vyb=vy;
 		let vrb=vxb/vyb;
 		let vr=vrb as i64;
 		if vr==0 {
@@ -1310,7 +4289,106 @@ impl PseudoDouble {
 				return PD_ZERO;
 			}
 		}
-		return PseudoDouble(((vr<<leading_bits)&EXP_MASK_INV)+new_exponent as i64);
+		PseudoDouble(((vr<<leading_bits)&EXP_MASK_INV)+new_exponent as i64)
+	}
+
+	/// Addition returning `None` on overflow. Underflow gives zero.
+	pub const fn checked_add(self, other: Self) -> Option<Self> {
+		self.checked_add_sub(other,false)
+	}
+
+	/// Subtraction returning `None` on overflow. Underflow gives zero.
+	pub const fn checked_sub(self, other: Self) -> Option<Self> {
+		self.checked_add_sub(other,true)
+	}
+
+	const fn checked_add_sub(self, other: Self, subtract: bool) -> Option<Self> {
+		let expx=(self.0&EXP_MASK) as i32;
+		let expy=(other.0&EXP_MASK) as i32;
+		let ydiffx=expy-expx;
+		if ydiffx>=(PSEUDO_DOUBLE_TOTAL_BITS-1) {
+			if !subtract {
+				return Some(other);
+			}
+			if (other.0&EXP_MASK_INV)==i64::MIN && expy==EXP_MASK as i32 {
+				return None;
+			}
+			return Some(other.const_neg());
+		}
+		if ydiffx<=-(PSEUDO_DOUBLE_TOTAL_BITS-1) {
+			return Some(self);
+		}
+		let mut vx=(self.0&EXP_MASK_INV)>>1;
+		let mut vy=(other.0&EXP_MASK_INV)>>1;
+		let exp_max;
+		if ydiffx>=0 {
+			exp_max=expy+1;
+			vx>>=ydiffx;
+		} else {
+			exp_max=expx+1;
+			vy>>=-ydiffx;
+		}
+		let vr=((if subtract {vx-vy} else {vx+vy})+PSEUDO_DOUBLE_HALF_ULP)&!PSEUDO_DOUBLE_HALF_ULP;
+		if vr==0 {
+			return Some(PD_ZERO);
+		}
+		let mut leading_bits=(if vr>0 {vr} else {!vr}).leading_zeros() as i32 - 1;
+		if leading_bits>exp_max {
+			leading_bits=exp_max;
+		}
+		let new_exponent=exp_max-leading_bits;
+		if new_exponent<0 {
+			return Some(PD_ZERO);
+		}
+		if new_exponent>EXP_MASK as i32 {
+			return None;
+		}
+		Some(PseudoDouble(((vr<<leading_bits)&EXP_MASK_INV)+new_exponent as i64))
+	}
+
+	/// Multiplication returning `None` on overflow. Underflow gives zero.
+	pub const fn checked_mul(self, other: Self) -> Option<Self> {
+		let expx=(self.0&EXP_MASK) as i32;
+		let expy=(other.0&EXP_MASK) as i32;
+		let vx=(self.0&EXP_MASK_INV) as i128;
+		let vy=(other.0&EXP_MASK_INV) as i128;
+		let vr=((vx*vy)>>64) as i64;
+		if vr==0 {
+			return Some(PD_ZERO);
+		}
+		let leading_bits=(if vr>0 {vr} else {!vr}).leading_zeros() as i32 - 1;
+		let new_exponent=expx+expy-PSEUDO_DOUBLE_EXP_BIAS as i32-leading_bits;
+		if new_exponent<0 {
+			return Some(PD_ZERO);
+		}
+		if new_exponent>EXP_MASK as i32 {
+			return None;
+		}
+		Some(PseudoDouble(((vr<<leading_bits)&EXP_MASK_INV)+new_exponent as i64))
+	}
+
+	/// Division returning `None` on overflow or division by zero. Underflow gives zero.
+	pub const fn checked_div(self, other: Self) -> Option<Self> {
+		let expx=(self.0&EXP_MASK) as i32;
+		let expy=(other.0&EXP_MASK) as i32;
+		let vx=(self.0&EXP_MASK_INV) as i128;
+		let vy=(other.0&EXP_MASK_INV) as i128;
+		if vy==0 {
+			return None;
+		}
+		let vr=(((vx>>2)<<64)/vy) as i64;
+		if vr==0 {
+			return Some(PD_ZERO);
+		}
+		let leading_bits=(if vr>0 {vr} else {!vr}).leading_zeros() as i32 - 1;
+		let new_exponent=2+expx-expy+PSEUDO_DOUBLE_EXP_BIAS as i32-leading_bits;
+		if new_exponent<0 {
+			return Some(PD_ZERO);
+		}
+		if new_exponent>EXP_MASK as i32 {
+			return None;
+		}
+		Some(PseudoDouble(((vr<<leading_bits)&EXP_MASK_INV)+new_exponent as i64))
 	}
 
 	pub const fn const_less_than_or_equal(&self, other: Self) -> bool {
@@ -1324,10 +4402,10 @@ impl PseudoDouble {
 		// signs are the same, check exponent
 		let expdiff=(other.0&EXP_MASK)-(self.0&EXP_MASK);
 		if expdiff!=0 {
-			return (expdiff>0)^neg;
+			(expdiff>0)^neg
 		} else {
 			// exponents are the same so don't need to mask off, check mantissa
-			return self.0<other.0;
+			self.0<other.0
 		}
     }
 
@@ -1342,10 +4420,10 @@ impl PseudoDouble {
 		// signs are the same, check exponent
 		let expdiff=(other.0&EXP_MASK)-(self.0&EXP_MASK);
 		if expdiff!=0 {
-			return (expdiff>0)^neg;
+			(expdiff>0)^neg
 		} else {
 			// exponents are the same so don't need to mask off, check mantissa
-			return self.0<other.0;
+			self.0<other.0
 		}
     }
 
@@ -1359,9 +4437,10 @@ impl PseudoDouble {
 			return self;
 		}
 		let m=(1<<(PSEUDO_DOUBLE_TOTAL_BITS-e))-1;
-		return PseudoDouble((self.0&!m)+exponent);
+		PseudoDouble((self.0&!m)+exponent)
     }
 
+	#[allow(clippy::collapsible_if)]
 	pub const fn ceil(self) -> PseudoDouble {
 		let exponent=self.0&EXP_MASK;
 		let e=(exponent-PSEUDO_DOUBLE_EXP_BIAS) as i32;
@@ -1389,17 +4468,18 @@ impl PseudoDouble {
 				return PD_ZERO;
 			}
 		}
-		return PseudoDouble((vr<<leading_bits)+new_exponent);
+		PseudoDouble((vr<<leading_bits)+new_exponent)
     }
 
    pub const fn trunc(self) -> PseudoDouble {
 		if (self.0>>(PSEUDO_DOUBLE_TOTAL_BITS-1))!=0 { // neg
-			return self.ceil();
+			self.ceil()
 		} else {
-			return self.floor();
+			self.floor()
 		}
     }
 
+	#[allow(clippy::collapsible_if)]
 	pub const fn round(self) -> PseudoDouble {
 		let exponent=self.0&EXP_MASK;
 		let e=(exponent-PSEUDO_DOUBLE_EXP_BIAS) as i32;
@@ -1428,13 +4508,80 @@ impl PseudoDouble {
 				return PD_ZERO;
 			}
 		}
-		return PseudoDouble((vr<<leading_bits)+new_exponent);
+		PseudoDouble((vr<<leading_bits)+new_exponent)
     }
 
     pub const fn fract(self) -> PseudoDouble {
 		self.const_sub(self.floor())
 	}
 
+	/// The remainder of `self/other` with the quotient truncated towards zero, like C's `fmod` and
+	/// `f64`'s `%`. The result is exact and has the sign of `self`.
+	pub const fn fmod(self, other: Self) -> PseudoDouble {
+		let d=pd_div_rem(self,other);
+		pd_from_remainder(d.r,d.exp2,self.0<0)
+	}
+
+	/// The IEEE remainder of `self/other`, with the quotient rounded to the nearest integer, ties to even.
+	/// The result is exact and in `[-|other|/2, |other|/2]`.
+	pub const fn remainder(self, other: Self) -> PseudoDouble {
+		let d=pd_div_rem(self,other);
+		if d.sy!=0 && (d.r*2>d.sy || (d.r*2==d.sy && d.odd)) {
+			return pd_from_remainder(d.sy-d.r,d.exp2,self.0>=0);
+		}
+		if d.sy==0 {
+			// |self|<|other| so the quotient rounds to zero or one
+			let (_negative_y,sy,ey)=pd_to_sig_exp(other);
+			if self.0!=0 && (d.exp2==ey-1 && d.r>sy || d.exp2==ey) {
+				let (_negative_x,sx,ex)=pd_to_sig_exp(self);
+				// |other|-|self| at the exponent of self
+				return pd_from_remainder((sy<<(ey-ex))-sx,ex,self.0>=0);
+			}
+		}
+		pd_from_remainder(d.r,d.exp2,self.0<0)
+	}
+
+	/// The least nonnegative remainder of `self/other`, the same definition as `f64::rem_euclid`. It is
+	/// exact except when `self` is negative and much smaller than `|other|`, where `|other|-|self|` is
+	/// rounded and can come out as `|other|`, just as for `f64`.
+	pub const fn rem_euclid(self, other: Self) -> PseudoDouble {
+		let d=pd_div_rem(self,other);
+		if self.0<0 && d.r!=0 {
+			if d.sy==0 {
+				// |self|<|other| so the result is |other|-|self|
+				let (_negative_y,sy,ey)=pd_to_sig_exp(other);
+				let (_negative_x,sx,ex)=pd_to_sig_exp(self);
+				if ey-ex<=17 {
+					return pd_from_remainder((sy<<(ey-ex))-sx,ex,false);
+				}
+				if ey-ex<=80 {
+					return u128_to_pd_rounded(((sy as u128)<<(ey-ex))-sx as u128,false).ldexp(ex);
+				}
+				// |self| is far below half an ulp of |other|
+				return pd_from_remainder(sy,ey,false);
+			}
+			return pd_from_remainder(d.sy-d.r,d.exp2,false);
+		}
+		pd_from_remainder(d.r,d.exp2,false)
+	}
+
+	/// The quotient that goes with `rem_euclid`, so that `self = other*div_euclid + rem_euclid`,
+	/// correctly rounded when it is too large to be exact.
+	pub const fn div_euclid(self, other: Self) -> PseudoDouble {
+		let d=pd_div_rem(self,other);
+		if self.0<0 && d.r!=0 {
+			// the remainder is made positive by one more step of the quotient away from zero
+			return pd_from_quotient(&pd_div_rem_plus_one(d),(self.0<0)!=(other.0<0));
+		}
+		pd_from_quotient(&d,(self.0<0)!=(other.0<0))
+	}
+
+	/// The integer and fractional parts, both with the sign of `self`, like C's `modf`. Both are exact.
+	pub const fn modf(self) -> (PseudoDouble, PseudoDouble) {
+		(self.trunc(),self.fmod(PD_ONE))
+	}
+
+	#[allow(clippy::collapsible_if)]
 	pub const fn inv_sqrt(self) -> PseudoDouble {
 		if cfg!(feature="panic_on_pseudodouble_overflow") {
 			if self.0<0 {
@@ -1452,9 +4599,10 @@ impl PseudoDouble {
 				return PseudoDouble(mantissa+3*(PSEUDO_DOUBLE_EXP_BIAS>>1)+3-(exponent>>1));
 			}
 		}
-		return PseudoDouble((inv_sqrt64_fixed(mantissa as u64) as i64&EXP_MASK_INV)+3*(PSEUDO_DOUBLE_EXP_BIAS>>1)+2-(exponent>>1));
+		PseudoDouble((inv_sqrt64_fixed(mantissa as u64) as i64&EXP_MASK_INV)+3*(PSEUDO_DOUBLE_EXP_BIAS>>1)+2-(exponent>>1))
 	}
 
+	#[allow(clippy::collapsible_if)]
 	pub const fn sqrt(self) -> PseudoDouble {
 		if cfg!(feature="panic_on_pseudodouble_overflow") {
 			if self.0<0 {
@@ -1476,13 +4624,64 @@ impl PseudoDouble {
 		}
 		// (1,4) * (1,0.5) = (1,2)
 		let y=(multu64hi(inv_sqrt64_fixed(mantissa as u64>>(64-PSEUDO_DOUBLE_TOTAL_BITS))<<(64-PSEUDO_DOUBLE_TOTAL_BITS),mantissa as u64)<<1) as i64;
-		return PseudoDouble((y&EXP_MASK_INV)+(PSEUDO_DOUBLE_EXP_BIAS>>1)+1+(exponent>>1));
+		PseudoDouble((y&EXP_MASK_INV)+(PSEUDO_DOUBLE_EXP_BIAS>>1)+1+(exponent>>1))
+	}
+
+	/// `sqrt(x^2+y^2)` correctly rounded. The squares are taken exactly in integers after scaling by the
+	/// larger exponent, so it only overflows when the result does and tiny values do not underflow.
+	/// Overflow panics with the `panic_on_pseudodouble_overflow` feature, otherwise `MAX` is returned.
+	pub const fn hypot(self, other:PseudoDouble) -> PseudoDouble {
+		norm2_rounded(&[self,other])
+	}
+
+	/// `sqrt(x^2+y^2+z^2)` correctly rounded, like `hypot`.
+	pub const fn hypot3(self, y:PseudoDouble, z:PseudoDouble) -> PseudoDouble {
+		norm2_rounded(&[self,y,z])
 	}
 
+	/// The Euclidean norm of a slice correctly rounded, like `hypot`. It is 0 for an empty slice.
+	pub const fn norm2(xs:&[PseudoDouble]) -> PseudoDouble {
+		norm2_rounded(xs)
+	}
+
+	/// Cube root, correctly rounded and odd, so negative numbers have the negative root. It is a fixed
+	/// point Newton iteration made exact with integer cubes, so perfect cubes give exact results.
 	pub const fn cbrt(self) -> PseudoDouble {
-		self.powf(PD_ONE.const_div(Self::pdc10(3,0)))
+		if self.0==0 {
+			return PD_ZERO;
+		}
+		let (negative,sig,exp2)=pd_to_sig_exp(self);
+		cbrt_rounded(sig,exp2,negative)
+	}
+
+	/// The `n`-th root, correctly rounded for `0<n<=1600` so exact powers give exact results, and
+	/// beyond that to about half an ulp. Odd roots of negative numbers are negative, even roots of
+	/// negative numbers panic, as does `n=0`. A negative `n` gives `1/x.root_n(-n)`, rounded twice.
+	pub const fn root_n(self, n:i32) -> PseudoDouble {
+		if n==0 {
+			panic!("root_n with n=0");
+		}
+		if n<0 {
+			return PD_ONE.const_div(self.root_n_abs(n.unsigned_abs()));
+		}
+		self.root_n_abs(n as u32)
+	}
+
+	const fn root_n_abs(self, n:u32) -> PseudoDouble {
+		if self.0<0 && n&1==0 {
+			panic!("root_n of negative number with even n");
+		}
+		if self.0==0 || n==1 {
+			return self;
+		}
+		let (negative,sig,exp2)=pd_to_sig_exp(self);
+		if n==3 {
+			return cbrt_rounded(sig,exp2,negative);
+		}
+		root_n_rounded(sig,exp2,n as u64,negative)
 	}
 
+	#[allow(clippy::collapsible_if)]
 	pub const fn ldexp(self, y:i32) -> PseudoDouble {
 		if self.0==0 {
 			return self;
@@ -1496,10 +4695,11 @@ impl PseudoDouble {
 		if (self.0&EXP_MASK)+yy<0 {
 			return PD_ZERO;
 		}
-		return PseudoDouble(self.0+yy);
+		PseudoDouble(self.0+yy)
 	}
 
 	// e^x=(2^log2(e))^x=2^(log2(e)*x)
+	#[allow(clippy::collapsible_if)]
 	pub const fn exp2(self) -> PseudoDouble {
 		if self.0==0 {
 			return PD_ONE;
@@ -1510,7 +4710,7 @@ impl PseudoDouble {
 		let mut fraction;
 		if e<2 {
 			if self.0<0 {
-				new_exponent=-1;
+				new_exponent= -1;
 				if e==1 {
 					fraction=(self.0&EXP_MASK_INV)<<1;
 				} else {
@@ -1553,9 +4753,10 @@ impl PseudoDouble {
 				}
 			}
 		}
-		return PseudoDouble(newe+(((exp2_64_fixed((fraction<<(64-PSEUDO_DOUBLE_TOTAL_BITS)) as u64) as i64)<<(64-PSEUDO_DOUBLE_TOTAL_BITS))&EXP_MASK_INV));
+		PseudoDouble(newe+(((exp2_64_fixed((fraction<<(64-PSEUDO_DOUBLE_TOTAL_BITS)) as u64) as i64)<<(64-PSEUDO_DOUBLE_TOTAL_BITS))&EXP_MASK_INV))
 	}
 
+	#[allow(clippy::collapsible_if)]
 	pub const fn log2(self) -> PseudoDouble {
 		if cfg!(feature="panic_on_pseudodouble_overflow") {
 			if self.0<=0 {
@@ -1578,15 +4779,35 @@ impl PseudoDouble {
 			return PseudoDouble((((logfrac2 as i64)<<(lead_bits-1))&EXP_MASK_INV)+PSEUDO_DOUBLE_EXP_BIAS+2-lead_bits);
 		}
 		let lead_bits=(if e<0 {!e} else {e}).leading_zeros() as i64;
-		return PseudoDouble((((e<<(PSEUDO_DOUBLE_TOTAL_BITS+(lead_bits as i32)-65))+((log_frac as i64)>>(64-(lead_bits as i32))))&EXP_MASK_INV) as i64+PSEUDO_DOUBLE_EXP_BIAS+65-lead_bits);
+		PseudoDouble((((e<<(PSEUDO_DOUBLE_TOTAL_BITS+(lead_bits as i32)-65))+((log_frac as i64)>>(64-(lead_bits as i32))))&EXP_MASK_INV)+PSEUDO_DOUBLE_EXP_BIAS+65-lead_bits)
 	}
 
+	/// `x^y`. An integral `y` below 2^63 goes to `pow_i64` whatever the sign of `x`, so it agrees with
+	/// `powi`. A negative `x` with a `y` that is not an integer is a domain error, which
+	/// panics with `panic_on_pseudodouble_overflow` and otherwise gives zero. `0^y` for `y<0` is a pole.
 	// x^y = e^ln(x)^y = 2^(y*ln2(x))
+	#[allow(clippy::collapsible_if)]
 	pub const fn powf(self, y:Self) -> Self {
-		if cfg!(feature="panic_on_pseudodouble_overflow") {
-			if self.0<=0 {
-				panic!("PseudoDouble pow of non-positive number");
+		if y.0==0 {
+			return PD_ONE;
+		}
+		let (yneg,ysig,yexp2)=pd_to_sig_exp(y);
+		if yexp2>=-46 && yexp2<=16 && (yexp2>=0 || ysig&((1u64<<-yexp2)-1)==0) {
+			let m=if yexp2>=0 {(ysig as i64)<<yexp2} else {(ysig>>-yexp2) as i64};
+			return self.pow_i64(if yneg {-m} else {m});
+		}
+		if self.0==0 {
+			return if yneg {pow_zero_pole()} else {PD_ZERO};
+		}
+		if self.0<0 {
+			if yexp2>0 {
+				// an even integer
+				return self.const_neg().powf(y);
+			}
+			if cfg!(feature="panic_on_pseudodouble_overflow") {
+				panic!("PseudoDouble pow of negative number to a non-integer power");
 			}
+			return PD_ZERO;
 		}
 		let exponent=self.0&EXP_MASK;
 		let e=exponent-PSEUDO_DOUBLE_EXP_BIAS-2;
@@ -1625,7 +4846,7 @@ impl PseudoDouble {
 		let mut fraction;
 		if er<2 {
 			if vr<0 {
-				new_exponent=-1;
+				new_exponent= -1;
 				if er==1 {
 					fraction=vr<<1;
 				} else {
@@ -1642,7 +4863,7 @@ impl PseudoDouble {
 			}
 		} else if er<=PSEUDO_DOUBLE_EXP_BITS { // max=2^(2^PSEUDO_DOUBLE_EXP_BITS)), log2(max)=2^PSEUDO_DOUBLE_EXP_BITS
 			let m=(1i64<<(PSEUDO_DOUBLE_TOTAL_BITS-er))-1;
-			new_exponent=((vr&!m))>>(PSEUDO_DOUBLE_TOTAL_BITS-er);
+			new_exponent=(vr&!m)>>(PSEUDO_DOUBLE_TOTAL_BITS-er);
 			fraction=(vr&m)<<er;
 		} else {
 			if vr<0 {
@@ -1667,89 +4888,335 @@ impl PseudoDouble {
 				}
 			}
 		}
-		return PseudoDouble(newe+(((exp2_64_fixed((fraction<<(64-PSEUDO_DOUBLE_TOTAL_BITS)) as u64) as i64)<<(64-PSEUDO_DOUBLE_TOTAL_BITS))&EXP_MASK_INV));
+		PseudoDouble(newe+(((exp2_64_fixed((fraction<<(64-PSEUDO_DOUBLE_TOTAL_BITS)) as u64) as i64)<<(64-PSEUDO_DOUBLE_TOTAL_BITS))&EXP_MASK_INV))
 	}
 
-	pub fn powi(self, exp: i32) -> PseudoDouble {
-		if exp == 0 {
+	/// `x^n` with the sign of `x` for odd `n`, exact whenever the result fits and otherwise rounded to
+	/// about half an ulp. Only overflows when the result does, and `0^0=1`.
+	pub const fn pow_u64(self, n:u64) -> PseudoDouble {
+		if n==0 {
 			return PD_ONE;
 		}
-		let invert = self.0<0;
-		let (mut expm,mut base) = if invert { (exp,self) } else { (-exp,PD_ONE.const_div(self)) };
+		if self.0==0 {
+			return PD_ZERO;
+		}
+		let (negative,sig,exp2)=pd_to_sig_exp(self);
+		pow_rounded(sig,exp2,n,false,negative && n&1!=0)
+	}
 
-		let mut result = PD_ONE;
-		while expm > 0 {
-			if expm % 2 == 1 {
-				result = result * base;
-			}
-			base = base * base;
-			expm /= 2;
+	/// `x^n` like `pow_u64`, with a negative `n` giving `1/x^-n` without rounding `x^-n` first. `0^n` for
+	/// `n<0` is a pole.
+	pub const fn pow_i64(self, n:i64) -> PseudoDouble {
+		if n>=0 {
+			return self.pow_u64(n as u64);
+		}
+		if self.0==0 {
+			return pow_zero_pole();
 		}
-		result
+		let (negative,sig,exp2)=pd_to_sig_exp(self);
+		pow_rounded(sig,exp2,n.unsigned_abs(),true,negative && n&1!=0)
+	}
+
+	/// `x^n`, the same as `pow_i64`.
+	pub const fn powi(self, n:i32) -> PseudoDouble {
+		self.pow_i64(n as i64)
 	}
 
 	pub const fn exp(self) -> PseudoDouble {
-		return self.const_mul(PD_LOG_2_E).exp2();
+		self.const_mul(PD_LOG_2_E).exp2()
+	}
+
+	/// `e^x-1`, accurate near zero where `exp(x)-1` would lose most of its bits.
+	pub const fn exp_m1(self) -> PseudoDouble {
+		let exponent=self.0&EXP_MASK;
+		if exponent>PSEUDO_DOUBLE_EXP_BIAS {
+			// x>=0.5 or x<-0.5, e^x=m*2^(k-125) from the range reduced kernel and the one is subtracted in fixed point
+			let (_,_,exp2)=pd_to_sig_exp(self);
+			if self.0<0 {
+				if exp2>=-40 {
+					// x<=-64, e^x is far below half an ulp of -1
+					return PD_NEG_ONE;
+				}
+				let (m,k)=exp_fixed(self,true);
+				return u128_scaled_to_pd((1u128<<125)-(m>>-k),-125,false,true);
+			}
+			if exp2>=-31 {
+				// x>=2^15
+				if cfg!(feature="panic_on_pseudodouble_overflow") {
+					panic!("Overflow in PseudoDouble exp");
+				}
+				return PseudoDouble::MAX;
+			}
+			let (m,k)=exp_fixed(self,false);
+			let one=if k<125 {1u128<<(125-k)} else {0};
+			return u128_scaled_to_pd(m-one,k-125,false,false);
+		}
+		let shift=PSEUDO_DOUBLE_EXP_BIAS+1-exponent;
+		let x=(self.0&EXP_MASK_INV)>>(if shift>63 {63} else {shift});
+		mul_fixed_2_62(self,expm1_ratio_64_fixed(x))
+	}
+
+	/// `ln(1+x)`, accurate near zero where `(1+x).ln()` would lose most of its bits.
+	#[allow(clippy::collapsible_if)]
+	pub const fn ln_1p(self) -> PseudoDouble {
+		if cfg!(feature="panic_on_pseudodouble_overflow") {
+			if self.const_less_than_or_equal(PD_NEG_ONE) {
+				panic!("PseudoDouble ln_1p of number less than or equal to -1");
+			}
+		}
+		let exponent=self.0&EXP_MASK;
+		if exponent>PSEUDO_DOUBLE_EXP_BIAS || (self.0<0 && exponent==PSEUDO_DOUBLE_EXP_BIAS) {
+			// x>=0.5 or x<-0.25, 1+x is formed exactly in integers for the fixed point log
+			if self.const_less_than_or_equal(PD_NEG_ONE) {
+				return PD_ONE.const_add(self).ln();
+			}
+			let (negative,sig,exp2)=pd_to_sig_exp(self);
+			let (v,e)=if negative {
+				ln_fixed((1u128<<-exp2)-sig as u128,exp2 as i64)
+			} else if exp2>=60 {
+				// the one is far below the bits of x
+				ln_fixed(sig as u128,exp2 as i64)
+			} else if exp2>=0 {
+				ln_fixed(((sig as u128)<<exp2)+1,0)
+			} else {
+				ln_fixed(sig as u128+(1u128<<-exp2),exp2 as i64)
+			};
+			return u128_scaled_to_pd(v.unsigned_abs(),e,false,v<0);
+		}
+		let shift=PSEUDO_DOUBLE_EXP_BIAS+1-exponent;
+		let x=(self.0&EXP_MASK_INV)>>(if shift>63 {63} else {shift});
+		mul_fixed_2_62(self,ln1p_ratio_64_fixed(x))
 	}
 
 	pub const fn exp10(self) -> PseudoDouble {
-		return self.const_mul(PD_LOG_2_10).exp2();
+		self.const_mul(PD_LOG_2_10).exp2()
 	}
 
 	pub const fn ln(self) -> PseudoDouble {
-		return self.log2().const_mul(PD_INV_LOG_2_E);
+		self.log2().const_mul(PD_INV_LOG_2_E)
 	}
 
 	pub const fn log10(self) -> PseudoDouble {
-		return self.log2().const_mul(PD_INV_LOG_2_10);
+		self.log2().const_mul(PD_INV_LOG_2_10)
 	}
 
-	pub fn string_to_pd(s:&str) -> Option<PseudoDouble> {
-		let mut chars = s.bytes();
-		let neg;
-		let mut ch=chars.next();
-		if ch==None {
-			return None;
+	/// Parses a decimal number, returning the nearest PseudoDouble (ties to even).
+	///
+	/// Accepts surrounding whitespace, an optional sign, digits with an optional decimal point
+	/// (`1.`, `.5`) and an optional exponent (`1e-5`, `2.5E+300`). Also accepts the exact
+	/// hexadecimal forms written by `{:x}` (`0x1.8p+3`) and `{:#x}` (`pd:0x4000000000008003`).
+	pub const fn string_to_pd(s:&str) -> Result<PseudoDouble, ParsePdError> {
+		let bytes=s.as_bytes();
+		let mut i=0;
+		let mut end=bytes.len();
+		while i<end && bytes[i].is_ascii_whitespace() {
+			i+=1;
+		}
+		while end>i && bytes[end-1].is_ascii_whitespace() {
+			end-=1;
+		}
+		if i==end {
+			return Err(ParsePdError::Empty);
+		}
+		if end-i>=3 && bytes[i]==b'p' && bytes[i+1]==b'd' && bytes[i+2]==b':' {
+			return parse_raw_bits(bytes,i+3,end);
+		}
+		let neg=bytes[i]==b'-';
+		if bytes[i]==b'-' || bytes[i]==b'+' {
+			i+=1;
+		}
+		if end-i>=2 && bytes[i]==b'0' && (bytes[i+1]==b'x' || bytes[i+1]==b'X') {
+			return parse_hex_float(bytes,i+2,end,neg);
+		}
+		// first pass checks the syntax and counts the significant digits
+		let mantissa_start=i;
+		let mut digits=0usize; // significant digits up to the last nonzero one
+		let mut pending_zeros=0usize; // trailing zeros
+		let mut exp_adj=0i64;
+		let mut any_digits=false;
+		let mut seen_point=false;
+		let mut sticky=false;
+		while i<end {
+			let c=bytes[i];
+			if c.is_ascii_digit() {
+				any_digits=true;
+				if digits+pending_zeros>=PARSE_MAX_DIGITS {
+					// more digits than can affect the rounding
+					if c!=b'0' {
+						sticky=true;
+					}
+					if !seen_point {
+						exp_adj+=1;
+					}
+				} else {
+					if seen_point {
+						exp_adj-=1;
+					}
+					if c!=b'0' {
+						digits+=pending_zeros+1;
+						pending_zeros=0;
+					} else if digits>0 {
+						pending_zeros+=1;
+					}
+				}
+			} else if c==b'.' && !seen_point {
+				seen_point=true;
+			} else {
+				break;
+			}
+			i+=1;
+		}
+		if !any_digits {
+			return Err(ParsePdError::InvalidDigit(i));
+		}
+		let mantissa_end=i;
+		exp_adj+=pending_zeros as i64;
+		let mut exp=0i64;
+		if i<end && (bytes[i]==b'e' || bytes[i]==b'E') {
+			i+=1;
+			let exp_neg=i<end && bytes[i]==b'-';
+			if i<end && (bytes[i]==b'-' || bytes[i]==b'+') {
+				i+=1;
+			}
+			if i==end || !bytes[i].is_ascii_digit() {
+				return Err(ParsePdError::InvalidDigit(i));
+			}
+			while i<end && bytes[i].is_ascii_digit() {
+				if exp<1_000_000_000 {
+					exp=exp*10+(bytes[i]-b'0') as i64;
+				}
+				i+=1;
+			}
+			if exp_neg {
+				exp= -exp;
+			}
 		}
-		if ch.unwrap()==b'-' {
-			neg=true;
-			ch=chars.next();
-			if ch==None {
-				return None;
+		if i<end {
+			return Err(ParsePdError::InvalidDigit(i));
+		}
+		if digits==0 {
+			return Ok(PD_ZERO);
+		}
+		let e10=exp+exp_adj;
+		// the value is in [10^(magnitude-1),10^magnitude), the PseudoDouble range is about 10^-9864 to 10^9864
+		let magnitude=e10+digits as i64;
+		if magnitude>9866 {
+			return Err(ParsePdError::Overflow);
+		}
+		if magnitude< -9868 {
+			return Err(ParsePdError::Underflow);
+		}
+		// the digits are less than 2^(digits*10/3), and a power of 5 has 7/3 bits per digit
+		if (digits as i64*10+e10.abs()*7)/3+192<(SMALL_LIMBS*64) as i64 {
+			return decimal_digits_to_pd::<SMALL_LIMBS>(bytes,mantissa_start,mantissa_end,digits,e10,sticky,neg);
+		}
+		decimal_digits_to_pd::<BIG_LIMBS>(bytes,mantissa_start,mantissa_end,digits,e10,sticky,neg)
+	}
+
+	/// Parses like `string_to_pd`, but panics on an error so that it can be used to initialize
+	/// `const` and `static` items, where the panic becomes a compile error.
+	pub const fn const_from_str(s:&str) -> PseudoDouble {
+		match PseudoDouble::string_to_pd(s) {
+			Ok(x) => x,
+			Err(ParsePdError::Empty) => panic!("PseudoDouble literal is empty"),
+			Err(ParsePdError::InvalidDigit(_)) => panic!("invalid digit in PseudoDouble literal"),
+			Err(ParsePdError::Overflow) => panic!("PseudoDouble literal too large"),
+			Err(ParsePdError::Underflow) => panic!("PseudoDouble literal too small"),
+			Err(ParsePdError::UnsupportedRadix(_)) => panic!("unsupported radix in PseudoDouble literal"),
+		}
+	}
+
+	// Used by the pd! macro: the stringified literal keeps the double quotes of a string literal, and
+	// a number literal can have _ separators and a type suffix such as f64, which are dropped here
+	#[doc(hidden)]
+	pub const fn const_from_literal(s:&str) -> PseudoDouble {
+		let bytes=s.as_bytes();
+		if bytes.len()>=2 && bytes[0]==b'"' && bytes[bytes.len()-1]==b'"' {
+			let (_quote,rest)=bytes.split_at(1);
+			let (inner,_quote)=rest.split_at(rest.len()-1);
+			match std::str::from_utf8(inner) {
+				Ok(inner) => return PseudoDouble::const_from_str(inner),
+				Err(_) => panic!("PseudoDouble literal is not valid UTF-8"),
 			}
-		} else {
-			neg=false;
 		}
-		let mut acc=0i64;
-		while ch!=None && ch.unwrap()>=b'0' && (ch.unwrap()<=b'9') {
-			acc=acc*10+(ch.unwrap()-b'0') as i64;
-			ch=chars.next();
+		let (number,_suffix)=bytes.split_at(bytes.len()-literal_suffix_len(bytes));
+		let mut buf=[0u8;LITERAL_MAX_LEN];
+		let mut n=0;
+		let mut i=0;
+		while i<number.len() {
+			if number[i]!=b'_' {
+				if n==LITERAL_MAX_LEN {
+					// too long to copy, so parse it as it is, which only fails if it has separators
+					break;
+				}
+				buf[n]=number[i];
+				n+=1;
+			}
+			i+=1;
 		}
-		if ch==None {
-			return Some(PseudoDouble::from(if neg {-acc} else {acc}));
+		let digits=if i<number.len() {number} else {buf.split_at(n).0};
+		match std::str::from_utf8(digits) {
+			Ok(digits) => PseudoDouble::const_from_str(digits),
+			Err(_) => panic!("PseudoDouble literal is not valid UTF-8"),
 		}
-		if ch.unwrap()!=b'.' {
-			return None;
+	}
+
+	/// Parses a hexadecimal floating point number such as `0x1.8p+3` or `-1.8p3` (the `0x` is
+	/// optional), returning the nearest PseudoDouble. Also accepts the raw bits form `pd:0x4000000000008003`.
+	pub const fn hex_string_to_pd(s:&str) -> Result<PseudoDouble, ParsePdError> {
+		let bytes=s.as_bytes();
+		let mut i=0;
+		let mut end=bytes.len();
+		while i<end && bytes[i].is_ascii_whitespace() {
+			i+=1;
 		}
-		ch=chars.next();
-		let mut frac_num=0i64;
-		let mut frac_den=1i64;
-		while ch!=None && ch.unwrap()>=b'0' && (ch.unwrap()<=b'9') {
-			frac_num=frac_num*10+(ch.unwrap()-b'0') as i64;
-			frac_den*=10;
-			ch=chars.next();
+		while end>i && bytes[end-1].is_ascii_whitespace() {
+			end-=1;
 		}
-		if ch!=None {
-			return None;
+		if i==end {
+			return Err(ParsePdError::Empty);
 		}
-		let ret=PseudoDouble::from(acc)+PseudoDouble::from(frac_num)/PseudoDouble::from(frac_den);
-		if neg {
-			return Some(-ret);
-		} else {
-			return Some(ret);
+		if end-i>=3 && bytes[i]==b'p' && bytes[i+1]==b'd' && bytes[i+2]==b':' {
+			return parse_raw_bits(bytes,i+3,end);
+		}
+		let neg=bytes[i]==b'-';
+		if bytes[i]==b'-' || bytes[i]==b'+' {
+			i+=1;
+		}
+		if end-i>=2 && bytes[i]==b'0' && (bytes[i+1]==b'x' || bytes[i+1]==b'X') {
+			i+=2;
+		}
+		parse_hex_float(bytes,i,end,neg)
+	}
+
+	/// The exact value in hexadecimal floating point, like C's `%a`: `0x1.8p+3`. The same as `format!("{:x}",x)`.
+	pub fn to_hex_string(self) -> String {
+		format!("{:x}",self)
+	}
+
+	/// The raw bits, `pd:0x4000000000008003`. The same as `format!("{:#x}",x)`.
+	pub fn to_bits_string(self) -> String {
+		format!("{:#x}",self)
+	}
+
+	/// The decimal value, signed mantissa, unbiased exponent and raw bits, for diagnostics.
+	pub fn describe(self) -> PseudoDoubleParts {
+		PseudoDoubleParts {
+			decimal: format!("{}",self),
+			mantissa: self.mantissa(),
+			exponent: self.unbiased_exponent(),
+			bits: self.0 as u64,
 		}
 	}
 
+	const fn mantissa(self) -> i64 {
+		self.0>>PSEUDO_DOUBLE_EXP_BITS
+	}
+
+	const fn unbiased_exponent(self) -> i32 {
+		((self.0 & EXP_MASK)-PSEUDO_DOUBLE_EXP_BIAS) as i32-(PSEUDO_DOUBLE_TOTAL_BITS-PSEUDO_DOUBLE_EXP_BITS)
+	}
+
 	pub const fn sin_rev(self) -> PseudoDouble {
 		if self.0==0 {
 			return PD_ZERO;
@@ -1769,21 +5236,21 @@ impl PseudoDouble {
 		}
 		let negative=fraction<0;
 		if negative && (fraction<<1)!=0 {
-			fraction=-fraction;
+			fraction= -fraction;
 		}
 		let mut ufraction=fraction as u64;
 		if (ufraction>>62)!=0 {
 			ufraction=0x8000000000000000u64-ufraction;
 		}
-		let mut d=sin_rev_64_fixed(ufraction as u64) as i64;
+		let mut d=sin_rev_64_fixed(ufraction) as i64;
 		if d==0 {
 			return PD_ZERO;
 		}
 		if negative {
-			d=-d;
+			d= -d;
 		}
 		let lead_bits=(if negative {!d} else {d}).leading_zeros() as i32;
-		return PseudoDouble((shift_left_signed(d,PSEUDO_DOUBLE_TOTAL_BITS+lead_bits-65)&EXP_MASK_INV)+PSEUDO_DOUBLE_EXP_BIAS+3-lead_bits as i64);
+		PseudoDouble((shift_left_signed(d,PSEUDO_DOUBLE_TOTAL_BITS+lead_bits-65)&EXP_MASK_INV)+PSEUDO_DOUBLE_EXP_BIAS+3-lead_bits as i64)
 	}
 
 	pub const fn cos_rev(self) -> PseudoDouble {
@@ -1810,7 +5277,7 @@ impl PseudoDouble {
 		}
 		let negative=fraction<0;
 		if negative {
-			fraction=-fraction;
+			fraction= -fraction;
 		}
 		let mut ufraction=fraction as u64;
 		if (ufraction>>62)!=0 {
@@ -1821,10 +5288,18 @@ impl PseudoDouble {
 			return PD_ZERO;
 		}
 		if negative {
-			d=-d;
+			d= -d;
 		}
 		let lead_bits=(if negative {!d} else {d}).leading_zeros() as i32;
-		return PseudoDouble((shift_left_signed(d,PSEUDO_DOUBLE_TOTAL_BITS+lead_bits-65)&EXP_MASK_INV)+PSEUDO_DOUBLE_EXP_BIAS+3-lead_bits as i64);
+		PseudoDouble((shift_left_signed(d,PSEUDO_DOUBLE_TOTAL_BITS+lead_bits-65)&EXP_MASK_INV)+PSEUDO_DOUBLE_EXP_BIAS+3-lead_bits as i64)
+	}
+
+	/// Computes the tangent of `self` in revolutions (full circle = 1).
+	///
+	/// Odd multiples of 1/4 are poles: with the `panic_on_pseudodouble_overflow` feature this panics,
+	/// otherwise `MAX` is returned for a positive pole and `MIN` for a negative one.
+	pub const fn tan_rev(self) -> PseudoDouble {
+		tan_or_pole(pd_tan_scaled(self,false),self)
 	}
 
     /// Computes the four quadrant arctangent of `self` (`y`) and `other` (`x`) in revolutions (full circle = 1).
@@ -1861,164 +5336,573 @@ impl PseudoDouble {
     /// assert!(abs_difference_2 < 1e-10);
     /// ```
 	pub const fn atan2_rev(self, other: PseudoDouble) -> PseudoDouble {
-		let mut negative=false;
-		let add_const;
-		let mut y=self;
-		let mut x=other;
-		if y.0==0 {
-			if x.0>=0 {
+		pd_atan2_scaled(self,other,false)
+	}
+
+	/// Computes the tangent of `self` in radians, with the argument reduced in 128 bit fixed point.
+	///
+	/// No `PseudoDouble` is an odd multiple of pi/2, so the poles are only hit past 2^128, where the
+	/// reduction runs out of bits of 1/(2*pi). They behave as in `tan_rev`.
+	pub const fn tan(self) -> PseudoDouble {
+		tan_or_pole(pd_tan_scaled(self,true),self)
+	}
+
+	pub const fn sin_cos(self) -> (PseudoDouble, PseudoDouble) {
+		(self.sin(),self.cos())
+	}
+
+	/// Computes the arcsine of `self` in revolutions (full circle = 1), in the range [-1/4,1/4].
+	pub const fn asin_rev(self) -> PseudoDouble {
+		pd_asin_scaled(self,false)
+	}
+
+	/// Computes the arccosine of `self` in revolutions (full circle = 1), in the range [0,1/2].
+	pub const fn acos_rev(self) -> PseudoDouble {
+		pd_acos_scaled(self,false)
+	}
+
+	pub const fn asin(self) -> PseudoDouble {
+		pd_asin_scaled(self,true)
+	}
+
+	pub const fn acos(self) -> PseudoDouble {
+		pd_acos_scaled(self,true)
+	}
+
+	/// Computes the arctangent of `self` in revolutions (full circle = 1), in the range [-1/4,1/4].
+	/// This is the same as `self.atan2_rev(PD_ONE)`.
+	pub const fn atan_rev(self) -> PseudoDouble {
+		pd_atan2_scaled(self,PD_ONE,false)
+	}
+
+	pub const fn atan(self) -> PseudoDouble {
+		pd_atan2_scaled(self,PD_ONE,true)
+	}
+
+	/// Hyperbolic sine, overflowing only when the result does.
+	pub const fn sinh(self) -> PseudoDouble {
+		if (self.0&EXP_MASK)<=PSEUDO_DOUBLE_EXP_BIAS {
+			// |x|<=1/2, no cancellation as x*ratio(x^2)
+			if self.0==0 {
 				return PD_ZERO;
-			} else {
-				return Self::pdc2(1,-1); // 1/2
-			}
-		} else if y.0>0 {
-			if x.0==0 {
-				return Self::pdc2(1,-2); // 1/4
-			} else if x.0>0 {
-				if y.const_less_than_or_equal(x) {
-					// q1
-					add_const=0;
-				} else {
-					// q2
-					{ let t=y; y=x; x=t;}
-					add_const=0x4000000000000000u64;
-					negative=true;
-				}
-			} else { // x<0
-				x=x.const_neg();
-				if y.const_less_than_or_equal(x) {
-					// q4
-					add_const=0x8000000000000000u64;
-					negative=true;
-				} else {
-					// q3
-					{ let t=y; y=x; x=t;}
-					add_const=0x4000000000000000u64;
-				}
 			}
-		} else { // y<0
-			y=y.const_neg();
-			if x.0==0 {
-				return Self::pdc2(3,-2); // 3/2
-			} else if x.0>0 {
-				if y.const_less_than_or_equal(x) {
-					// q8
-					add_const=0; // boolean
-					negative=true;
-				} else {
-					// q7
-					{ let t=y; y=x; x=t;}
-					add_const=0xC000000000000000u64;
-				}
-			} else { // x<0
-				x=x.const_neg();
-				if y.const_less_than_or_equal(x) {
-					// q5
-					add_const=0x8000000000000000u64;
-				} else {
-					// q6
-					{ let t=y; y=x; x=t;}
-					add_const=0xC000000000000000u64;
-					negative=true; // boolean
-				}
+			return mul_fixed_2_62(self,sinh_ratio_64_fixed(pd_abs_to_fixed_2_62(self)));
+		}
+		let negative=self.0<0;
+		if (self.0&EXP_MASK)>PSEUDO_DOUBLE_EXP_BIAS+16 {
+			// |x|>2^15
+			return u128_scaled_to_pd(1,i64::MAX>>1,false,negative);
+		}
+		let (_,s,k)=cosh_sinh_fixed(if negative {self.const_neg()} else {self});
+		u128_scaled_to_pd(s,k-126,false,negative)
+	}
+
+	/// Hyperbolic cosine, overflowing only when the result does.
+	pub const fn cosh(self) -> PseudoDouble {
+		if (self.0&EXP_MASK)>PSEUDO_DOUBLE_EXP_BIAS+16 {
+			// |x|>2^15
+			return u128_scaled_to_pd(1,i64::MAX>>1,false,false);
+		}
+		let (c,_,k)=cosh_sinh_fixed(if self.0<0 {self.const_neg()} else {self});
+		u128_scaled_to_pd(c,k-126,false,false)
+	}
+
+	/// Hyperbolic tangent, `±1` once `e^-2|x|` is below half an ulp.
+	pub const fn tanh(self) -> PseudoDouble {
+		let negative=self.0<0;
+		if (self.0&EXP_MASK)>PSEUDO_DOUBLE_EXP_BIAS+16 {
+			// |x|>2^15
+			return if negative {PD_NEG_ONE} else {PD_ONE};
+		}
+		let a=if negative {self.const_neg()} else {self};
+		let (c,s,k)=cosh_sinh_fixed(a);
+		if (self.0&EXP_MASK)<=PSEUDO_DOUBLE_EXP_BIAS {
+			// |x|<=1/2, take sinh from x*ratio(x^2) to keep the precision of x
+			if self.0==0 {
+				return PD_ZERO;
 			}
+			let n=(self.0&EXP_MASK_INV).unsigned_abs() as u128*sinh_ratio_64_fixed(pd_abs_to_fixed_2_62(self)) as u128;
+			return ratio_u128_to_pd(n,c,(self.0&EXP_MASK)-PSEUDO_DOUBLE_EXP_BIAS-k,negative);
 		}
-		let expx=(x.0&EXP_MASK) as i32;
-		let expy=(y.0&EXP_MASK) as i32;
-		let vx=x.0&EXP_MASK_INV;
-		let vy=y.0&EXP_MASK_INV;
-		let ratio;
-		if x.0==y.0 {
-			ratio=0x4000000000000000i64;
-		} else if x.0==y.const_neg().0 {
-			ratio=-0x4000000000000000i64;
+		ratio_u128_to_pd(s,c,0,negative)
+	}
+
+	/// Inverse hyperbolic sine, odd so there is no cancellation for negative `x`.
+	pub const fn asinh(self) -> PseudoDouble {
+		if (self.0&EXP_MASK)<=PSEUDO_DOUBLE_EXP_BIAS {
+			// |x|<=1/2
+			if self.0==0 {
+				return PD_ZERO;
+			}
+			return mul_fixed_2_62(self,asinh_ratio_64_fixed(pd_abs_to_fixed_2_62(self)));
+		}
+		let (negative,sig,exp2)=pd_to_sig_exp(self);
+		let (v,e)=if exp2>=-22 {
+			// |x|>=2^24, sqrt(x^2+1)=|x| to full precision, asinh(x)=ln(2|x|)
+			ln_fixed(sig as u128,exp2 as i64+1)
 		} else {
-			let mut vr=divs64hi(vy>>2,vx);
-			if vr==0 {
-				ratio=0;
-			} else {
-				let leading_bits=vr.leading_zeros() as i32-1;
-				vr<<=leading_bits;
-				let new_exponent=expy-expx-leading_bits;
-				if new_exponent< -63 {
-					ratio=0;
-				} else {
-					ratio=vr>>(-new_exponent);
+			ln_fixed_sum_sqrt(sig,exp2,false)
+		};
+		u128_scaled_to_pd(v.unsigned_abs(),e,false,negative)
+	}
+
+	/// Inverse hyperbolic cosine, panics for `x<1`.
+	pub const fn acosh(self) -> PseudoDouble {
+		if self.const_less_than(PD_ONE) {
+			panic!("acosh of number less than 1");
+		}
+		let (_,sig,exp2)=pd_to_sig_exp(self);
+		let (v,e)=if exp2>=-22 {
+			// x>=2^24, sqrt(x^2-1)=x to full precision, acosh(x)=ln(2x)
+			ln_fixed(sig as u128,exp2 as i64+1)
+		} else {
+			ln_fixed_sum_sqrt(sig,exp2,true)
+		};
+		u128_scaled_to_pd(v.unsigned_abs(),e,false,false)
+	}
+
+	/// Inverse hyperbolic tangent, panics unless `-1<x<1`.
+	pub const fn atanh(self) -> PseudoDouble {
+		if self.const_less_than_or_equal(PD_NEG_ONE) || PD_ONE.const_less_than_or_equal(self) {
+			panic!("atanh of number not between -1 and 1");
+		}
+		if (self.0&EXP_MASK)<=PSEUDO_DOUBLE_EXP_BIAS {
+			// |x|<=1/2
+			if self.0==0 {
+				return PD_ZERO;
+			}
+			return mul_fixed_2_62(self,atanh_ratio_64_fixed(pd_abs_to_fixed_2_62(self)));
+		}
+		// atanh(x)=(ln(1+|x|)-ln(1-|x|))/2, both exact multiples of 2^-47 for 1/2<|x|<1
+		let (negative,sig,exp2)=pd_to_sig_exp(self);
+		let a=sig>>(-47-exp2);
+		let (v1,_)=ln_fixed(((1u64<<47)+a) as u128,-47);
+		let (v2,_)=ln_fixed(((1u64<<47)-a) as u128,-47);
+		u128_scaled_to_pd((v1-v2) as u128,-111,false,negative)
+	}
+
+	/// Error function, keeping the relative precision of `x` near 0.
+	pub const fn erf(self) -> PseudoDouble {
+		erf_erfc(self,self.0<0,false,false)
+	}
+
+	/// Complementary error function `1-erf(x)`, keeping its relative precision until it underflows.
+	pub const fn erfc(self) -> PseudoDouble {
+		erf_erfc(self,self.0<0,true,false)
+	}
+
+	/// Inverse error function, panics unless `-1<x<1`.
+	pub const fn erf_inv(self) -> PseudoDouble {
+		if self.const_less_than_or_equal(PD_NEG_ONE) || PD_ONE.const_less_than_or_equal(self) {
+			panic!("erf_inv of number not between -1 and 1");
+		}
+		if self.0==0 {
+			return PD_ZERO;
+		}
+		let negative=self.0<0;
+		let a=if negative {self.const_neg()} else {self};
+		let r=if a.const_less_than_or_equal(PD_ONE.ldexp(-1)) {
+			erf_inv_central(a,false)
+		} else {
+			erf_inv_tail(abs_one_minus_exact(a),false)
+		};
+		if negative {r.const_neg()} else {r}
+	}
+
+	/// Standard normal cumulative distribution function `(1+erf(x/sqrt(2)))/2`, precise in both tails.
+	pub const fn normal_cdf(self) -> PseudoDouble {
+		erf_erfc(self,self.0>0,true,true)
+	}
+
+	/// Inverse of `normal_cdf`, panics unless `0<p<1`.
+	pub const fn normal_quantile(self) -> PseudoDouble {
+		if self.0<=0 || PD_ONE.const_less_than_or_equal(self) {
+			panic!("normal_quantile of number not between 0 and 1");
+		}
+		// the argument of the erf or erfc inverse is exact in each range
+		if self.const_less_than(PD_ONE.ldexp(-2)) {
+			return erf_inv_tail(self.ldexp(1),true).const_neg();
+		}
+		let r=abs_one_minus_exact(self.ldexp(1));
+		if r.0==0 {
+			return PD_ZERO;
+		}
+		if self.const_less_than(PD_ONE.ldexp(-1)) {
+			return erf_inv_central(r,true).const_neg();
+		}
+		if self.const_less_than_or_equal(PD_ONE.ldexp(-1).const_add(PD_ONE.ldexp(-2))) {
+			return erf_inv_central(r,true);
+		}
+		erf_inv_tail(abs_one_minus_exact(self).ldexp(1),true)
+	}
+
+	/// Gamma function. Positive integers give the correctly rounded factorial, elsewhere the result is
+	/// `+-e^lnΓ(x)` with the logarithm in fixed point. Overflows like `exp` above about 3210 and
+	/// underflows to 0 below about -3210.
+	///
+	/// Zero and the negative integers are poles: with the `panic_on_pseudodouble_overflow` feature this
+	/// panics, otherwise `MAX` is returned.
+	pub const fn gamma(self) -> PseudoDouble {
+		let (negative,sig,exp2)=pd_to_sig_exp(self);
+		if !negative {
+			if self.0==0 {
+				if cfg!(feature="panic_on_pseudodouble_overflow") {
+					panic!("PseudoDouble gamma at a pole");
 				}
+				return PseudoDouble::MAX;
+			}
+			if exp2>=-46 && exp2<=-35 && sig&((1u64<<-exp2)-1)==0 {
+				// an integer below 2^12
+				return PseudoDouble::factorial((sig>>-exp2)-1);
+			}
+			let (v,e)=lgamma_fixed(sig as u128,exp2 as i64);
+			return exp_fixed_to_pd(v,e,false);
+		}
+		if exp2<=-49 {
+			// -1/4<x<0
+			let (s,e)=normalize_63(sig as u128,exp2 as i64);
+			let (v,e)=lgamma_small(s,e,true);
+			return exp_fixed_to_pd(v,e,true);
+		}
+		let (f,_,even)=dist_to_integer(sig,exp2);
+		if f==0 {
+			if cfg!(feature="panic_on_pseudodouble_overflow") {
+				panic!("PseudoDouble gamma at a pole");
+			}
+			return PseudoDouble::MAX;
+		}
+		let (v,e)=lgamma_reflect(sig,exp2,f);
+		exp_fixed_to_pd(v,e,even)
+	}
+
+	/// Natural logarithm of `|Γ(x)|`. It uses the whole exponent range, so it stays finite far beyond
+	/// where `gamma` overflows. Near the zeros at 1 and 2 the relative precision is kept, near the other
+	/// zeros (all below -2) the error is about 2^-60 absolute.
+	///
+	/// Zero and the negative integers are poles, handled like `gamma`.
+	pub const fn lgamma(self) -> PseudoDouble {
+		let (negative,sig,exp2)=pd_to_sig_exp(self);
+		let (f,_,_)=if negative && exp2>=-48 {dist_to_integer(sig,exp2)} else {(1,false,false)};
+		if self.0==0 || f==0 {
+			if cfg!(feature="panic_on_pseudodouble_overflow") {
+				panic!("PseudoDouble lgamma at a pole");
+			}
+			return PseudoDouble::MAX;
+		}
+		let (v,e)=if !negative {
+			lgamma_fixed(sig as u128,exp2 as i64)
+		} else if exp2<=-49 {
+			let (s,e)=normalize_63(sig as u128,exp2 as i64);
+			lgamma_small(s,e,true)
+		} else {
+			lgamma_reflect(sig,exp2,f)
+		};
+		u128_scaled_to_pd(v.unsigned_abs(),e,false,v<0)
+	}
+
+	/// Digamma function `ψ(x)=Γ'(x)/Γ(x)`. Negative arguments use `ψ(x)=ψ(1-x)-pi*cot(pi*x)`. Near its
+	/// zeros the error is about 2^-60 absolute.
+	///
+	/// Zero and the negative integers are poles, handled like `gamma`.
+	pub const fn digamma(self) -> PseudoDouble {
+		let (negative,sig,exp2)=pd_to_sig_exp(self);
+		let (f,up,_)=if negative && exp2>=-48 {dist_to_integer(sig,exp2)} else {(1,false,false)};
+		if self.0==0 || f==0 {
+			if cfg!(feature="panic_on_pseudodouble_overflow") {
+				panic!("PseudoDouble digamma at a pole");
 			}
+			return PseudoDouble::MAX;
+		}
+		if exp2<=-49 {
+			// |x|<1/4, ψ(x)=ψ(1+x)-1/x=-(1-x*ψ(1+x))/x
+			let (s,e)=normalize_63(sig as u128,exp2 as i64);
+			let (p,_)=if e<=-127 {
+				digamma_fixed(1,0)
+			} else if negative {
+				digamma_fixed((1u128<<-e)-s as u128,e)
+			} else {
+				digamma_fixed((1u128<<-e)+s as u128,e)
+			};
+			let a=shift_i128(s as i128*(p>>47),e+47); // |x|*ψ(1+x)*2^110
+			let n=(1i128<<110)-if negative {-a} else {a};
+			let (q,qe)=ratio_u128_fixed(n as u128,s as u128);
+			return u128_scaled_to_pd(q,qe-110-e,false,!negative);
+		}
+		if !negative {
+			let (v,e)=digamma_fixed(sig as u128,exp2 as i64);
+			return u128_scaled_to_pd(v.unsigned_abs(),e,false,v<0);
+		}
+		// pi*cot(pi*f)=pi*cos(pi*f)/sin(pi*f), with sin(pi*f)=f*g*2^(exp2-61)
+		let fx=f<<(exp2+63);
+		let g=sin_rev_ratio_64_fixed(fx);
+		let c=sin_rev_64_fixed((1u64<<62)-fx);
+		let (q,qe)=ratio_u128_fixed(PI_FIXED_62 as u128*c as u128,f as u128*g as u128);
+		let (p,pe)=digamma_fixed((sig+(1u64<<-exp2)) as u128,exp2 as i64);
+		let (v,e)=if q==0 {(p,pe)} else {add_fixed(p,pe,if up {-(q as i128)} else {q as i128},qe-63-exp2 as i64)};
+		u128_scaled_to_pd(v.unsigned_abs(),e,false,v<0)
+	}
+
+	/// Beta function `Γ(a)Γ(b)/Γ(a+b)`, panics unless `a>0` and `b>0`. It is the exponential of fixed
+	/// point log-gammas, so the relative error grows with `a+b`, to about 2^-40 near `a+b=2^20`.
+	pub const fn beta(self, b:PseudoDouble) -> PseudoDouble {
+		if self.0<=0 || b.0<=0 {
+			panic!("beta of non-positive number");
+		}
+		let (_,sa,ea)=pd_to_sig_exp(self);
+		let (_,sb,eb)=pd_to_sig_exp(b);
+		// a+b, exact unless the exponents are more than 80 apart
+		let (hi,he,lo,le)=if ea>=eb {(sa,ea as i64,sb,eb as i64)} else {(sb,eb as i64,sa,ea as i64)};
+		let d=if he-le>80 {80} else {he-le};
+		let sum=((hi as u128)<<d)+shift_u128(lo as u128,le-he+d);
+		if he>=-42 {
+			// the larger argument is at least 16
+			let (v1,e1)=lgamma_fixed(lo as u128,le);
+			let (v2,e2)=lgamma_diff(hi,he,lo,le,sum,he-d);
+			let (v,e)=add_fixed(v1,e1,-v2,e2);
+			return exp_fixed_to_pd(v,e,false);
+		}
+		let (v1,e1)=lgamma_fixed(sa as u128,ea as i64);
+		let (v2,e2)=lgamma_fixed(sb as u128,eb as i64);
+		let (v3,e3)=lgamma_fixed(sum,he-d);
+		let (v,e)=add_fixed(v1,e1,v2,e2);
+		let (v,e)=add_fixed(v,e,-v3,e3);
+		exp_fixed_to_pd(v,e,false)
+	}
+
+	/// `n!` correctly rounded, computed exactly in integers. Overflows like `exp` above 3209.
+	pub const fn factorial(n:u64) -> PseudoDouble {
+		let mut c=BigUint::<BIG_LIMBS>::from_u64(1);
+		let mut i=2;
+		while i<=n && c.bit_len()<=(PseudoDouble::MAX_EXP+2) as usize {
+			c.mul_small(i);
+			i+=1;
+		}
+		big_integer_to_pd(&mut c)
+	}
+
+	/// Binomial coefficient `n` choose `k` correctly rounded, computed exactly in integers. It is 0 for
+	/// `k>n` and overflows like `exp` above `MAX`.
+	pub const fn binomial(n:u64, k:u64) -> PseudoDouble {
+		if k>n {
+			return PD_ZERO;
+		}
+		let k=if k>n-k {n-k} else {k};
+		let mut c=BigUint::<BIG_LIMBS>::from_u64(1);
+		let mut i=0;
+		// c=n!/(i!(n-i)!) stays exact, and grows with i up to n/2
+		while i<k && c.bit_len()<=(PseudoDouble::MAX_EXP+2) as usize {
+			c.mul_small(n-i);
+			c.div_small(i+1);
+			i+=1;
+		}
+		big_integer_to_pd(&mut c)
+	}
+
+	/// Bessel function of the first kind of order 0.
+	///
+	/// Below 25 it is the power series in 128 bit fixed point, with an absolute error of about 2^-78, so
+	/// the relative precision is kept except within about 2^-30 of a zero. From 25 up it is the Hankel
+	/// asymptotic form, with the phase `x/(2*pi)-1/8` reduced in revolutions and fed to the
+	/// `sin_rev`/`cos_rev` kernel, so the error is about 2^-60 of the amplitude `sqrt(2/(pi*x))` and
+	/// grows relative to the result near its zeros. The phase is exact up to 2^190; beyond that the
+	/// stored bits of `1/(2*pi)` run out and the result is only bounded by the amplitude, below 2^-95.
+	pub const fn j0(self) -> PseudoDouble {
+		if self.0==0 {
+			return PD_ONE;
 		}
-		let mut d=(atan_rev_64_fixed(ratio as u64)>>1) as i128;
-		d=add_const as i128+if negative {-d} else {d};
-		let d64=d as i64;
-		if d64==0 {
+		let (_,sig,exp2)=pd_to_sig_exp(self);
+		let (v,e)=bessel_fixed(sig,exp2,0,false);
+		u128_scaled_to_pd(v.unsigned_abs(),e,false,v<0)
+	}
+
+	/// Bessel function of the first kind of order 1, odd in `x`. It is computed like `j0`, and keeps
+	/// its relative precision as `x` goes to 0.
+	pub const fn j1(self) -> PseudoDouble {
+		if self.0==0 {
 			return PD_ZERO;
 		}
-		let negatived=d64<0;
-		let lead_bits=(if negatived {!d64} else {d64}).leading_zeros() as i32;
-		return PseudoDouble(((shift_left_signed(d64,PSEUDO_DOUBLE_TOTAL_BITS+lead_bits-65))&EXP_MASK_INV)+PSEUDO_DOUBLE_EXP_BIAS+1-lead_bits as i64);
+		let (negative,sig,exp2)=pd_to_sig_exp(self);
+		let (v,e)=bessel_fixed(sig,exp2,1,false);
+		u128_scaled_to_pd(v.unsigned_abs(),e,false,(v<0)!=negative)
 	}
 
-	pub const fn tan(self) -> PseudoDouble {
-		self.sin().const_div(self.cos())
+	/// Bessel function of the first kind of integer order `n`, with `J_-n(x)=(-1)^n*J_n(x)`.
+	///
+	/// While `x^2/4<=|n|+1` it is `(x/2)^n/n!` times a series without cancellation, so it keeps its
+	/// relative precision and underflows to 0 only when the result does. For `x>=|n|` it is the
+	/// forward recurrence from `j0` and `j1`, in between Miller's backward recurrence normalized by
+	/// `j0` or `j1`. The recurrences run in 125 bit floating fixed point, so the error is that of
+	/// `j0` and `j1` near their zeros, and the time grows linearly with `n`.
+	pub const fn jn(self, n:i32) -> PseudoDouble {
+		let m=n.unsigned_abs() as u64;
+		if m==0 {
+			return self.j0();
+		}
+		if self.0==0 {
+			return PD_ZERO;
+		}
+		let (negative,sig,exp2)=pd_to_sig_exp(self);
+		let flip=m&1==1 && (n<0)!=negative;
+		let (v,e)=if m==1 {bessel_fixed(sig,exp2,1,false)} else {bessel_jn_fixed(m,sig,exp2)};
+		u128_scaled_to_pd(v.unsigned_abs(),e,false,(v<0)!=flip)
 	}
 
-	pub const fn sin_cos(self) -> (PseudoDouble, PseudoDouble) {
-		(self.sin(),self.cos())
+	/// Bessel function of the second kind of order 0, panics for negative `x`.
+	///
+	/// Below 25 it is `2/pi*(ln(x/2)+γ)*j0(x)` plus a power series, in 128 bit fixed point. The
+	/// logarithm is good to about 2^-64 absolute, which is the error near the zeros. Above that it is
+	/// the Hankel form like `j0`, with the same behaviour near zeros and for large `x`.
+	///
+	/// Zero is a pole: with the `panic_on_pseudodouble_overflow` feature this panics, otherwise `MIN` is
+	/// returned.
+	pub const fn y0(self) -> PseudoDouble {
+		if self.0<0 {
+			panic!("y0 of negative number");
+		}
+		if self.0==0 {
+			if cfg!(feature="panic_on_pseudodouble_overflow") {
+				panic!("PseudoDouble y0 at a pole");
+			}
+			return PseudoDouble::MIN;
+		}
+		let (_,sig,exp2)=pd_to_sig_exp(self);
+		let (v,e)=bessel_fixed(sig,exp2,0,true);
+		u128_scaled_to_pd(v.unsigned_abs(),e,false,v<0)
 	}
 
-	pub const fn asin(self) -> PseudoDouble {
+	/// Bessel function of the second kind of order 1, panics for negative `x`. The `-2/(pi*x)` term is
+	/// factored out of the series, so near 0 it keeps its relative precision until it overflows like
+	/// `exp`. Zero is a pole, handled like `y0`.
+	pub const fn y1(self) -> PseudoDouble {
+		if self.0<0 {
+			panic!("y1 of negative number");
+		}
+		if self.0==0 {
+			if cfg!(feature="panic_on_pseudodouble_overflow") {
+				panic!("PseudoDouble y1 at a pole");
+			}
+			return PseudoDouble::MIN;
+		}
+		let (_,sig,exp2)=pd_to_sig_exp(self);
+		let (v,e)=bessel_fixed(sig,exp2,1,true);
+		u128_scaled_to_pd(v.unsigned_abs(),e,false,v<0)
+	}
+
+	/// Bessel function of the second kind of integer order `n`, with `Y_-n(x)=(-1)^n*Y_n(x)`, panics
+	/// for negative `x`. It is the forward recurrence from `y0` and `y1` in 125 bit floating fixed
+	/// point, which is stable for every `n`, and it overflows like `exp` once `|Y_n(x)|` passes `MAX`.
+	/// Zero is a pole, handled like `y0`.
+	pub const fn yn(self, n:i32) -> PseudoDouble {
+		let m=n.unsigned_abs() as u64;
+		if m==0 {
+			return self.y0();
+		}
+		if self.0<0 {
+			panic!("yn of negative number");
+		}
+		if self.0==0 {
+			if cfg!(feature="panic_on_pseudodouble_overflow") {
+				panic!("PseudoDouble yn at a pole");
+			}
+			return if m&1==1 && n<0 {PseudoDouble::MAX} else {PseudoDouble::MIN};
+		}
+		let (_,sig,exp2)=pd_to_sig_exp(self);
+		let (r,re)=(recip_fixed_174((sig as u128)<<16) as i128,-158-exp2 as i64); // 1/x
+		let (mut a,mut ae)=bessel_fixed(sig,exp2,0,true);
+		let (mut b,mut be)=bessel_fixed(sig,exp2,1,true);
+		let mut k=1;
+		// once |Y_k| is past MAX it only grows
+		while k<m && be<=PseudoDouble::MAX_EXP as i64 {
+			let (c,ce)=bessel_step(k,r,re,b,be,a,ae);
+			(a,ae,b,be)=(b,be,c,ce);
+			k+=1;
+		}
+		let flip=m&1==1 && n<0;
+		u128_scaled_to_pd(b.unsigned_abs(),be,false,(b<0)!=flip)
+	}
+
+	/// `asin`, or a `PdDomainError` unless `-1<=x<=1`.
+	pub const fn try_asin(self) -> Result<PseudoDouble, PdDomainError> {
 		if self.const_less_than(PD_NEG_ONE) || PD_ONE.const_less_than(self) {
-			panic!("acosh of number <1");
+			return Err(PdDomainError{function:"asin"});
 		}
-		self.atan2(PD_ONE.const_sub(self.const_mul(self)).sqrt())
+		Ok(self.asin())
 	}
 
-	pub const fn acos(self) -> PseudoDouble {
+	/// `acos`, or a `PdDomainError` unless `-1<=x<=1`.
+	pub const fn try_acos(self) -> Result<PseudoDouble, PdDomainError> {
 		if self.const_less_than(PD_NEG_ONE) || PD_ONE.const_less_than(self) {
-			panic!("acosh of number <1");
+			return Err(PdDomainError{function:"acos"});
 		}
-		PD_ONE.const_sub(self.const_mul(self)).sqrt().atan2(self)
+		Ok(self.acos())
 	}
 
-	pub const fn atan_rev(self) -> PseudoDouble {
-		self.atan2_rev(PD_ONE)
+	/// `tan_rev`, or a `PdDomainError` at the poles, the odd multiples of 1/4.
+	pub const fn try_tan_rev(self) -> Result<PseudoDouble, PdDomainError> {
+		pd_tan_scaled(self,false)
 	}
 
-	pub const fn atan(self) -> PseudoDouble {
-		self.atan2(PD_ONE)
+	/// `tan`, or a `PdDomainError` when the reduced argument is exactly a pole.
+	pub const fn try_tan(self) -> Result<PseudoDouble, PdDomainError> {
+		pd_tan_scaled(self,true)
 	}
 
-	pub const fn sinh(self) -> PseudoDouble {
-		let t=self.exp();
-		t.const_sub(PD_ONE.const_div(t)).ldexp(-1)
+	/// `acosh`, or a `PdDomainError` unless `x>=1`.
+	pub const fn try_acosh(self) -> Result<PseudoDouble, PdDomainError> {
+		if self.const_less_than(PD_ONE) {
+			return Err(PdDomainError{function:"acosh"});
+		}
+		Ok(self.acosh())
 	}
 
-	pub const fn cosh(self) -> PseudoDouble {
-		let t=self.exp();
-		t.const_add(PD_ONE.const_div(t)).ldexp(-1)
+	/// `atanh`, or a `PdDomainError` unless `-1<x<1`.
+	pub const fn try_atanh(self) -> Result<PseudoDouble, PdDomainError> {
+		if self.const_less_than_or_equal(PD_NEG_ONE) || PD_ONE.const_less_than_or_equal(self) {
+			return Err(PdDomainError{function:"atanh"});
+		}
+		Ok(self.atanh())
 	}
 
-	pub const fn tanh(self) -> PseudoDouble {
-		let t=self.ldexp(1).const_neg().exp();
-		PD_ONE.const_sub(t).const_div(PD_ONE.const_add(t))
+	/// `log2`, or a `PdDomainError` unless `x>0`.
+	pub const fn try_log2(self) -> Result<PseudoDouble, PdDomainError> {
+		if self.0<=0 {
+			return Err(PdDomainError{function:"log2"});
+		}
+		Ok(self.log2())
 	}
 
-	pub const fn asinh(self) -> PseudoDouble {
-		self.const_add(self.const_mul(self).const_add(PD_ONE).sqrt()).ln()
+	/// `sqrt`, or a `PdDomainError` unless `x>=0`.
+	pub const fn try_sqrt(self) -> Result<PseudoDouble, PdDomainError> {
+		if self.0<0 {
+			return Err(PdDomainError{function:"sqrt"});
+		}
+		Ok(self.sqrt())
 	}
 
-	pub const fn acosh(self) -> PseudoDouble {
+	/// `x^y`, or a `PdDomainError` when `x<0` and `y` is not an integer, or `x=0` and `y<0`.
+	///
+	/// A negative `x` with an integral `y` gives a result with the sign of `x^y`, and `0^0=1`.
+	pub const fn try_powf(self, y:Self) -> Result<PseudoDouble, PdDomainError> {
+		if (self.0==0 && y.0<0) || (self.0<0 && y.floor().0!=y.0) {
+			return Err(PdDomainError{function:"powf"});
+		}
+		Ok(self.powf(y))
+	}
+
+	/// `erf_inv`, or a `PdDomainError` unless `-1<x<1`.
+	pub const fn try_erf_inv(self) -> Result<PseudoDouble, PdDomainError> {
 		if self.const_less_than_or_equal(PD_NEG_ONE) || PD_ONE.const_less_than_or_equal(self) {
-			panic!("acosh of number <1");
+			return Err(PdDomainError{function:"erf_inv"});
 		}
-		self.const_add(self.const_mul(self).const_sub(PD_ONE).sqrt()).ln()
+		Ok(self.erf_inv())
 	}
 
-	pub const fn atanh(self) -> PseudoDouble {
-		if self.const_less_than_or_equal(PD_ONE) {
-			panic!("atanh of number not between -1 and 1");
+	/// `normal_quantile`, or a `PdDomainError` unless `0<p<1`.
+	pub const fn try_normal_quantile(self) -> Result<PseudoDouble, PdDomainError> {
+		if self.0<=0 || PD_ONE.const_less_than_or_equal(self) {
+			return Err(PdDomainError{function:"normal_quantile"});
 		}
-		PD_ONE.const_add(self).const_div(PD_ONE.const_sub(self)).ln().ldexp(-1)
+		Ok(self.normal_quantile())
 	}
 
 	pub const fn sin(self) -> PseudoDouble {
@@ -2030,7 +5914,7 @@ impl PseudoDouble {
 	}
 
 	pub const fn atan2(self, other: PseudoDouble) -> PseudoDouble {
-		self.atan2_rev(other).const_mul(PD_TAU)
+		pd_atan2_scaled(self,other,true)
 	}
 
 	pub const fn is_finite(&self) -> bool {true}
@@ -2047,12 +5931,12 @@ impl ComplexField for PseudoDouble {
 	fn trunc(self) -> Self { self.trunc() }
 	fn fract(self) -> Self { self.fract() }
 	fn mul_add(self, a: Self, b: Self) -> Self { self.const_mul(a).const_add(b) }
-	fn hypot(self, other: Self) -> Self { self.const_mul(self).const_add(other.const_mul(other)).sqrt() }
+	fn hypot(self, other: Self) -> Self { self.hypot(other) }
 	fn log(self, base: Self) -> Self { self.log2().const_div(base.log2()) }
 	fn log2(self) -> Self { self.log2() }
 	fn log10(self) -> Self { self.log10() }
 	fn exp2(self) -> Self { self.exp2() }
-	fn exp_m1(self) -> Self { self.exp().const_sub(PD_ONE) }
+	fn exp_m1(self) -> Self { self.exp_m1() }
 	fn powc(self, other: Self) -> Self { self.powf(other) }
 	fn cbrt(self) -> Self { self.cbrt() }
 	fn try_sqrt(self) -> Option<Self> { if self.0>=0 {Some(self.sqrt())} else {None} }
@@ -2070,7 +5954,7 @@ impl ComplexField for PseudoDouble {
 	fn is_finite(&self) -> bool { self.is_finite() }
 	fn from_real(re: PseudoDouble) -> Self { re }
 	fn to_exp(self) -> (Self, Self) { (self, PD_ZERO) }
-	fn ln_1p(self) -> Self { (PD_ONE + self).ln() }
+	fn ln_1p(self) -> Self { self.ln_1p() }
 	fn exp(self) -> Self { self.exp() }
 	fn ln(self) -> Self { self.ln() }
 	fn sqrt(self) -> Self { self.sqrt() }
@@ -2092,9 +5976,103 @@ impl ComplexField for PseudoDouble {
 	fn atanh(self) -> Self { self.atanh() }
 }
 
+/// Writes the shortest decimal that parses back to exactly the same value, using only integer
+/// arithmetic. Plain notation is used for decimal exponents from -6 to 20, otherwise scientific
+/// notation (`1.5e300`). With a precision (`{:.3}`) it writes that many digits after the decimal
+/// point, correctly rounded. Width, fill, alignment, `+` and `0` flags work as for `f64`.
 impl Display for PseudoDouble {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", f64::from(*self))
+		if let Some(precision)=f.precision() {
+			return f.pad_integral(self.0>=0,"",&pd_to_fixed_string(*self,precision));
+		}
+		let (_negative,mut c,mut n,mut k)=pd_to_shortest_decimal(*self);
+		// rounding can leave trailing zeros or carry into a new digit
+		while n>1 && c%10==0 {
+			c/=10;
+			n-=1;
+		}
+		if c==10u64.pow(n as u32) {
+			c=1;
+			n=1;
+			k+=1;
+		}
+		let digits=c.to_string();
+		let mut s=String::new();
+		if (-6..21).contains(&k) {
+			if k>=n-1 {
+				s.push_str(&digits);
+				for _i in 0..(k-n+1) {
+					s.push('0');
+				}
+			} else if k>=0 {
+				s.push_str(&digits[..(k+1) as usize]);
+				s.push('.');
+				s.push_str(&digits[(k+1) as usize..]);
+			} else {
+				s.push_str("0.");
+				for _i in 0..(-k-1) {
+					s.push('0');
+				}
+				s.push_str(&digits);
+			}
+		} else {
+			s.push_str(&digits[..1]);
+			if n>1 {
+				s.push('.');
+				s.push_str(&digits[1..]);
+			}
+			s.push('e');
+			s.push_str(&k.to_string());
+		}
+		f.pad_integral(self.0>=0,"",&s)
+    }
+}
+
+/// The exact value in hexadecimal floating point (`0x1.8p+3`), or with `{:#x}` the raw bits (`pd:0x4000000000008003`).
+impl LowerHex for PseudoDouble {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		if f.alternate() {
+			return f.pad(&format!("pd:0x{:016x}",self.0 as u64));
+		}
+		f.pad_integral(self.0>=0,"",&pd_to_hex_float_string(*self,false))
+    }
+}
+
+/// The exact value in hexadecimal floating point (`0X1.8P+3`), or with `{:#X}` the raw bits (`pd:0x4000000000008003`).
+impl UpperHex for PseudoDouble {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		if f.alternate() {
+			return f.pad(&format!("pd:0x{:016X}",self.0 as u64));
+		}
+		f.pad_integral(self.0>=0,"",&pd_to_hex_float_string(*self,true))
+    }
+}
+
+/// Shows the decimal value, signed mantissa, unbiased exponent and raw bits. `{:#?}` puts each on its own line.
+impl Debug for PseudoDouble {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("PseudoDouble")
+			.field("value",&format_args!("{}",self))
+			.field("mantissa",&self.mantissa())
+			.field("exponent",&self.unbiased_exponent())
+			.field("bits",&format_args!("{:#018x}",self.0))
+			.finish()
+    }
+}
+
+/// Scientific notation (`1.5e300`) using only integer arithmetic. Without a precision it writes the
+/// shortest digits that parse back to exactly the same value.
+impl LowerExp for PseudoDouble {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		f.pad_integral(self.0>=0,"",&pd_to_exp_string(*self,f.precision(),false))
+    }
+}
+
+/// Scientific notation (`1.5E300`) using only integer arithmetic. Without a precision it writes the
+/// shortest digits that parse back to exactly the same value.
+impl UpperExp for PseudoDouble {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		f.pad_integral(self.0>=0,"",&pd_to_exp_string(*self,f.precision(),true))
     }
 }
 
@@ -2106,22 +6084,127 @@ impl RealField for PseudoDouble {
 	fn max(self, other: Self) -> Self { if self>other {self} else {other} }
 	fn min(self, other: Self) -> Self { if self<other {self} else {other} }
 	fn clamp(self, c1: Self, c2: Self) -> Self { if self<c1 {c1} else {if self>c2 {c2} else {self}} }
-	fn min_value() -> Option<Self> { Some(PseudoDouble( 0x7fffffffffffffffi64)) }
-	fn max_value() -> Option<Self> { Some(PseudoDouble((EXP_MASK as u64+0x8000000000000000u64) as i64)) }
-	fn pi() -> Self { PD_PI }
-	fn two_pi() -> Self { PD_PI.ldexp(1) }
-	fn frac_pi_2() -> Self { PD_PI.ldexp(-1) }
-	fn frac_pi_3() -> Self { PD_PI.const_div(PseudoDouble::pdc10(3,0)) }
-	fn frac_pi_4() -> Self { PD_PI.ldexp(-2) }
-	fn frac_pi_6() -> Self { PD_PI.const_div(PseudoDouble::pdc10(6,0)) }
-	fn frac_pi_8() -> Self { PD_PI.ldexp(-3) }
-	fn frac_1_pi() -> Self { PD_ONE.const_div(PD_PI) }
-	fn frac_2_pi() -> Self { PseudoDouble::pdc10(2,0).const_div(PD_PI) }
-	fn frac_2_sqrt_pi() -> Self { PseudoDouble::pdc10(2,0).const_div(PD_PI.sqrt()) }
-	fn e() -> Self { PD_ONE.exp() }
-	fn log2_e() -> Self { PD_ONE.exp().log2() }
-	fn log10_e() -> Self { PD_ONE.exp().log10() }
-	fn ln_2() -> Self { PseudoDouble::pdc10(2,0).ln() }
-	fn ln_10() -> Self { PseudoDouble::pdc10(10,0).ln() }
+	fn min_value() -> Option<Self> { Some(PseudoDouble::MIN) }
+	fn max_value() -> Option<Self> { Some(PseudoDouble::MAX) }
+	fn pi() -> Self { consts::PI }
+	fn two_pi() -> Self { consts::TAU }
+	fn frac_pi_2() -> Self { consts::FRAC_PI_2 }
+	fn frac_pi_3() -> Self { consts::FRAC_PI_3 }
+	fn frac_pi_4() -> Self { consts::FRAC_PI_4 }
+	fn frac_pi_6() -> Self { consts::FRAC_PI_6 }
+	fn frac_pi_8() -> Self { consts::FRAC_PI_8 }
+	fn frac_1_pi() -> Self { consts::FRAC_1_PI }
+	fn frac_2_pi() -> Self { consts::FRAC_2_PI }
+	fn frac_2_sqrt_pi() -> Self { consts::FRAC_2_SQRT_PI }
+	fn e() -> Self { consts::E }
+	fn log2_e() -> Self { consts::LOG2_E }
+	fn log10_e() -> Self { consts::LOG10_E }
+	fn ln_2() -> Self { consts::LN_2 }
+	fn ln_10() -> Self { consts::LN_10 }
+	fn atan2(self, other: Self) -> Self { self.atan2(other) }
+}
+
+/// PseudoDouble has no NaN, infinities or negative zero, and is deterministic on every platform:
+/// `nan()` and `neg_zero()` return zero, `infinity()` and `neg_infinity()` return `MAX` and `MIN`,
+/// `is_nan`, `is_infinite` and `is_subnormal` are always false, and `is_finite` is always true.
+/// Zero behaves as `+0.0`, so `is_sign_positive` treats it as positive, but `signum` of zero is zero
+/// as in `Signed::signum`, since there is no signed zero to report. `integer_decode`
+/// drops low mantissa bits below 2^-32722, where the exponent no longer fits in an `i16`.
+/// `Real` comes from the blanket implementation for `Float`.
+impl num_traits::Float for PseudoDouble {
+	fn nan() -> Self { PD_ZERO }
+	fn infinity() -> Self { PseudoDouble::MAX }
+	fn neg_infinity() -> Self { PseudoDouble::MIN }
+	fn neg_zero() -> Self { PD_ZERO }
+	fn min_value() -> Self { PseudoDouble::MIN }
+	fn min_positive_value() -> Self { PseudoDouble::MIN_POSITIVE }
+	fn epsilon() -> Self { PseudoDouble::EPSILON }
+	fn max_value() -> Self { PseudoDouble::MAX }
+	fn is_nan(self) -> bool { false }
+	fn is_infinite(self) -> bool { false }
+	fn is_finite(self) -> bool { true }
+	fn is_normal(self) -> bool { self.0!=0 }
+	fn is_subnormal(self) -> bool { false }
+	fn classify(self) -> FpCategory { if self.0==0 {FpCategory::Zero} else {FpCategory::Normal} }
+	fn floor(self) -> Self { self.floor() }
+	fn ceil(self) -> Self { self.ceil() }
+	fn round(self) -> Self { self.round() }
+	fn trunc(self) -> Self { self.trunc() }
+	fn fract(self) -> Self { self.fract() }
+	fn abs(self) -> Self { num_traits::Signed::abs(&self) }
+	fn signum(self) -> Self { num_traits::Signed::signum(&self) }
+	fn is_sign_positive(self) -> bool { self.0>=0 }
+	fn is_sign_negative(self) -> bool { self.0<0 }
+	fn mul_add(self, a: Self, b: Self) -> Self { self.const_mul(a).const_add(b) }
+	fn recip(self) -> Self { PD_ONE / self }
+	fn powi(self, n: i32) -> Self { self.powi(n) }
+	fn powf(self, n: Self) -> Self { self.powf(n) }
+	fn sqrt(self) -> Self { self.sqrt() }
+	fn exp(self) -> Self { self.exp() }
+	fn exp2(self) -> Self { self.exp2() }
+	fn ln(self) -> Self { self.ln() }
+	fn log(self, base: Self) -> Self { self.log2().const_div(base.log2()) }
+	fn log2(self) -> Self { self.log2() }
+	fn log10(self) -> Self { self.log10() }
+	fn to_degrees(self) -> Self { self.const_mul(PD_DEGREES_PER_RADIAN) }
+	fn to_radians(self) -> Self { self.const_mul(PD_RADIANS_PER_DEGREE) }
+	fn max(self, other: Self) -> Self { if self>other {self} else {other} }
+	fn min(self, other: Self) -> Self { if self<other {self} else {other} }
+	fn clamp(self, min: Self, max: Self) -> Self { if self<min {min} else {if self>max {max} else {self}} }
+	fn abs_sub(self, other: Self) -> Self { if self<=other {PD_ZERO} else {self.const_sub(other)} }
+	fn cbrt(self) -> Self { self.cbrt() }
+	fn hypot(self, other: Self) -> Self { self.hypot(other) }
+	fn sin(self) -> Self { self.sin() }
+	fn cos(self) -> Self { self.cos() }
+	fn tan(self) -> Self { self.tan() }
+	fn asin(self) -> Self { self.asin() }
+	fn acos(self) -> Self { self.acos() }
+	fn atan(self) -> Self { self.atan() }
 	fn atan2(self, other: Self) -> Self { self.atan2(other) }
+	fn sin_cos(self) -> (Self, Self) { self.sin_cos() }
+	fn exp_m1(self) -> Self { self.exp_m1() }
+	fn ln_1p(self) -> Self { self.ln_1p() }
+	fn sinh(self) -> Self { self.sinh() }
+	fn cosh(self) -> Self { self.cosh() }
+	fn tanh(self) -> Self { self.tanh() }
+	fn asinh(self) -> Self { self.asinh() }
+	fn acosh(self) -> Self { self.acosh() }
+	fn atanh(self) -> Self { self.atanh() }
+	fn integer_decode(self) -> (u64, i16, i8) { pd_integer_decode(self) }
+	fn copysign(self, sign: Self) -> Self { if (self.0^sign.0)<0 {self.const_neg()} else {self} }
+}
+
+/// The same as the `Float` implementation.
+impl num_traits::float::FloatCore for PseudoDouble {
+	fn nan() -> Self { <PseudoDouble as num_traits::Float>::nan() }
+	fn infinity() -> Self { <PseudoDouble as num_traits::Float>::infinity() }
+	fn neg_infinity() -> Self { <PseudoDouble as num_traits::Float>::neg_infinity() }
+	fn neg_zero() -> Self { <PseudoDouble as num_traits::Float>::neg_zero() }
+	fn min_value() -> Self { <PseudoDouble as num_traits::Float>::min_value() }
+	fn min_positive_value() -> Self { <PseudoDouble as num_traits::Float>::min_positive_value() }
+	fn epsilon() -> Self { <PseudoDouble as num_traits::Float>::epsilon() }
+	fn max_value() -> Self { <PseudoDouble as num_traits::Float>::max_value() }
+	fn is_nan(self) -> bool { num_traits::Float::is_nan(self) }
+	fn is_infinite(self) -> bool { num_traits::Float::is_infinite(self) }
+	fn is_finite(self) -> bool { num_traits::Float::is_finite(self) }
+	fn is_normal(self) -> bool { num_traits::Float::is_normal(self) }
+	fn is_subnormal(self) -> bool { num_traits::Float::is_subnormal(self) }
+	fn classify(self) -> FpCategory { num_traits::Float::classify(self) }
+	fn floor(self) -> Self { self.floor() }
+	fn ceil(self) -> Self { self.ceil() }
+	fn round(self) -> Self { self.round() }
+	fn trunc(self) -> Self { self.trunc() }
+	fn fract(self) -> Self { self.fract() }
+	fn abs(self) -> Self { num_traits::Float::abs(self) }
+	fn signum(self) -> Self { num_traits::Float::signum(self) }
+	fn is_sign_positive(self) -> bool { num_traits::Float::is_sign_positive(self) }
+	fn is_sign_negative(self) -> bool { num_traits::Float::is_sign_negative(self) }
+	fn min(self, other: Self) -> Self { num_traits::Float::min(self,other) }
+	fn max(self, other: Self) -> Self { num_traits::Float::max(self,other) }
+	fn clamp(self, min: Self, max: Self) -> Self { num_traits::Float::clamp(self,min,max) }
+	fn recip(self) -> Self { num_traits::Float::recip(self) }
+	fn powi(self, n: i32) -> Self { self.powi(n) }
+	fn to_degrees(self) -> Self { num_traits::Float::to_degrees(self) }
+	fn to_radians(self) -> Self { num_traits::Float::to_radians(self) }
+	fn integer_decode(self) -> (u64, i16, i8) { pd_integer_decode(self) }
 }
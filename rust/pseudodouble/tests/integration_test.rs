@@ -1,4 +1,4 @@
-use pseudodouble::PseudoDouble;
+use pseudodouble::{consts,pd,PseudoDouble,PseudoDoubleParts,ParsePdError,PdDomainError};
 use libm::{ ldexp };
 use rand::{Rng,SeedableRng};
 use rand::rngs::StdRng;
@@ -18,9 +18,9 @@ pub const NEAR_EXACT3:  f64=0.999;
 
 const fn compare(d1:f64, d2:f64, exactness:f64) -> bool {
 	if d1>=0.0 {
-		return d1*exactness<=d2 && d2*exactness<=d1;
+		d1*exactness<=d2 && d2*exactness<=d1
 	} else {
-		return d1*exactness>=d2 && d2*exactness>=d1;
+		d1*exactness>=d2 && d2*exactness>=d1
 	}
 }
 
@@ -58,7 +58,7 @@ fn do_tests() {
 			count+=1;
 			if !compare(f1+f2,ffa,NEAR_EXACT8) {
 				failures+=1;
-				println!("add {}+{}=={}!={}",f64::from(f1),f64::from(f2),f64::from(f1+f2),f64::from(ffa));
+				println!("add {}+{}=={}!={}",f1,f2,f1+f2,ffa);
 			}
             assert!(compare(f1+f2,ffa,NEAR_EXACT8),"add failed");
             assert!(compare(f1+f2,f64::from(pd1.const_add(pd2)),NEAR_EXACT8),"const add failed");
@@ -66,7 +66,7 @@ fn do_tests() {
 			count+=1;
 			if !compare(f1-f2,ffs,NEAR_EXACT8) {
 				failures+=1;
-				println!("sub {}-{}=={}!={}",f64::from(f1),f64::from(f2),f64::from(f1-f2),f64::from(ffs));
+				println!("sub {}-{}=={}!={}",f1,f2,f1-f2,ffs);
 			}
             assert!(compare(f1-f2,ffs,NEAR_EXACT8),"sub failed");
             assert!(compare(f1-f2,f64::from(pd1.const_sub(pd2)),NEAR_EXACT8),"const sub failed");
@@ -74,7 +74,7 @@ fn do_tests() {
 			count+=1;
 			if !compare(f1*f2,ffm,NEAR_EXACT8) {
 				failures+=1;
-				println!("mul {}*{}=={}!={}",f64::from(f1),f64::from(f2),f64::from(f1*f2),f64::from(ffm));
+				println!("mul {}*{}=={}!={}",f1,f2,f1*f2,ffm);
 			}
             assert!(compare(f1*f2,ffm,NEAR_EXACT8),"mul failed");
             assert!(compare(f1*f2,f64::from(pd1.const_mul(pd2)),NEAR_EXACT8),"const mul failed");
@@ -97,7 +97,7 @@ fn do_tests() {
 				count+=1;
 				if !compare(f1/f2,ffd,NEAR_EXACT8) {
 					failures+=1;
-					println!("div {}/{}=={}!={}",f64::from(f1),f64::from(f2),f64::from(f1/f2),f64::from(ffd));
+					println!("div {}/{}=={}!={}",f1,f2,f1/f2,ffd);
 				}
                 assert!(compare(f1/f2,ffd,NEAR_EXACT8),"div failed");
                 assert!(compare(f1/f2,f64::from(pd1.const_div(pd2)),NEAR_EXACT8),"const div failed");
@@ -274,3 +274,1272 @@ fn do_tests() {
 	println!("Rust: Solution 2 = {}",f64::from(sol2));
 }
 
+
+#[test]
+fn half_precision_tests() {
+	for bits in 0u16..=0xFFFF {
+		if (bits&0x7C00)!=0x7C00 {
+			let pd=PseudoDouble::from_f16_bits(bits);
+			let expected=if (bits&0x7FFF)==0 {0} else {bits};
+			assert_eq!(pd.to_f16_bits(),expected,"f16 round trip failed");
+			assert_eq!(PseudoDouble::from_f32(f32::from(pd)),pd,"f16 to f32 failed");
+		}
+		if (bits&0x7F80)!=0x7F80 {
+			let pd=PseudoDouble::from_bf16_bits(bits);
+			let expected=if (bits&0x7FFF)==0 {0} else {bits};
+			assert_eq!(pd.to_bf16_bits(),expected,"bf16 round trip failed");
+			assert_eq!(f64::from(pd),f32::from_bits((bits as u32)<<16) as f64,"bf16 value failed");
+		}
+	}
+	// ties round to even
+	assert_eq!(PseudoDouble::from_f32(1.0+ldexp(1.0,-11) as f32).to_f16_bits(),0x3C00);
+	assert_eq!(PseudoDouble::from_f32(1.0+ldexp(3.0,-11) as f32).to_f16_bits(),0x3C02);
+	assert_eq!(PseudoDouble::from(65520).to_f16_bits(),0x7C00);
+	assert_eq!(PseudoDouble::from(-65519).to_f16_bits(),0xFBFF);
+	assert_eq!(PseudoDouble::from_f32(ldexp(1.0,-25) as f32).to_f16_bits(),0);
+	assert_eq!(PseudoDouble::from_f32(ldexp(3.0,-26) as f32).to_f16_bits(),1);
+	let mut rng = StdRng::seed_from_u64(226);
+	for _i in 0..10000 {
+		let f=f32::from_bits(rng.gen::<u32>());
+		if f.is_finite() {
+			let pd=PseudoDouble::from(f);
+			assert_eq!(f64::from(pd),f as f64,"from_f32 failed for {}",f);
+		}
+	}
+}
+
+// exact decimal representation of m*2^e for small e
+fn exact_decimal(m:u64, e:i32) -> String {
+	if e>=0 {
+		return format!("{}",(m as u128)<<e);
+	}
+	let digits=format!("{:0>width$}",(m as u128)*5u128.pow((-e) as u32),width=(-e) as usize+1);
+	let point=digits.len()-(-e) as usize;
+	format!("{}.{}",&digits[..point],&digits[point..])
+}
+
+#[test]
+fn string_parse_tests() {
+	let parse=|s:&str| PseudoDouble::string_to_pd(s).unwrap();
+	assert_eq!(parse("1.5"),PseudoDouble::pdc2(3,-1));
+	assert_eq!(parse("+1.5"),PseudoDouble::pdc2(3,-1));
+	assert_eq!(parse("  -1.5e0\n"),PseudoDouble::pdc2(-3,-1));
+	assert_eq!(parse(".5"),PseudoDouble::pdc2(1,-1));
+	assert_eq!(parse("5."),PseudoDouble::from(5));
+	assert_eq!(parse("0.000"),PseudoDouble::from(0));
+	assert_eq!(parse("-0"),PseudoDouble::from(0));
+	assert_eq!(parse("0e-99999"),PseudoDouble::from(0));
+	assert_eq!(parse("1e-5"),parse("0.00001"));
+	assert_eq!(parse("1e-5"),parse("10E-6"));
+	assert_eq!(parse("1234567890123456789012345678901234567890"),parse("1.234567890123456789012345678901234567890e39"));
+	assert_eq!(parse("123456789012345"),PseudoDouble::from(123456789012345u64));
+	assert_eq!(parse("0.1000000000000000000000000000001"),parse("0.1"));
+	assert!((f64::from(parse("1e300"))/1e300-1.0).abs()<1e-14);
+	assert!((f64::from(parse("-2.5e-300"))/-2.5e-300-1.0).abs()<1e-14);
+	assert_eq!(parse("1e9000"),parse(&format!("1{}","0".repeat(9000))));
+	assert_eq!(parse("1e-9000"),parse(&format!("0.{}1",&"0".repeat(8999))));
+	assert_eq!(parse(&format!("0.1{}1",&"0".repeat(30000))),parse("0.1"));
+	assert_eq!(parse(&format!("1.{}1",&"0".repeat(30000))),parse("1"));
+	assert_eq!(PseudoDouble::string_to_pd(""),Err(ParsePdError::Empty));
+	assert_eq!(PseudoDouble::string_to_pd("  "),Err(ParsePdError::Empty));
+	assert_eq!(PseudoDouble::string_to_pd("-"),Err(ParsePdError::InvalidDigit(1)));
+	assert_eq!(PseudoDouble::string_to_pd("."),Err(ParsePdError::InvalidDigit(1)));
+	assert_eq!(PseudoDouble::string_to_pd("1.2.3"),Err(ParsePdError::InvalidDigit(3)));
+	assert_eq!(PseudoDouble::string_to_pd("1e"),Err(ParsePdError::InvalidDigit(2)));
+	assert_eq!(PseudoDouble::string_to_pd("1e+x"),Err(ParsePdError::InvalidDigit(3)));
+	assert_eq!(PseudoDouble::string_to_pd("12a"),Err(ParsePdError::InvalidDigit(2)));
+	assert_eq!(PseudoDouble::string_to_pd("1e99999"),Err(ParsePdError::Overflow));
+	assert_eq!(PseudoDouble::string_to_pd("-1e9865"),Err(ParsePdError::Overflow));
+	assert_eq!(PseudoDouble::string_to_pd("1e-99999"),Err(ParsePdError::Underflow));
+	assert!(PseudoDouble::string_to_pd("1e9863").is_ok());
+	assert!(PseudoDouble::string_to_pd("1e-9864").is_ok());
+	// exact values, halfway points and values either side of halfway points
+	let mut rng = StdRng::seed_from_u64(227);
+	for _i in 0..2000 {
+		let m=rng.gen_range((1u64<<46)..(1u64<<47));
+		let e=rng.gen_range(-30..20);
+		let exact=PseudoDouble::pdc2(m as i64,e);
+		assert_eq!(parse(&exact_decimal(m,e)),exact,"exact parse failed");
+		assert_eq!(parse(&format!("-{}",exact_decimal(m,e))),-exact,"exact negative parse failed");
+		let halfway=exact_decimal(2*m+1,e-1);
+		let even=if (m&1)==0 {exact} else {PseudoDouble::pdc2(m as i64+1,e)};
+		assert_eq!(parse(&halfway),even,"halfway parse failed");
+		let above=if halfway.contains('.') {format!("{}000000000000000000000000000001",halfway)} else {format!("{}.000000000000000000000000000001",halfway)};
+		assert_eq!(parse(&above),PseudoDouble::pdc2(m as i64+1,e),"above halfway parse failed");
+		let below=if e>=1 {
+			format!("{}.99999999999999999999999999",(((2*m+1) as u128)<<(e-1))-1)
+		} else {
+			format!("{}499999999999999999999999999",&halfway[..halfway.len()-1]) // halfway ends in 5
+		};
+		assert_eq!(parse(&below),exact,"below halfway parse failed");
+	}
+}
+
+#[test]
+fn display_tests() {
+	let parse=|s:&str| PseudoDouble::string_to_pd(s).unwrap();
+	for s in ["0","1","-1","1.5","0.1","-0.3","123456789","0.000001","1e-7","1.25e-8","100000000000000000000","1e21","1e300","-2.5e-300","1e5000","1.2345e-9000"] {
+		assert_eq!(parse(s).to_string(),s,"display failed");
+	}
+	assert_eq!(PseudoDouble::from(1u64<<40).to_string(),"1099511627776");
+	assert_eq!(format!("{}",PseudoDouble::pdc2(1,-3)),"0.125");
+	let mut rng = StdRng::seed_from_u64(228);
+	for _i in 0..5000 {
+		let mantissa=rng.gen::<i64>()&!0xFFFF;
+		let exponent=rng.gen_range(0..0x10000i64);
+		if (mantissa>>62)==0 || (mantissa>>62)==-1 {
+			continue; // not normalized
+		}
+		let x=PseudoDouble(mantissa+exponent);
+		let s=x.to_string();
+		assert_eq!(parse(&s),x,"round trip failed for {}",s);
+		// shortest: dropping the last digit does not round trip
+		let digits=s.split('e').next().unwrap().trim_start_matches(['-','0','.']).replace('.',"");
+		if digits.len()>1 && s.contains('e') {
+			let (mantissa_str,exp_str)=s.split_once('e').unwrap();
+			let shorter=&mantissa_str[..mantissa_str.len()-1];
+			assert_ne!(parse(&format!("{}e{}",shorter,exp_str)),x,"not shortest {}",s);
+		}
+	}
+}
+
+#[test]
+fn format_tests() {
+	let parse=|s:&str| PseudoDouble::string_to_pd(s).unwrap();
+	let x=parse("1234.5678");
+	assert_eq!(format!("{:.2}",x),"1234.57");
+	assert_eq!(format!("{:.0}",x),"1235");
+	assert_eq!(format!("{:.6}",-x),"-1234.567800");
+	assert_eq!(format!("{:.1}",parse("0.25")),"0.2");
+	assert_eq!(format!("{:.1}",parse("0.75")),"0.8");
+	assert_eq!(format!("{:.3}",parse("0.0001")),"0.000");
+	assert_eq!(format!("{:.3}",parse("0.0006")),"0.001");
+	assert_eq!(format!("{:.2}",parse("1e-9000")),"0.00");
+	assert_eq!(format!("{:.2}",PseudoDouble::from(0)),"0.00");
+	assert_eq!(format!("{:.2}",parse("99.999")),"100.00");
+	assert_eq!(format!("{:.30}",parse("0.5")),format!("0.5{}","0".repeat(29)));
+	assert_eq!(format!("{:.0}",PseudoDouble::pdc2(1,100)),"1267650600228229401496703205376");
+	assert_eq!(x.to_string_fixed(3),"1234.568");
+	assert_eq!(format!("{:e}",x),"1.2345678e3");
+	assert_eq!(format!("{:E}",x),"1.2345678E3");
+	assert_eq!(format!("{:e}",parse("-1e-300")),"-1e-300");
+	assert_eq!(format!("{:e}",PseudoDouble::from(0)),"0e0");
+	assert_eq!(format!("{:.2e}",x),"1.23e3");
+	assert_eq!(format!("{:.2e}",parse("9.999")),"1.00e1");
+	assert_eq!(format!("{:.2e}",PseudoDouble::from(0)),"0.00e0");
+	assert_eq!(format!("{:.3e}",parse("1.2345e5000")),"1.234e5000");
+	assert_eq!(format!("{:.20e}",parse("0.5")),"5.00000000000000000000e-1");
+	assert_eq!(format!("{:.40e}",PseudoDouble::pdc2(1,-20)),"9.5367431640625000000000000000000000000000e-7");
+	assert_eq!(format!("{:.2E}",parse("-1.5e-9000")),"-1.50E-9000");
+	assert_eq!(format!("{:+}",x),"+1234.5678");
+	assert_eq!(format!("{:+.1}",-x),"-1234.6");
+	assert_eq!(format!("{:>12}",x),"   1234.5678");
+	assert_eq!(format!("{:<12}|",x),"1234.5678   |");
+	assert_eq!(format!("{:*^13}",x),"**1234.5678**");
+	assert_eq!(format!("{:012.3}",-x),"-0001234.568");
+	assert_eq!(format!("{:+010e}",x),"+1.2345678e3");
+	assert_eq!(format!("{:12.2e}",x),"      1.23e3");
+	// agrees with f64 where the value is exactly representable in both
+	let mut rng = StdRng::seed_from_u64(229);
+	for _i in 0..2000 {
+		let f=(rng.gen::<f64>()-0.5)*ldexp(1.0,rng.gen_range(-60..60));
+		let f=f32::from_bits((f as f32).to_bits()&0xFFFFFF00) as f64; // fewer mantissa bits than a PseudoDouble
+		let pd=PseudoDouble::double_to_pseudodouble_unsafe(f);
+		for precision in [0,1,3,10,25] {
+			assert_eq!(format!("{:.*}",precision,pd),format!("{:.*}",precision,f),"fixed {}",f);
+			assert_eq!(format!("{:.*e}",precision,pd),format!("{:.*e}",precision,f),"exp {}",f);
+		}
+	}
+}
+
+#[test]
+fn hex_tests() {
+	let x=PseudoDouble::from(12);
+	assert_eq!(format!("{:x}",x),"0x1.8p+3");
+	assert_eq!(format!("{:X}",-x),"-0X1.8P+3");
+	assert_eq!(format!("{:x}",PseudoDouble::from(1)),"0x1p+0");
+	assert_eq!(format!("{:x}",PseudoDouble::pdc2(-1,-1)),"-0x1p-1");
+	assert_eq!(format!("{:x}",PseudoDouble::from(0)),"0x0p+0");
+	assert_eq!(format!("{:#x}",x),"pd:0x6000000000008005");
+	assert_eq!(x.to_hex_string(),"0x1.8p+3");
+	assert_eq!(x.to_bits_string(),"pd:0x6000000000008005");
+	assert_eq!(PseudoDouble::string_to_pd("0x1.8p+3"),Ok(x));
+	assert_eq!(PseudoDouble::string_to_pd(" -0X1.8P3 "),Ok(-x));
+	assert_eq!(PseudoDouble::string_to_pd("0x18"),Ok(PseudoDouble::from(24)));
+	assert_eq!(PseudoDouble::string_to_pd("0x.8"),Ok(PseudoDouble::pdc2(1,-1)));
+	assert_eq!(PseudoDouble::string_to_pd("pd:0x6000000000008005"),Ok(x));
+	assert_eq!(PseudoDouble::hex_string_to_pd("1.8p3"),Ok(x));
+	assert_eq!(<PseudoDouble as num_traits::Num>::from_str_radix("-1.8p3",16),Ok(-x));
+	assert_eq!(<PseudoDouble as num_traits::Num>::from_str_radix("1.5",10),Ok(PseudoDouble::pdc2(3,-1)));
+	assert!(<PseudoDouble as num_traits::Num>::from_str_radix("1.5",8).is_err());
+	// rounding beyond the 47 bits of the mantissa, ties to even
+	assert_eq!(PseudoDouble::string_to_pd("0x1.000000000001p0"),Ok(PseudoDouble::from(1)));
+	assert_eq!(PseudoDouble::string_to_pd("0x1.000000000002p0"),Ok(PseudoDouble::from(1)));
+	assert_eq!(PseudoDouble::string_to_pd("0x1.000000000006p0"),Ok(PseudoDouble::pdc2((1<<46)+2,-46)));
+	assert_eq!(PseudoDouble::string_to_pd("0x1.000000000003p0"),Ok(PseudoDouble::pdc2((1<<46)+1,-46)));
+	assert_eq!(PseudoDouble::string_to_pd("0x1.0000000000020000000001p0"),Ok(PseudoDouble::pdc2((1<<46)+1,-46)));
+	assert_eq!(PseudoDouble::string_to_pd("0x1p99999"),Err(ParsePdError::Overflow));
+	assert_eq!(PseudoDouble::string_to_pd("0x1p-99999"),Err(ParsePdError::Underflow));
+	assert_eq!(PseudoDouble::string_to_pd("0x1.8q3"),Err(ParsePdError::InvalidDigit(5)));
+	assert_eq!(PseudoDouble::string_to_pd("0x"),Err(ParsePdError::InvalidDigit(2)));
+	assert_eq!(PseudoDouble::string_to_pd("pd:0x"),Err(ParsePdError::InvalidDigit(5)));
+	assert_eq!(PseudoDouble::string_to_pd("pd:0x12345678901234567"),Err(ParsePdError::Overflow));
+	let mut rng = StdRng::seed_from_u64(230);
+	for _i in 0..5000 {
+		let bits=rng.gen::<i64>();
+		assert_eq!(PseudoDouble::string_to_pd(&format!("{:#x}",PseudoDouble(bits))),Ok(PseudoDouble(bits)),"raw bits round trip failed");
+		let mantissa=bits&!0xFFFF;
+		if (mantissa>>62)==0 || (mantissa>>62)==-1 {
+			continue; // not normalized
+		}
+		let x=PseudoDouble(bits);
+		assert_eq!(PseudoDouble::string_to_pd(&format!("{:x}",x)),Ok(x),"hex round trip failed");
+		assert_eq!(PseudoDouble::string_to_pd(&format!("{:X}",x)),Ok(x),"hex round trip failed");
+	}
+}
+
+#[test]
+fn from_str_tests() -> Result<(),Box<dyn std::error::Error>> {
+	let x:PseudoDouble="1.5".parse()?;
+	assert_eq!(x,PseudoDouble::pdc2(3,-1));
+	assert_eq!("-2e3".parse::<PseudoDouble>(),Ok(PseudoDouble::from(-2000)));
+	assert_eq!("0x1p-2".parse::<PseudoDouble>(),Ok(PseudoDouble::pdc2(1,-2)));
+	assert_eq!("".parse::<PseudoDouble>(),Err(ParsePdError::Empty));
+	assert_eq!("1.5x".parse::<PseudoDouble>(),Err(ParsePdError::InvalidDigit(3)));
+	assert_eq!("1e100000".parse::<PseudoDouble>(),Err(ParsePdError::Overflow));
+	assert_eq!("1e-100000".parse::<PseudoDouble>(),Err(ParsePdError::Underflow));
+	assert_eq!(<PseudoDouble as num_traits::Num>::from_str_radix("17",8),Err(ParsePdError::UnsupportedRadix(8)));
+	assert_eq!("1.5x".parse::<PseudoDouble>().unwrap_err().to_string(),"invalid digit found in string at position 3");
+	assert_eq!("".parse::<PseudoDouble>().unwrap_err().to_string(),"cannot parse PseudoDouble from empty string");
+	assert_eq!("1e100000".parse::<PseudoDouble>().unwrap_err().to_string(),"number too large to fit in PseudoDouble");
+	assert_eq!("1e-100000".parse::<PseudoDouble>().unwrap_err().to_string(),"number too small to fit in PseudoDouble");
+	Ok(())
+}
+
+const TAU_LITERAL:PseudoDouble=pd!("6.283185307179586477");
+static SMALL_LITERAL:PseudoDouble=pd!(1.5e-3);
+
+#[test]
+fn literal_macro_tests() {
+	assert_eq!(TAU_LITERAL,PseudoDouble::string_to_pd("6.283185307179586477").unwrap());
+	assert_eq!(SMALL_LITERAL,PseudoDouble::string_to_pd("0.0015").unwrap());
+	assert_eq!(pd!(12),PseudoDouble::from(12));
+	assert_eq!(pd!(-2.5),PseudoDouble::pdc2(-5,-1));
+	assert_eq!(pd!("-2.5"),PseudoDouble::pdc2(-5,-1));
+	assert_eq!(pd!(" 1e-9000 "),PseudoDouble::string_to_pd("1e-9000").unwrap());
+	assert_eq!(pd!("0x1.8p+3"),PseudoDouble::from(12));
+	// separators and type suffixes of number literals
+	assert_eq!(pd!(1_000.0),PseudoDouble::from(1000));
+	assert_eq!(pd!(2.5f64),PseudoDouble::pdc2(5,-1));
+	assert_eq!(pd!(-2.5f32),PseudoDouble::pdc2(-5,-1));
+	assert_eq!(pd!(0.1f32),PseudoDouble::string_to_pd("0.1").unwrap());
+	assert_eq!(pd!(1_0e-1_0f64),PseudoDouble::string_to_pd("1e-9").unwrap());
+	assert_eq!(pd!(7u32),PseudoDouble::from(7));
+	assert_eq!(pd!(1_000_000_i64),PseudoDouble::from(1000000));
+	assert_eq!(PseudoDouble::const_from_str("0.5"),PseudoDouble::pdc2(1,-1));
+	assert!(std::panic::catch_unwind(|| PseudoDouble::const_from_str("\"0.5\"")).is_err());
+}
+
+#[test]
+fn debug_tests() {
+	let x=pd!(-1.5);
+	assert_eq!(format!("{:?}",x),"PseudoDouble { value: -1.5, mantissa: -105553116266496, exponent: -46, bits: 0xa000000000008002 }");
+	assert_eq!(format!("{:#?}",x),"PseudoDouble {\n    value: -1.5,\n    mantissa: -105553116266496,\n    exponent: -46,\n    bits: 0xa000000000008002,\n}");
+	assert_eq!(x.describe(),PseudoDoubleParts{decimal:"-1.5".to_string(),mantissa:-105553116266496,exponent:-46,bits:0xa000000000008002});
+	let mut rng=StdRng::seed_from_u64(233);
+	for _ in 0..1000 {
+		let x=PseudoDouble::from(rng.gen_range(-1e30..1e30));
+		let parts=x.describe();
+		assert_eq!(PseudoDouble(parts.bits as i64),x);
+		assert_eq!(PseudoDouble::from(parts.mantissa).ldexp(parts.exponent),x);
+		assert_eq!(parts.decimal.parse::<PseudoDouble>().unwrap(),x);
+	}
+	assert_eq!(PseudoDouble::from(0).describe().mantissa,0);
+}
+
+#[test]
+fn consts_tests() {
+	use std::f64::consts as f;
+	use simba::scalar::RealField;
+	let cases=[(consts::PI,f::PI),(consts::TAU,f::TAU),(consts::FRAC_PI_2,f::FRAC_PI_2),(consts::FRAC_PI_3,f::FRAC_PI_3),
+		(consts::FRAC_PI_4,f::FRAC_PI_4),(consts::FRAC_PI_6,f::FRAC_PI_6),(consts::FRAC_PI_8,f::FRAC_PI_8),(consts::FRAC_1_PI,f::FRAC_1_PI),
+		(consts::FRAC_2_PI,f::FRAC_2_PI),(consts::FRAC_2_SQRT_PI,f::FRAC_2_SQRT_PI),(consts::SQRT_2,f::SQRT_2),(consts::FRAC_1_SQRT_2,f::FRAC_1_SQRT_2),
+		(consts::E,f::E),(consts::LOG2_E,f::LOG2_E),(consts::LOG2_10,f::LOG2_10),(consts::LOG10_E,f::LOG10_E),(consts::LOG10_2,f::LOG10_2),
+		(consts::LN_2,f::LN_2),(consts::LN_10,f::LN_10),(consts::FRAC_1_SQRT_PI,0.5641895835477563),(consts::FRAC_1_SQRT_2PI,0.3989422804014327),
+		(consts::SQRT_3,1.7320508075688772),(consts::FRAC_1_SQRT_3,0.5773502691896257),(consts::PHI,1.618033988749895),(consts::EGAMMA,0.5772156649015329)];
+	for (c,x) in cases {
+		// within half a PseudoDouble ulp of the true value, allowing for the f64 rounding
+		let ulp=2f64.powi(x.log2().floor() as i32-46);
+		assert!((f64::from(c)-x).abs()<=ulp*0.5+x*f64::EPSILON,"{} {}",c,x);
+	}
+	assert_eq!(consts::TAU,consts::PI.ldexp(1));
+	assert_eq!(consts::FRAC_PI_8,consts::PI.ldexp(-3));
+	assert_eq!(<PseudoDouble as RealField>::e(),consts::E);
+	assert_eq!(<PseudoDouble as RealField>::frac_pi_3(),consts::FRAC_PI_3);
+	assert_eq!(<PseudoDouble as RealField>::ln_10(),consts::LN_10);
+}
+
+#[test]
+fn limits_tests() {
+	use num_traits::Bounded;
+	use simba::scalar::RealField;
+	assert_eq!(PseudoDouble::MANTISSA_DIGITS,47);
+	assert_eq!(PseudoDouble::EPSILON,PseudoDouble::pdc2(1,-46));
+	assert!(PseudoDouble::from(1)+PseudoDouble::EPSILON>PseudoDouble::from(1));
+	assert_eq!(PseudoDouble::MIN_POSITIVE,PseudoDouble::pdc2(1,PseudoDouble::MIN_EXP-1));
+	assert!(PseudoDouble::MAX.ldexp(-1)<PseudoDouble::pdc2(1,PseudoDouble::MAX_EXP-1));
+	assert!(PseudoDouble::MAX>PseudoDouble::pdc2(1,PseudoDouble::MAX_EXP-1));
+	assert_eq!(PseudoDouble::MIN,PseudoDouble::pdc2(-1,PseudoDouble::MAX_EXP));
+	assert_eq!(-PseudoDouble::MAX-PseudoDouble::pdc2(1,PseudoDouble::MAX_EXP-47),PseudoDouble::MIN);
+	assert!(PseudoDouble::MIN<PseudoDouble::pdc10(-1,9000)&&PseudoDouble::MAX>PseudoDouble::pdc10(1,9000));
+	assert!(PseudoDouble::MIN_POSITIVE>PseudoDouble::from(0));
+	if cfg!(feature="check_on_pseudodouble_underflow") {
+		assert_eq!(-PseudoDouble::MIN_POSITIVE,PseudoDouble::from(0));
+		assert_eq!(PseudoDouble::MIN_POSITIVE.const_neg(),PseudoDouble::from(0));
+	}
+	assert_eq!(-PseudoDouble::MIN_POSITIVE.ldexp(1),PseudoDouble::pdc2(-1,PseudoDouble::MIN_EXP));
+	if cfg!(feature="panic_on_pseudodouble_overflow") {
+		assert!(std::panic::catch_unwind(|| -PseudoDouble::MIN).is_err());
+	}
+	assert_eq!(<PseudoDouble as Bounded>::min_value(),PseudoDouble::MIN);
+	assert_eq!(<PseudoDouble as Bounded>::max_value(),PseudoDouble::MAX);
+	assert_eq!(<PseudoDouble as RealField>::min_value(),Some(PseudoDouble::MIN));
+	assert_eq!(<PseudoDouble as RealField>::max_value(),Some(PseudoDouble::MAX));
+	assert_eq!(PseudoDouble::MAX.to_string().parse::<PseudoDouble>(),Ok(PseudoDouble::MAX));
+	assert_eq!(PseudoDouble::MIN_POSITIVE.to_string().parse::<PseudoDouble>(),Ok(PseudoDouble::MIN_POSITIVE));
+}
+
+fn float_norm<T:num_traits::Float>(x:T, y:T) -> T {
+	(x*x+y*y).sqrt().max(T::min_positive_value())
+}
+
+fn real_angle<T:num_traits::real::Real>(x:T, y:T) -> T {
+	y.atan2(x).to_degrees()
+}
+
+#[test]
+fn float_trait_tests() {
+	use num_traits::ToPrimitive;
+	use num_traits::{Float,NumCast as Cast};
+	use num_traits::float::FloatCore;
+	use std::num::FpCategory;
+	assert_eq!(float_norm(pd!(3),pd!(4)),pd!(5));
+	assert!((real_angle(pd!(1),pd!(1)).to_f64().unwrap()-45.0).abs()<1e-10);
+	assert_eq!(<PseudoDouble as Float>::nan(),pd!(0));
+	assert_eq!(<PseudoDouble as Float>::infinity(),PseudoDouble::MAX);
+	assert_eq!(<PseudoDouble as FloatCore>::neg_infinity(),PseudoDouble::MIN);
+	assert!(!Float::is_nan(PseudoDouble::MAX) && !FloatCore::is_infinite(PseudoDouble::MAX) && FloatCore::is_finite(PseudoDouble::MIN));
+	assert_eq!(Float::classify(pd!(0)),FpCategory::Zero);
+	assert_eq!(FloatCore::classify(PseudoDouble::MIN_POSITIVE),FpCategory::Normal);
+	assert_eq!(Float::signum(pd!(0)),pd!(0));
+	assert_eq!(FloatCore::signum(pd!(0)),num_traits::Signed::signum(&pd!(0)));
+	assert_eq!(Float::signum(pd!(-0.5)),pd!(-1));
+	assert_eq!(Float::abs_sub(pd!(2),pd!(5)),pd!(0));
+	assert_eq!(num_traits::Signed::abs(&pd!(-2)),pd!(2));
+	assert_eq!(num_traits::Signed::abs(&pd!(-0.5)),pd!(0.5));
+	assert_eq!(Float::copysign(pd!(2),pd!(-5)),pd!(-2));
+	assert_eq!(Float::to_degrees(pd!(0)),pd!(0));
+	assert_eq!(FloatCore::to_radians(pd!(180)),consts::PI);
+	let mut rng=StdRng::seed_from_u64(236);
+	for _ in 0..1000 {
+		let x=PseudoDouble::pdc2(rng.gen_range(-(1i64<<47)..(1i64<<47)),rng.gen_range(-32700..32700));
+		let (m,e,s)=Float::integer_decode(x);
+		assert_eq!(PseudoDouble::pdc2(m as i64*s as i64,e as i32),x);
+		let f=rng.gen_range(-1e300..1e300)*rng.gen_range(-1e-300..1e-300);
+		let y:PseudoDouble=Cast::from(f).unwrap();
+		assert_eq!(y,format!("{:.800e}",f).parse::<PseudoDouble>().unwrap());
+		let i=rng.gen::<i64>();
+		let y:PseudoDouble=Cast::from(i).unwrap();
+		assert_eq!(y,i.to_string().parse::<PseudoDouble>().unwrap());
+		if let Some(j)=y.to_i64() {
+			assert_eq!(<PseudoDouble as From<i64>>::from(j),y);
+		}
+	}
+	assert_eq!(Float::integer_decode(PseudoDouble::pdc2(-1,-32760)),(256,i16::MIN,-1));
+	assert_eq!(<PseudoDouble as Cast>::from(f64::NAN),None);
+	assert_eq!(<PseudoDouble as Cast>::from(u64::MAX),Some(PseudoDouble::pdc2(1,64)));
+	assert_eq!(<PseudoDouble as Cast>::from(0.1f32),Some(PseudoDouble::from_f32(0.1)));
+	assert_eq!(pd!(-1.5).to_i64(),Some(-1));
+	assert_eq!(PseudoDouble::pdc2(1,63).to_i64(),None);
+	assert_eq!(PseudoDouble::pdc2(-1,63).to_i64(),Some(i64::MIN));
+	assert_eq!(pd!(-0.5).to_u64(),Some(0));
+	assert_eq!(pd!(-1).to_u64(),None);
+	assert_eq!(PseudoDouble::MAX.to_f64(),Some(f64::INFINITY));
+	assert_eq!(<f64 as Cast>::from(pd!(2.5)),Some(2.5));
+}
+
+#[test]
+fn num_traits_tests() {
+	use num_traits::{ToPrimitive,FromPrimitive,AsPrimitive,Pow,Euclid,CheckedAdd,CheckedMul,CheckedDiv,MulAdd,Inv,FloatConst};
+	assert_eq!(pd!(1e30).to_i128(),Some(1000000000000003116109368655872));
+	assert_eq!(pd!(-2.5e20).to_i128(),Some(-249999999999999475712));
+	assert_eq!(PseudoDouble::pdc2(-1,127).to_i128(),Some(i128::MIN));
+	assert_eq!(PseudoDouble::pdc2(1,127).to_i128(),None);
+	assert_eq!(pd!(3e38).to_u128(),Some(299999999999999634346576772275065847808));
+	assert_eq!(pd!(-7.9).to_i32(),Some(-7));
+	assert_eq!(pd!(300).to_u8(),None);
+	assert_eq!(pd!(0.1).to_f32(),Some(0.1f32));
+	assert_eq!(PseudoDouble::from_f64(0.1),Some(pd!(0.1)));
+	assert_eq!(PseudoDouble::from_f64(f64::INFINITY),None);
+	assert_eq!(PseudoDouble::from_i128(-(1i128<<100)-1),Some(PseudoDouble::pdc2(-1,100)));
+	assert_eq!(PseudoDouble::from_u128(u128::MAX),Some(PseudoDouble::pdc2(1,128)));
+	assert_eq!(PseudoDouble::from_i32(-12),Some(pd!(-12)));
+	let x:i8=pd!(-1000.5).as_();
+	assert_eq!(x,i8::MIN);
+	let x:u32=pd!(-3).as_();
+	assert_eq!(x,0);
+	let x:i64=pd!(-3.99).as_();
+	assert_eq!(x,-3);
+	let x:f64=pd!(1.25).as_();
+	assert_eq!(x,1.25);
+	let x:PseudoDouble=u64::MAX.as_();
+	assert_eq!(x,PseudoDouble::pdc2(1,64));
+	let x:PseudoDouble=f64::NAN.as_();
+	assert_eq!(x,pd!(0));
+	let x:PseudoDouble=(-0.75f32).as_();
+	assert_eq!(x,pd!(-0.75));
+	assert_eq!(pd!(1.5).pow(-2),pd!(1.5).powi(-2));
+	assert_eq!(pd!(4).pow(pd!(0.5)),pd!(2));
+	assert_eq!(Euclid::div_euclid(&pd!(-7),&pd!(2)),pd!(-4));
+	assert_eq!(Euclid::rem_euclid(&pd!(-7),&pd!(2)),pd!(1));
+	assert_eq!(Euclid::div_euclid(&pd!(7),&pd!(-2)),pd!(-3));
+	assert_eq!(Euclid::rem_euclid(&pd!(-7),&pd!(-2)),pd!(1));
+	assert_eq!(CheckedAdd::checked_add(&pd!(1),&pd!(2)),Some(pd!(3)));
+	assert_eq!(PseudoDouble::MAX.checked_add(PseudoDouble::MAX),None);
+	assert_eq!(PseudoDouble::MIN.checked_sub(PseudoDouble::MAX),None);
+	assert_eq!(PseudoDouble::MAX.checked_sub(PseudoDouble::MAX),Some(pd!(0)));
+	assert_eq!(pd!(0).checked_sub(PseudoDouble::MAX),Some(-PseudoDouble::MAX));
+	assert_eq!(CheckedMul::checked_mul(&PseudoDouble::MAX,&pd!(2)),None);
+	assert_eq!(PseudoDouble::MIN_POSITIVE.checked_mul(pd!(0.5)),Some(pd!(0)));
+	assert_eq!(CheckedDiv::checked_div(&pd!(1),&pd!(0)),None);
+	assert_eq!(PseudoDouble::MAX.checked_div(pd!(0.5)),None);
+	let mut rng=StdRng::seed_from_u64(237);
+	for _ in 0..1000 {
+		let a=PseudoDouble::pdc2(rng.gen_range(-(1i64<<47)..(1i64<<47)),rng.gen_range(-100..100));
+		let b=PseudoDouble::pdc2(rng.gen_range(-(1i64<<47)..(1i64<<47)),rng.gen_range(-100..100));
+		assert_eq!(a.checked_add(b),Some(a+b));
+		assert_eq!(a.checked_sub(b),Some(a-b));
+		assert_eq!(a.checked_mul(b),Some(a*b));
+		if b!=pd!(0) {
+			assert_eq!(a.checked_div(b),Some(a/b));
+		}
+		assert_eq!(MulAdd::mul_add(a,b,a),a*b+a);
+	}
+	assert_eq!(pd!(4).inv(),pd!(0.25));
+	assert_eq!(PseudoDouble::PI(),consts::PI);
+	assert_eq!(PseudoDouble::LOG2_10(),consts::LOG2_10);
+}
+
+#[test]
+#[allow(clippy::op_ref)]
+fn operator_tests() {
+	let v=vec![pd!(1.5),pd!(2),pd!(-0.25),pd!(4)];
+	assert_eq!(v.iter().sum::<PseudoDouble>(),pd!(7.25));
+	assert_eq!(v.iter().copied().sum::<PseudoDouble>(),pd!(7.25));
+	assert_eq!(v.iter().product::<PseudoDouble>(),pd!(-3));
+	assert_eq!(v.into_iter().product::<PseudoDouble>(),pd!(-3));
+	assert_eq!(Vec::<PseudoDouble>::new().iter().sum::<PseudoDouble>(),pd!(0));
+	assert_eq!(Vec::<PseudoDouble>::new().iter().product::<PseudoDouble>(),pd!(1));
+	let a=pd!(7.5);
+	let b=pd!(2);
+	assert_eq!(&a+&b,pd!(9.5));
+	assert_eq!(a-&b,pd!(5.5));
+	assert_eq!(&a*b,pd!(15));
+	assert_eq!(&a/&b,pd!(3.75));
+	assert_eq!(&a%&b,pd!(1.5));
+	assert_eq!(-&a,pd!(-7.5));
+	let mut c=a;
+	c+=&b;
+	c*=&b;
+	c-=&b;
+	c/=&b;
+	c%=&b;
+	assert_eq!(c,pd!(0.5));
+	assert_eq!(a*2,pd!(15));
+	assert_eq!(2*a,pd!(15));
+	assert_eq!(a+1i64,pd!(8.5));
+	assert_eq!(10u32-a,pd!(2.5));
+	assert_eq!(a/-3,pd!(-2.5));
+	assert_eq!(a%2,pd!(1.5));
+	let mut d=a;
+	d*=4u32;
+	d-=-2;
+	d/=4i64;
+	assert_eq!(d,pd!(8));
+	assert_eq!(pd!(1)*i32::MIN,PseudoDouble::pdc2(-1,31));
+	let mut rng=StdRng::seed_from_u64(238);
+	for _ in 0..1000 {
+		let x=PseudoDouble::pdc2(rng.gen_range(-(1i64<<47)..(1i64<<47)),rng.gen_range(-60..60));
+		let n=rng.gen::<i64>()>>rng.gen_range(0..63);
+		let (n32,u32)=(n as i32,n as u32);
+		let (pn,pn32,pu32)=(PseudoDouble::from(n),PseudoDouble::from(n32),PseudoDouble::from(u32));
+		assert_eq!(x+n,x+pn);
+		assert_eq!(n-x,pn-x);
+		assert_eq!(x*n32,x*pn32);
+		assert_eq!(u32+x,pu32+x);
+		let mut y=x;
+		y-=n;
+		assert_eq!(y,x-pn);
+		if n32!=0 {
+			assert_eq!(x/n32,x/pn32);
+			assert_eq!(x%n32,x%pn32);
+		}
+		if x!=pd!(0) {
+			assert_eq!(n/x,pn/x);
+			assert_eq!(u32%x,pu32%x);
+		}
+	}
+}
+
+fn round_half_even_div(x:i128, y:i128) -> i128 {
+	let q=x.div_euclid(y);
+	let r=x.rem_euclid(y);
+	if 2*r>y.abs() || (2*r==y.abs() && q%2!=0) {
+		return q+y.signum();
+	}
+	q
+}
+
+#[test]
+fn remainder_tests() {
+	use num_traits::FromPrimitive;
+	let exact=|v:i128,e:i32| PseudoDouble::from_i128(v).unwrap().ldexp(e);
+	let mut rng=StdRng::seed_from_u64(239);
+	for i in 0..20000 {
+		let bits=if i%2==0 {47} else {rng.gen_range(1..48)};
+		let a=rng.gen_range(-(1i64<<bits)..(1i64<<bits));
+		let b=rng.gen_range(-(1i64<<bits)..(1i64<<bits));
+		if b==0 {
+			continue;
+		}
+		let ea=rng.gen_range(-1000..1000);
+		let eb=ea+rng.gen_range(-70..70);
+		let m=ea.min(eb);
+		let (x,y)=(PseudoDouble::pdc2(a,ea),PseudoDouble::pdc2(b,eb));
+		let (xi,yi)=((a as i128)<<(ea-m),(b as i128)<<(eb-m));
+		assert_eq!(x.fmod(y),exact(xi%yi,m),"fmod {} {}",x,y);
+		assert_eq!(x%y,exact(xi%yi,m));
+		assert_eq!(x.rem_euclid(y),exact(xi.rem_euclid(yi),m),"rem_euclid {} {}",x,y);
+		assert_eq!(x.div_euclid(y),exact(xi.div_euclid(yi),0),"div_euclid {} {}",x,y);
+		let n=round_half_even_div(xi,yi);
+		assert_eq!(x.remainder(y),exact(xi-n*yi,m),"remainder {} {}",x,y);
+	}
+	for _ in 0..20000 {
+		// quotients of 60 to 80 bits
+		let a=rng.gen_range(-(1i64<<47)..(1i64<<47));
+		let b=rng.gen_range(1i64<<46..1i64<<47)*if rng.gen::<bool>() {1} else {-1};
+		let d=rng.gen_range(60..80);
+		let (x,y)=(PseudoDouble::pdc2(a,d),PseudoDouble::pdc2(b,0));
+		let (xi,yi)=((a as i128)<<d,b as i128);
+		assert_eq!(x.div_euclid(y),exact(xi.div_euclid(yi),0),"div_euclid {} {}",x,y);
+		assert_eq!(x.rem_euclid(y),exact(xi.rem_euclid(yi),0),"rem_euclid {} {}",x,y);
+	}
+	assert_eq!(PseudoDouble::pdc2(1,1000).fmod(pd!(3)),pd!(1));
+	assert_eq!(PseudoDouble::pdc2(1,1001).fmod(pd!(3)),pd!(2));
+	assert_eq!(PseudoDouble::pdc2(1,1001).remainder(pd!(3)),pd!(-1));
+	assert_eq!(PseudoDouble::pdc2(-1,5000).fmod(pd!(10)),pd!(-6));
+	assert_eq!(PseudoDouble::pdc2(-1,5000).rem_euclid(pd!(10)),pd!(4));
+	assert_eq!(pd!(-1e-300).rem_euclid(pd!(1)),pd!(1));
+	assert_eq!(pd!(5).remainder(pd!(2)),pd!(1));
+	assert_eq!(pd!(7).remainder(pd!(2)),pd!(-1));
+	assert_eq!(pd!(1).remainder(pd!(2)),pd!(1));
+	assert_eq!(pd!(1.5).remainder(pd!(2)),pd!(-0.5));
+	assert_eq!(pd!(-7.25).modf(),(pd!(-7),pd!(-0.25)));
+	assert_eq!(pd!(0.75).modf(),(pd!(0),pd!(0.75)));
+	assert_eq!(PseudoDouble::pdc2(3,200).modf(),(PseudoDouble::pdc2(3,200),pd!(0)));
+	let angle=pd!(1e6);
+	let wrapped=angle.rem_euclid(consts::TAU);
+	assert!(wrapped>=pd!(0) && wrapped<consts::TAU);
+	assert_eq!(angle.div_euclid(consts::TAU)*consts::TAU+wrapped,angle);
+}
+
+// error in units of the last place of the PseudoDouble result
+// an f64 reference is good to 1/64 of a PseudoDouble ulp, so a bound of 0.52 checks correct rounding
+fn ulp_error(x:PseudoDouble, f:f64) -> f64 {
+	let ulp=2f64.powi(f.abs().log2().floor() as i32-46);
+	(f64::from(x)-f).abs()/ulp
+}
+
+// relative error within an ulp, for references given to more digits than a PseudoDouble holds
+fn rel_near(x:PseudoDouble, y:PseudoDouble) -> bool {
+	f64::from((x-y)/y).abs()<ldexp(1.0,-46)
+}
+
+#[test]
+fn exp_m1_ln_1p_tests() {
+	let mut rng=StdRng::seed_from_u64(240);
+	for _ in 0..100000 {
+		// near zero both are correctly rounded apart from the last bit or so
+		let f=ldexp(rng.gen_range(-1.0..1.0),rng.gen_range(-60..0));
+		let x=PseudoDouble::from_f32(f as f32);
+		let xf=f64::from(x);
+		if xf==0.0 {
+			continue;
+		}
+		assert!(ulp_error(x.exp_m1(),xf.exp_m1())<1.0,"exp_m1({})",xf);
+		if xf>=-0.25 {
+			assert!(ulp_error(x.ln_1p(),xf.ln_1p())<1.0,"ln_1p({})",xf);
+		}
+	}
+	for _ in 0..10000 {
+		// further out they use the range reduced exp and log kernels
+		let xf=f64::from(PseudoDouble::from_f32(rng.gen_range(-0.99..4.0)));
+		let x=PseudoDouble::from_f32(xf as f32);
+		assert!(ulp_error(x.exp_m1(),xf.exp_m1())<=2.0,"exp_m1({})",xf);
+		assert!(ulp_error(x.ln_1p(),xf.ln_1p())<=2.0,"ln_1p({})",xf);
+		let xf=f64::from(PseudoDouble::from_f32(rng.gen_range(-80.0..700.0)));
+		let x=PseudoDouble::from_f32(xf as f32);
+		assert!(ulp_error(x.exp_m1(),xf.exp_m1())<=2.0,"exp_m1({})",xf);
+		let xf=f64::from(PseudoDouble::from_f32(rng.gen_range(-1.0..1e30)));
+		let x=PseudoDouble::from_f32(xf as f32);
+		assert!(ulp_error(x.ln_1p(),xf.ln_1p())<=2.0,"ln_1p({})",xf);
+	}
+	assert_eq!(pd!(0).exp_m1(),pd!(0));
+	assert_eq!(pd!(0).ln_1p(),pd!(0));
+	assert_eq!(PseudoDouble::pdc2(3,-200).exp_m1(),PseudoDouble::pdc2(3,-200));
+	assert_eq!(PseudoDouble::pdc2(-3,-200).ln_1p(),PseudoDouble::pdc2(-3,-200));
+	assert_eq!(pd!(-100).exp_m1(),pd!(-1));
+	assert_eq!((-PseudoDouble::MAX).exp_m1(),pd!(-1));
+	assert_eq!(pd!(-0.5).exp_m1(),pd!(-0.39346934028736657639620046500881632759));
+	assert_eq!(pd!(0.5).exp_m1(),pd!(0.64872127070012814684865078831404079202));
+	assert!((f64::from(pd!(10).exp_m1())/10f64.exp_m1()-1.0).abs()<1e-12);
+	assert!((f64::from(pd!(1e6).ln_1p())/1e6f64.ln_1p()-1.0).abs()<1e-12);
+	assert_eq!(pd!(-0.75).ln_1p(),-consts::LN_2.ldexp(1));
+	assert_eq!(PseudoDouble::pdc2(1,100).ln_1p(),pd!(69.31471805599453094172321214581765680755));
+}
+
+// tan of x turns in f64, reduced exactly so the poles do not amplify the error
+fn tan_rev_f64(x:f64) -> f64 {
+	let r=x-(2.0*x).round()/2.0;
+	if r.abs()>0.125 {
+		return r.signum()/(std::f64::consts::TAU*(0.25-r.abs())).tan();
+	}
+	(std::f64::consts::TAU*r).tan()
+}
+
+#[test]
+fn tan_tests() {
+	let mut rng=StdRng::seed_from_u64(241);
+	for _ in 0..100000 {
+		let x=PseudoDouble::from_f32(rng.gen_range(-4.0..4.0));
+		let xf=f64::from(x);
+		if (4.0*xf).fract()==0.0 {
+			continue;
+		}
+		assert!(ulp_error(x.tan_rev(),tan_rev_f64(xf))<1.0,"tan_rev({})",xf);
+	}
+	for _ in 0..10000 {
+		// tiny arguments keep full precision
+		let x=PseudoDouble::from_f32(ldexp(rng.gen_range(-1.0..1.0),rng.gen_range(-100..-3)) as f32);
+		let xf=f64::from(x);
+		if xf==0.0 {
+			continue;
+		}
+		assert!(ulp_error(x.tan_rev(),tan_rev_f64(xf))<1.0,"tan_rev({})",xf);
+		// close to a pole
+		let y=pd!(0.25)-x;
+		if y==pd!(0.25) {
+			continue;
+		}
+		assert!(ulp_error(y.tan_rev(),tan_rev_f64(f64::from(y)))<1.0,"tan_rev({})",f64::from(y));
+	}
+	for _ in 0..10000 {
+		let x=PseudoDouble::from_f32(rng.gen_range(-1.2..1.2));
+		let xf=f64::from(x);
+		assert!(ulp_error(x.tan(),xf.tan())<1.0,"tan({})",xf);
+		// large arguments are reduced with enough bits of 1/(2*pi)
+		let y=PseudoDouble::pdc2(rng.gen_range(-(1i64<<47)..(1i64<<47)),rng.gen_range(-47..60));
+		let yf=f64::from(y);
+		assert!(ulp_error(y.tan(),yf.tan())<1.0,"tan({})",yf);
+		let z=PseudoDouble::from_f32(ldexp(rng.gen_range(-1.0..1.0),rng.gen_range(-100..-1)) as f32);
+		let zf=f64::from(z);
+		if zf!=0.0 {
+			assert!(ulp_error(z.tan(),zf.tan())<1.0,"tan({})",zf);
+		}
+	}
+	assert_eq!(pd!(0).tan(),pd!(0));
+	assert_eq!(PseudoDouble::pdc2(3,-30000).tan(),PseudoDouble::pdc2(3,-30000));
+	assert_eq!(pd!(0).tan_rev(),pd!(0));
+	assert_eq!(pd!(0.125).tan_rev(),pd!(1));
+	assert_eq!(pd!(-0.375).tan_rev(),pd!(1));
+	assert_eq!(pd!(1000.5).tan_rev(),pd!(0));
+	assert_eq!(PseudoDouble::pdc2(3,-200).tan_rev(),PseudoDouble::pdc2(3,-200)*consts::TAU);
+	if cfg!(feature="panic_on_pseudodouble_overflow") {
+		assert!(std::panic::catch_unwind(|| pd!(0.25).tan_rev()).is_err());
+		assert!(std::panic::catch_unwind(|| pd!(-12.75).tan_rev()).is_err());
+	} else {
+		assert_eq!(pd!(0.25).tan_rev(),PseudoDouble::MAX);
+		assert_eq!(pd!(-12.75).tan_rev(),PseudoDouble::MIN);
+	}
+}
+
+#[test]
+fn inverse_trig_rev_tests() {
+	let tau=std::f64::consts::TAU;
+	let mut rng=StdRng::seed_from_u64(242);
+	for _ in 0..100000 {
+		let x=PseudoDouble::from_f32(rng.gen_range(-1.0..1.0));
+		let xf=f64::from(x);
+		if xf==0.0 {
+			continue;
+		}
+		assert!(ulp_error(x.asin_rev(),xf.asin()/tau)<1.0,"asin_rev({})",xf);
+		assert!(ulp_error(x.acos_rev(),xf.acos()/tau)<1.0,"acos_rev({})",xf);
+		assert!(ulp_error(x.atan_rev(),xf.atan()/tau)<1.0,"atan_rev({})",xf);
+		let w=PseudoDouble::from_f32(rng.gen_range(-1e6..1e6));
+		assert!(ulp_error(w.atan_rev(),f64::from(w).atan()/tau)<1.0,"atan_rev({})",f64::from(w));
+		// tiny arguments keep full precision
+		let z=PseudoDouble::from_f32(rng.gen_range(0.5..1.0)).ldexp(rng.gen_range(-900..-1));
+		let zf=f64::from(z);
+		assert!(ulp_error(z.asin_rev(),zf.asin()/tau)<1.0,"asin_rev({})",zf);
+		assert!(ulp_error(z.atan_rev(),zf.atan()/tau)<1.0,"atan_rev({})",zf);
+		// near the endpoints, where 1-x*x would cancel
+		let y=pd!(1)-PseudoDouble::from_f32(rng.gen_range(0.5..1.0)).ldexp(rng.gen_range(-46..-1));
+		let yf=f64::from(y);
+		if yf==1.0 {
+			continue;
+		}
+		assert!(ulp_error(y.asin_rev(),yf.asin()/tau)<1.0,"asin_rev({})",yf);
+		assert!(ulp_error(y.acos_rev(),yf.acos()/tau)<1.0,"acos_rev({})",yf);
+		assert!(ulp_error((-y).acos_rev(),(-yf).acos()/tau)<1.0,"acos_rev({})",-yf);
+	}
+	for _ in 0..10000 {
+		// radians are rounded once from the fixed point result
+		let x=PseudoDouble::from_f32(rng.gen_range(-1.0..1.0));
+		let xf=f64::from(x);
+		assert!(ulp_error(x.asin(),xf.asin())<1.0,"asin({})",xf);
+		assert!(ulp_error(x.acos(),xf.acos())<1.0,"acos({})",xf);
+		assert!(ulp_error(x.atan(),xf.atan())<1.0,"atan({})",xf);
+		let y=PseudoDouble::from_f32(rng.gen_range(-1.0..1.0)).ldexp(rng.gen_range(-60..60));
+		let yf=f64::from(y);
+		assert!(ulp_error(y.atan2_rev(x),yf.atan2(xf)/tau)<1.0,"atan2_rev({},{})",yf,xf);
+		assert!(ulp_error(y.atan2(x),yf.atan2(xf))<1.0,"atan2({},{})",yf,xf);
+		assert_eq!(y.atan_rev(),y.atan2_rev(pd!(1)));
+		assert_eq!(y.atan(),y.atan2(pd!(1)));
+	}
+	assert_eq!(PseudoDouble::MIN_POSITIVE.asin(),PseudoDouble::MIN_POSITIVE);
+	assert_eq!(PseudoDouble::pdc2(-1,PseudoDouble::MIN_EXP).atan(),PseudoDouble::pdc2(-1,PseudoDouble::MIN_EXP));
+	assert_eq!(pd!(1).asin_rev(),pd!(0.25));
+	assert_eq!(pd!(-1).asin_rev(),pd!(-0.25));
+	assert_eq!(pd!(0).asin_rev(),pd!(0));
+	assert_eq!(pd!(1).acos_rev(),pd!(0));
+	assert_eq!(pd!(-1).acos_rev(),pd!(0.5));
+	assert_eq!(pd!(0).acos_rev(),pd!(0.25));
+	assert_eq!(pd!(1).atan_rev(),pd!(0.125));
+	assert_eq!(pd!(-1).atan_rev(),pd!(-0.125));
+	assert_eq!(PseudoDouble::pdc2(1,1000).atan_rev(),pd!(0.25));
+	assert_eq!(PseudoDouble::pdc2(-3,-1000).atan_rev(),-PseudoDouble::pdc2(3,-1000).atan_rev());
+	assert!(std::panic::catch_unwind(|| pd!(1.0000001).asin_rev()).is_err());
+	assert!(std::panic::catch_unwind(|| pd!(-1.0000001).acos_rev()).is_err());
+}
+
+// x, asin_rev, acos_rev, atan_rev, asin, acos, atan
+// the same vectors are checked against the C library in PseudoDouble_test.cpp
+const INVERSE_TRIG_VECTORS:[(u64,[u64;6]);8]=[
+	(0x4CCCCCCCCCCD8000u64,[0x63507A3010B17FFDu64,0x672BE173FBD47FFFu64,0x5F000980497F7FFDu64,0x4E00567853DC8000u64,0x5107D7B2FBBD8002u64,0x4A9CE9987ABC8000u64]),
+	(0xA666666666668001u64,[0x81A13E3231257FFEu64,0x5F97B07373B78000u64,0x9C7796A767067FFEu64,0x9CBFC65B54098001u64,0x4B140511B3588003u64,0xB1D3BB4A10AD8001u64]),
+	(0x7FDF3B645A1D8001u64,[0x7C5B0036EDC17FFFu64,0x749FF92247EB7FFAu64,0x7FEB20F9A7DD7FFEu64,0x61AB26B463E48002u64,0x5B98D3959A157FFDu64,0x647788EA30FF8001u64]),
+	(0x9C35E642BFBD7F9Eu64,[0x80F1B1CF937A7F9Bu64,0x4000000000008000u64,0x80F1B1CF937A7F9Bu64,0x9C35E642BFBD7F9Eu64,0x6487ED5110B48002u64,0x9C35E642BFBD7F9Eu64]),
+	(0x4000000000008001u64,[0x5555555555557FFEu64,0x5555555555557FFFu64,0x4B90147677CC7FFEu64,0x430548E0B5CE8001u64,0x430548E0B5CE8002u64,0x76B19C1586ED8000u64]),
+	(0x8000000000008001u64,[0x8000000000007FFFu64,0x4000000000008001u64,0x8000000000007FFEu64,0x9B7812AEEF4C8002u64,0x6487ED5110B48003u64,0x9B7812AEEF4C8001u64]),
+	(0x4000000000000000u64,[0x0000000000000000u64,0x4000000000008000u64,0x0000000000000000u64,0x4000000000000000u64,0x6487ED5110B48002u64,0x4000000000000000u64]),
+	(0x7FFFFFFFFE808001u64,[0x7FFFF3865CA67FFFu64,0x63CD1AD30C307FECu64,0x7FFFFFFFFF0C7FFEu64,0x6487E384C9AA8002u64,0x4E62385024987FEFu64,0x6487ED510FF48001u64]),
+];
+
+// y, x, atan2_rev, atan2
+const ATAN2_VECTORS:[(u64,u64,[u64;2]);6]=[
+	(0x6000000000008003u64,0x8000000000008003u64,[0x65C80A3B3BE68000u64,0x4FF05DADEA158003u64]),
+	(0x920C8098A1097FE0u64,0x8000000000008002u64,[0x8000000008C08000u64,0x9B7812AEF62B8003u64]),
+	(0x5000000000008004u64,0x5E72843249087FBFu64,[0x4000000000008000u64,0x6487ED5110B48002u64]),
+	(0x9000000000008004u64,0x7000000000008004u64,[0x8000000000007FFEu64,0x9B7812AEEF4C8001u64]),
+	(0x830000000000800Bu64,0x4189374BC6A87FF8u64,[0x80000557216C7FFFu64,0x9B7816E0AD348002u64]),
+	(0x4000000000008000u64,0xA000000000008000u64,[0x680AE90E442F8000u64,0x51B7027B0A518003u64]),
+];
+
+#[test]
+fn inverse_trig_vectors() {
+	for (x,r) in INVERSE_TRIG_VECTORS {
+		let x=PseudoDouble(x as i64);
+		let v=[x.asin_rev(),x.acos_rev(),x.atan_rev(),x.asin(),x.acos(),x.atan()];
+		assert_eq!(v.map(|y| y.0 as u64),r,"{:#x}",x.0);
+	}
+	for (y,x,r) in ATAN2_VECTORS {
+		let (y,x)=(PseudoDouble(y as i64),PseudoDouble(x as i64));
+		assert_eq!([y.atan2_rev(x).0 as u64,y.atan2(x).0 as u64],r,"{:#x} {:#x}",y.0,x.0);
+	}
+}
+
+#[test]
+fn hyperbolic_tests() {
+	let mut rng=StdRng::seed_from_u64(243);
+	for _ in 0..100000 {
+		let x=PseudoDouble::from_f32(rng.gen_range(-1.0..1.0)).ldexp(rng.gen_range(-60..10));
+		let xf=f64::from(x);
+		if xf==0.0 {
+			continue;
+		}
+		let a=xf.abs();
+		assert!(ulp_error(x.sinh(),xf.sinh())<1.0,"sinh({})",xf);
+		assert!(ulp_error(x.cosh(),xf.cosh())<1.0,"cosh({})",xf);
+		assert!(ulp_error(x.tanh(),xf.tanh())<1.0,"tanh({})",xf);
+		assert!(ulp_error(x.asinh(),xf.signum()*a.asinh())<1.0,"asinh({})",xf);
+		if a<1.0 {
+			// f64::atanh itself cancels for negative x near -1
+			assert!(ulp_error(x.atanh(),xf.signum()*a.atanh())<1.0,"atanh({})",xf);
+		}
+		if a>=1.0 {
+			let y=if xf<0.0 {-x} else {x};
+			assert!(ulp_error(y.acosh(),a.acosh())<1.0,"acosh({})",a);
+		}
+	}
+	// tiny arguments, down to the bottom of the exponent range
+	for e in [-40,-100,-1000,-32000] {
+		let x=PseudoDouble::pdc2(-3,e);
+		assert_eq!(x.sinh(),x);
+		assert_eq!(x.tanh(),x);
+		assert_eq!(x.asinh(),x);
+		assert_eq!(x.atanh(),x);
+		assert_eq!(x.cosh(),pd!(1));
+	}
+	// large arguments, only overflowing when the result does
+	assert!(rel_near(pd!(20000).sinh(),pd!(3.87800236299343052291602e+8685)));
+	assert!(rel_near(pd!(-22000).cosh(),pd!(1.505122596463808310921624e+9554)));
+	assert!(rel_near(PseudoDouble::pdc2(-1,30000).asinh(),pd!(-20795.10856397891922782638)));
+	assert!(rel_near(pd!(1e9000).acosh(),pd!(20723.95898412697110147134)));
+	assert!(pd!(-22712).sinh()<pd!(0));
+	assert_eq!(pd!(100).tanh(),pd!(1));
+	assert_eq!(pd!(-1e9000).tanh(),pd!(-1));
+	assert_eq!(pd!(0).sinh(),pd!(0));
+	assert_eq!(pd!(0).cosh(),pd!(1));
+	assert_eq!(pd!(1).acosh(),pd!(0));
+	if cfg!(feature="panic_on_pseudodouble_overflow") {
+		assert!(std::panic::catch_unwind(|| pd!(22713).cosh()).is_err());
+		assert!(std::panic::catch_unwind(|| pd!(-1e10).sinh()).is_err());
+	}
+	assert!(std::panic::catch_unwind(|| pd!(0.9999999).acosh()).is_err());
+	assert!(std::panic::catch_unwind(|| pd!(-1).atanh()).is_err());
+}
+
+#[test]
+fn domain_tests() {
+	let one=pd!(1);
+	let above_one=one+PseudoDouble::EPSILON;
+	let below_one=pd!(0.99999999999999);
+	let tiny=PseudoDouble::MIN_POSITIVE;
+	let neg_tiny=PseudoDouble::pdc2(-1,-32000);
+	let err=|function:&'static str| Err(PdDomainError{function});
+	assert_eq!(above_one,pd!(1.00000000000001));
+	assert_eq!(below_one.0,0x7fffffffffff8001); // largest below 1
+	// asin and acos: [-1,1]
+	assert_eq!(one.try_asin(),Ok(pd!(0.25)*consts::TAU));
+	assert_eq!((-one).try_asin(),Ok(pd!(-0.25)*consts::TAU));
+	assert_eq!(above_one.try_asin(),err("asin"));
+	assert_eq!((-above_one).try_asin(),err("asin"));
+	assert_eq!(one.try_acos(),Ok(pd!(0)));
+	assert_eq!((-one).try_acos(),Ok(pd!(-1).acos()));
+	assert_eq!(above_one.try_acos(),err("acos"));
+	assert_eq!((-above_one).try_acos(),err("acos"));
+	// tan_rev: not at the odd multiples of 1/4
+	assert_eq!(pd!(0.125).try_tan_rev(),Ok(pd!(1)));
+	assert_eq!(pd!(0.25).try_tan_rev(),err("tan_rev"));
+	assert_eq!(pd!(-12.75).try_tan_rev(),err("tan_rev"));
+	assert_eq!((pd!(0.25)-PseudoDouble::EPSILON).try_tan_rev(),Ok((pd!(0.25)-PseudoDouble::EPSILON).tan_rev()));
+	assert_eq!(consts::FRAC_PI_2.try_tan(),Ok(consts::FRAC_PI_2.tan()));
+	assert!(consts::FRAC_PI_2.tan()>pd!(1e14));
+	// acosh: [1,inf)
+	assert_eq!(one.try_acosh(),Ok(pd!(0)));
+	assert_eq!(PseudoDouble::MAX.try_acosh(),Ok(PseudoDouble::MAX.acosh()));
+	assert_eq!(below_one.try_acosh(),err("acosh"));
+	assert_eq!(pd!(0).try_acosh(),err("acosh"));
+	assert_eq!((-one).try_acosh(),err("acosh"));
+	// atanh: (-1,1)
+	assert_eq!(one.try_atanh(),err("atanh"));
+	assert_eq!((-one).try_atanh(),err("atanh"));
+	assert_eq!(below_one.try_atanh(),Ok(below_one.atanh()));
+	assert_eq!((-below_one).try_atanh(),Ok(-below_one.atanh()));
+	assert!(below_one.atanh()>pd!(16));
+	// log2: (0,inf)
+	assert_eq!(tiny.try_log2(),Ok(pd!(-32770)));
+	assert_eq!(pd!(0).try_log2(),err("log2"));
+	assert_eq!(neg_tiny.try_log2(),err("log2"));
+	// sqrt: [0,inf)
+	assert_eq!(pd!(0).try_sqrt(),Ok(pd!(0)));
+	assert_eq!(pd!(4).try_sqrt(),Ok(pd!(2)));
+	assert_eq!(neg_tiny.try_sqrt(),err("sqrt"));
+	// powf: negative bases need an integral exponent, zero needs a non-negative one
+	assert_eq!(pd!(2).try_powf(pd!(-0.5)),Ok(pd!(2).powf(pd!(-0.5))));
+	assert_eq!(pd!(0).try_powf(pd!(0.5)),Ok(pd!(0)));
+	assert_eq!(pd!(0).try_powf(pd!(0)),Ok(pd!(1)));
+	assert_eq!(pd!(0).try_powf(neg_tiny),err("powf"));
+	assert_eq!(pd!(-2).try_powf(pd!(3)),Ok(-pd!(2).powf(pd!(3))));
+	assert_eq!(pd!(-2).try_powf(pd!(-2)),Ok(pd!(2).powf(pd!(-2))));
+	assert_eq!(pd!(-1).try_powf(pd!(1e20)),Ok(pd!(1).powf(pd!(1e20))));
+	assert_eq!(pd!(-1).try_powf(pd!(-7)),Ok(pd!(-1)));
+	assert_eq!(pd!(-2).try_powf(pd!(2.5)),err("powf"));
+	assert_eq!(pd!(-2).try_powf(tiny),err("powf"));
+	assert_eq!(PdDomainError{function:"sqrt"}.to_string(),"argument outside the domain of PseudoDouble sqrt");
+}
+
+#[test]
+fn erf_tests() {
+	let mut rng=StdRng::seed_from_u64(245);
+	let two_over_sqrt_pi=std::f64::consts::FRAC_2_SQRT_PI;
+	for _ in 0..100000 {
+		let x=PseudoDouble::from_f32(rng.gen_range(-1.0..1.0)).ldexp(rng.gen_range(-30..5));
+		let xf=f64::from(x);
+		if xf==0.0 {
+			continue;
+		}
+		assert!(ulp_error(x.erf(),libm::erf(xf))<1.0,"erf({})",xf);
+		if xf<26.0 {
+			assert!(ulp_error(x.erfc(),libm::erfc(xf))<1.0,"erfc({})",xf);
+		}
+		if xf>-5.0 {
+			// beyond this the rounding of x/sqrt(2) in f64 is amplified by x^2
+			assert!(ulp_error(x.normal_cdf(),libm::erfc(-xf*std::f64::consts::FRAC_1_SQRT_2)/2.0)<1.0,"normal_cdf({})",xf);
+		}
+		// the inverses against one Newton step in f64 from the result
+		let p=PseudoDouble::from_f32(rng.gen_range(-1.0..1.0));
+		let pf=f64::from(p);
+		if pf!=0.0 {
+			let y=f64::from(p.erf_inv());
+			let yr=if pf.abs()<=0.5 {
+				y-(libm::erf(y)-pf)/(two_over_sqrt_pi*(-y*y).exp())
+			} else {
+				y+pf.signum()*(libm::erfc(y.abs())-(1.0-pf.abs()))/(two_over_sqrt_pi*(-y*y).exp())
+			};
+			assert!(ulp_error(p.erf_inv(),yr)<1.0,"erf_inv({})",pf);
+		}
+		let q=PseudoDouble::from_f32(rng.gen_range(0.0..1.0)).ldexp(rng.gen_range(-900..1));
+		let qf=f64::from(q);
+		if qf!=0.0 && qf<1.0 {
+			let y=f64::from(q.normal_quantile());
+			let s=-y*std::f64::consts::FRAC_1_SQRT_2;
+			let yr=y+(libm::erfc(s)/2.0-qf)/(two_over_sqrt_pi*(-s*s).exp())*2.0f64.sqrt();
+			assert!(ulp_error(q.normal_quantile(),yr)<1.0,"normal_quantile({})",qf);
+		}
+	}
+	// tails keep their relative precision down to the bottom of the exponent range
+	assert!(rel_near(pd!(100).erfc(),pd!(6.405961424921732039021339148586394148214e-4346)));
+	assert!(rel_near(pd!(-150).normal_cdf(),pd!(4.091457980903002399032776796971440461509e-4889)));
+	assert!(rel_near(pd!(1e-9000).normal_quantile(),pd!(-203.5535848124207190399536663485650059209)));
+	assert!(rel_near(pd!(1e-9000).erf_inv(),pd!(8.862269254527580136490837416705725913988e-9001)));
+	assert!(rel_near(PseudoDouble::MIN_POSITIVE.normal_quantile(),pd!(-213.1110148874163788795491296811221545291)));
+	assert_eq!(pd!(300).erfc(),pd!(0));
+	assert_eq!(pd!(-300).erfc(),pd!(2));
+	assert_eq!(pd!(7).erf(),pd!(1));
+	assert_eq!(pd!(-1e100).erf(),pd!(-1));
+	assert_eq!(pd!(0).erf(),pd!(0));
+	assert_eq!(pd!(0).erfc(),pd!(1));
+	assert_eq!(pd!(0).normal_cdf(),pd!(0.5));
+	assert_eq!(pd!(0.5).normal_quantile(),pd!(0));
+	assert_eq!(pd!(0).erf_inv(),pd!(0));
+	for e in [-40,-100,-1000,-32000] {
+		let x=PseudoDouble::pdc2(3,e);
+		assert!(rel_near(x.erf(),x*consts::FRAC_2_SQRT_PI));
+		assert!(rel_near(x.erf().erf_inv(),x));
+	}
+	assert_eq!(pd!(1).try_erf_inv(),Err(PdDomainError{function:"erf_inv"}));
+	assert_eq!(pd!(0).try_normal_quantile(),Err(PdDomainError{function:"normal_quantile"}));
+	assert_eq!(pd!(0.3).try_normal_quantile(),Ok(pd!(0.3).normal_quantile()));
+	assert!(std::panic::catch_unwind(|| pd!(-1).erf_inv()).is_err());
+	assert!(std::panic::catch_unwind(|| pd!(1).normal_quantile()).is_err());
+}
+
+// x, erf, erfc, normal_cdf
+// the same vectors are checked against the C library in PseudoDouble_test.cpp
+const ERF_VECTORS:[(u64,[u64;3]);8]=[
+	(0x4CCCCCCCCCCD8000u64,[0x5420E22077A88000u64,0x55EF8EEFC42C8001u64,0x4F17B8B3102E8001u64]),
+	(0xA666666666668001u64,[0xA93DCF7BA3A78001u64,0x6B6118422E2D8002u64,0x7BE2A8EA3CD57FFFu64]),
+	(0x5000000000008003u64,[0x7FF2AA3D32708001u64,0x6AAE166C82B27FF6u64,0x7F34858D40DB8001u64]),
+	(0xB000000000008004u64,[0x8000000000D88001u64,0x7FFFFFFFFF948002u64,0x4CF28BC84CE17FECu64]),
+	(0x6C00000000008006u64,[0x4000000000008002u64,0x6783C337E0EA7BE0u64,0x4000000000008002u64]),
+	(0x5E72843249087FBFu64,[0x6A9288C0912B7FBFu64,0x4000000000008002u64,0x4000000000008001u64]),
+	(0xB50000000000800Au64,[0x8000000000008001u64,0x4000000000008003u64,0x0000000000000000u64]),
+	(0x4666666666668001u64,[0x481AFAE5B8308001u64,0x6FCA0A348FA08000u64,0x5ABB4785AA4C8001u64]),
+];
+
+// p, erf_inv, normal_quantile
+const ERF_INV_VECTORS:[(u64,[u64;2]);8]=[
+	(0x4CCCCCCCCCCD8000u64,[0x45C01DD1217B8000u64,0xBCE071A9F4E88001u64]),
+	(0x4CCCCCCCCCCD8001u64,[0x4C2CC38593618001u64,0x40DB5B12812E8000u64]),
+	(0x7333333333338001u64,[0x4A700519DC198002u64,0x5204F0DB89B48002u64]),
+	(0x7FFFF79C84308001u64,[0x6EAF65923D0B8003u64,0x4C0E06A6098E8004u64]),
+	(0x51EB851EB8527FFBu64,[0x489A032532B77FFBu64,0xB58E8EE73C048003u64]),
+	(0x465E6604B7A87FDAu64,[0x7CB9B0A47CC37FD9u64,0x8F72C116A1738004u64]),
+	(0x55B87F0BE3CD7C1Du64,[0x4BF7CDAFDA6E7C1Du64,0xB5E7E2FE6CF68007u64]),
+	(0x6000000000008001u64,[0x681E243DACAF8001u64,0x5655AE1D4A298001u64]),
+];
+
+#[test]
+fn erf_vectors() {
+	for (x,r) in ERF_VECTORS {
+		let x=PseudoDouble(x as i64);
+		assert_eq!([x.erf(),x.erfc(),x.normal_cdf()].map(|y| y.0 as u64),r,"{:#x}",x.0);
+	}
+	for (p,r) in ERF_INV_VECTORS {
+		let p=PseudoDouble(p as i64);
+		assert_eq!([p.erf_inv(),p.normal_quantile()].map(|y| y.0 as u64),r,"{:#x}",p.0);
+	}
+}
+
+#[test]
+fn gamma_tests() {
+	let mut rng=StdRng::seed_from_u64(246);
+	for _ in 0..20000 {
+		let x=PseudoDouble::from_f32(rng.gen_range(-30.0..170.0));
+		let xf=f64::from(x);
+		if xf<=0.0 && xf.floor()==xf {
+			continue;
+		}
+		assert!(ulp_error(x.gamma(),libm::tgamma(xf))<1.0,"gamma({})",xf);
+		let lg=libm::lgamma(xf);
+		if xf>0.0 || lg.abs()>0.5 {
+			// the negative zeros of lgamma are only good to an absolute error
+			assert!(ulp_error(x.lgamma(),lg)<1.0,"lgamma({})",xf);
+		}
+		// ψ(x+1)=ψ(x)+1/x
+		if xf>0.5 {
+			let d=f64::from((x+pd!(1)).digamma()-x.digamma()-pd!(1)/x);
+			assert!(d.abs()<ldexp(1.0,-42),"digamma({})",xf);
+		}
+		let a=PseudoDouble::from_f32(rng.gen_range(0.0..60.0));
+		let b=PseudoDouble::from_f32(rng.gen_range(0.0..60.0));
+		let (af,bf)=(f64::from(a),f64::from(b));
+		if af>0.0 && bf>0.0 {
+			let r=f64::from(a.beta(b)*(a+b).gamma()/a.gamma()/b.gamma());
+			assert!((r-1.0).abs()<ldexp(1.0,-43),"beta({},{})",af,bf);
+		}
+	}
+	assert!(rel_near(pd!(0.5).digamma(),pd!(-1.963510026021423479440976332998755567193)));
+	assert!(rel_near(pd!(-0.5).digamma(),pd!(0.03648997397857652055902366700124443280684)));
+	assert!(rel_near(pd!(-2.25).digamma(),pd!(4.158583564657972274817557681627263751274)));
+	assert!(rel_near(pd!(1e-9000).digamma(),pd!(-1e9000)));
+	assert!(rel_near(pd!(1e9000).digamma(),pd!(20723.26583694641115616192309215927786841)));
+	assert!(rel_near(pd!(1e9000).lgamma(),pd!(2.072226583694641115616192309215927786841e9004)));
+	assert!(rel_near(pd!(-1e-20).lgamma(),pd!(46.05170185988091368036560125033629948063)));
+	assert!(rel_near(PseudoDouble::MIN_POSITIVE.lgamma(),pd!(22714.43310694940778960269662018444613583)));
+	assert!(rel_near(pd!(1e6).beta(pd!(3.5)),pd!(3.323336430833691878505609439895305317696e-21)));
+	assert!(rel_near(pd!(1e-20).beta(pd!(5)),pd!(99999999999999999997.9166666666666666667)));
+	assert!(rel_near(pd!(300).beta(pd!(400)),pd!(4.720116108831210718063563562675245459327e-209)));
+	assert!(rel_near(pd!(-2.5).gamma(),pd!(-0.9453087204829418812256893244486107641587)));
+	assert!(rel_near(pd!(1000.25).gamma(),pd!(2.262577706439994073632529822263798299636e2565)));
+	assert!(rel_near(pd!(-1000.25).gamma(),pd!(-1.963146937403278107341966514204439514531e-2568)));
+	assert_eq!(pd!(1).lgamma(),pd!(0));
+	assert_eq!(pd!(2).lgamma(),pd!(0));
+	assert_eq!(pd!(5).gamma(),pd!(24));
+	assert_eq!(pd!(171).gamma(),PseudoDouble::factorial(170));
+	assert_eq!(pd!(-4000.5).gamma(),pd!(0));
+	assert_eq!(PseudoDouble::factorial(0),pd!(1));
+	assert_eq!(PseudoDouble::factorial(20),pd!(2432902008176640000));
+	assert_eq!(PseudoDouble::factorial(170),pd!(7.25741561530799896739672821112926311471699168e306));
+	assert_eq!(PseudoDouble::binomial(100,50),pd!(100891344545564193334812497256));
+	assert_eq!(PseudoDouble::binomial(10,3),pd!(120));
+	assert_eq!(PseudoDouble::binomial(5,7),pd!(0));
+	assert_eq!(PseudoDouble::binomial(u64::MAX,u64::MAX-1),pd!(18446744073709551615));
+	if cfg!(feature="panic_on_pseudodouble_overflow") {
+		assert!(std::panic::catch_unwind(|| PseudoDouble::factorial(3210)).is_err());
+		assert!(std::panic::catch_unwind(|| pd!(-3).gamma()).is_err());
+		assert!(std::panic::catch_unwind(|| pd!(0).lgamma()).is_err());
+		assert!(std::panic::catch_unwind(|| pd!(-1).digamma()).is_err());
+	} else {
+		assert_eq!(PseudoDouble::factorial(3210),PseudoDouble::MAX);
+		assert_eq!(pd!(-3).gamma(),PseudoDouble::MAX);
+		assert_eq!(pd!(0).lgamma(),PseudoDouble::MAX);
+		assert_eq!(pd!(-1).digamma(),PseudoDouble::MAX);
+	}
+	assert!(std::panic::catch_unwind(|| pd!(0).beta(pd!(1))).is_err());
+}
+
+#[test]
+fn bessel_tests() {
+	let mut rng=StdRng::seed_from_u64(247);
+	// error relative to the result, or to the amplitude sqrt(2/(pi*x)) in the oscillating region
+	let bessel_error=|r:PseudoDouble,yf:f64,xf:f64,n:i32| {
+		let amp=if xf.abs()>(n.abs() as f64).max(1.0) {(2.0/(std::f64::consts::PI*xf.abs())).sqrt()} else {0.0};
+		(f64::from(r)-yf).abs()/yf.abs().max(amp)
+	};
+	for _ in 0..20000 {
+		let x=PseudoDouble::from_f32(rng.gen_range(-60.0..200.0));
+		let xf=f64::from(x);
+		assert!(bessel_error(x.j0(),libm::j0(xf),xf,0)<ldexp(1.0,-45),"j0({})",xf);
+		assert!(bessel_error(x.j1(),libm::j1(xf),xf,1)<ldexp(1.0,-45),"j1({})",xf);
+		let n=rng.gen_range(-20..20);
+		assert!(bessel_error(x.jn(n),libm::jn(n,xf),xf,n)<ldexp(1.0,-44),"jn({},{})",n,xf);
+		if xf>0.0 {
+			assert!(bessel_error(x.y0(),libm::y0(xf),xf,0)<ldexp(1.0,-45),"y0({})",xf);
+			assert!(bessel_error(x.y1(),libm::y1(xf),xf,1)<ldexp(1.0,-45),"y1({})",xf);
+			assert!(bessel_error(x.yn(n),libm::yn(n,xf),xf,n)<ldexp(1.0,-44),"yn({},{})",n,xf);
+		}
+	}
+	assert!(rel_near(pd!(1).j0(),pd!(0.7651976865579665514497175261026632209093)));
+	assert!(rel_near(pd!(1).y0(),pd!(0.08825696421567695798292676602351516282782)));
+	assert!(rel_near(pd!(10).j1(),pd!(0.04347274616886143666974876802585928830627)));
+	assert!(rel_near(pd!(30).y1(),pd!(0.08442557066174723489092290414965591415503)));
+	assert!(rel_near(pd!(-2.5).j1(),pd!(-0.4970941024642740380108162762644222425212)));
+	assert!(rel_near(pd!(2.5).jn(-3),pd!(-0.2166003910391135247666890035159637217168)));
+	assert!(rel_near(pd!(7.5).yn(4),pd!(0.314180295828187723394601627887213079411)));
+	assert!(rel_near(pd!(40).jn(50),pd!(0.0006818524353176831141482653120301150787578)));
+	assert!(rel_near(pd!(1000000).jn(3),pd!(0.0007259670326359003355030493255215093902205)));
+	assert!(rel_near(pd!(0.5).yn(-5),pd!(7946.301478807473341829161714785235335148)));
+	assert!(rel_near(pd!(1).ldexp(-33).jn(5),pd!(5.568259812692074262789639973552105750182e-54)));
+	assert!(rel_near(pd!(1).ldexp(-10).jn(20),pd!(2.439362437852211689507064648130764694642e-85)));
+	assert!(rel_near(pd!(1).ldexp(-300).y0(),pd!(-132.4551643866996432631482031987470976059)));
+	assert!(rel_near(pd!(1).ldexp(-300).y1(),pd!(-1.2968173795586343480516044591885272968e90)));
+	assert!(rel_near(pd!(1).ldexp(100).j0(),pd!(-1.91924167544405227451412386639878143758e-16)));
+	assert!(rel_near(pd!(1).ldexp(180).j0(),pd!(4.440698380287668328285811431788759937919e-28)));
+	assert!(rel_near(pd!(1).ldexp(150).y1(),pd!(-3.315535031047445998222436013744818614277e-24)));
+	// at the double nearest the first zero of J0 the absolute error is far below an ulp of 1
+	let z=pd!(2.404825557695773);
+	assert!(f64::from(z.j0()-pd!(-6.108765259736730397081979074235388478631e-17)).abs()<ldexp(1.0,-76));
+	assert_eq!(pd!(0).j0(),pd!(1));
+	assert_eq!(pd!(0).j1(),pd!(0));
+	assert_eq!(pd!(0).jn(7),pd!(0));
+	assert_eq!(pd!(1).ldexp(-100).jn(1000),pd!(0));
+	if cfg!(feature="panic_on_pseudodouble_overflow") {
+		assert!(std::panic::catch_unwind(|| pd!(0).y0()).is_err());
+		assert!(std::panic::catch_unwind(|| pd!(1e-20).yn(1000)).is_err());
+	} else {
+		assert_eq!(pd!(0).y0(),PseudoDouble::MIN);
+		assert_eq!(pd!(1e-20).yn(1000),PseudoDouble::MIN);
+	}
+	assert!(std::panic::catch_unwind(|| pd!(-1).y1()).is_err());
+}
+
+#[test]
+fn hypot_tests() {
+	let mut rng=StdRng::seed_from_u64(248);
+	for _ in 0..100000 {
+		let x=PseudoDouble::pdc2(rng.gen_range(-(1i64<<47)..(1i64<<47)),rng.gen_range(-60..0));
+		let y=PseudoDouble::pdc2(rng.gen_range(-(1i64<<47)..(1i64<<47)),rng.gen_range(-60..0));
+		let z=PseudoDouble::pdc2(rng.gen_range(-(1i64<<47)..(1i64<<47)),rng.gen_range(-60..0));
+		let (xf,yf,zf)=(f64::from(x),f64::from(y),f64::from(z));
+		assert!(ulp_error(x.hypot(y),libm::hypot(xf,yf))<0.52,"hypot({},{})",xf,yf);
+		assert!(ulp_error(x.hypot3(y,z),(xf*xf+yf*yf+zf*zf).sqrt())<0.52,"hypot3({},{},{})",xf,yf,zf);
+		// scaling by a power of 2 far outside the f64 range changes nothing else
+		assert_eq!(x.ldexp(30000).hypot(y.ldexp(30000)),x.hypot(y).ldexp(30000));
+		assert_eq!(x.ldexp(-30000).hypot3(y.ldexp(-30000),z.ldexp(-30000)),x.hypot3(y,z).ldexp(-30000));
+		assert_eq!(PseudoDouble::norm2(&[x,y,z]),x.hypot3(y,z));
+	}
+	assert_eq!(pd!(3).hypot(pd!(-4)),pd!(5));
+	assert_eq!(pd!(-2).hypot3(pd!(3),pd!(6)),pd!(7));
+	assert_eq!(PseudoDouble::norm2(&[pd!(1);16]),pd!(4));
+	assert_eq!(PseudoDouble::norm2(&[]),pd!(0));
+	assert_eq!(pd!(0).hypot(pd!(0)),pd!(0));
+	assert_eq!(pd!(1e-9000).hypot(pd!(0)),pd!(1e-9000));
+	assert_eq!(pd!(1).hypot(pd!(1e-9000)),pd!(1));
+	assert_eq!(PseudoDouble::MAX.hypot(PseudoDouble::MIN_POSITIVE),PseudoDouble::MAX);
+	assert_eq!(PseudoDouble::MIN_POSITIVE.hypot(PseudoDouble::MIN_POSITIVE),consts::SQRT_2.ldexp(-32770));
+	assert_eq!(pd!(1).hypot(pd!(1)),consts::SQRT_2);
+	assert_eq!(<PseudoDouble as simba::scalar::ComplexField>::hypot(pd!(5),pd!(12)),pd!(13));
+	if cfg!(feature="panic_on_pseudodouble_overflow") {
+		assert!(std::panic::catch_unwind(|| PseudoDouble::MAX.hypot(PseudoDouble::MAX)).is_err());
+	} else {
+		assert_eq!(PseudoDouble::MAX.hypot(PseudoDouble::MAX),PseudoDouble::MAX);
+	}
+}
+
+#[test]
+fn cbrt_root_tests() {
+	let mut rng=StdRng::seed_from_u64(249);
+	for _ in 0..100000 {
+		let x=PseudoDouble::pdc2(rng.gen_range(-(1i64<<47)..(1i64<<47)),rng.gen_range(-80..20));
+		let xa=if x<pd!(0) {-x} else {x};
+		let xf=f64::from(x);
+		assert!(ulp_error(x.cbrt(),xf.cbrt())<0.52,"cbrt({})",xf);
+		// powf(0.2) is off by a few f64 ulps for large x, so polish it with a Newton step
+		let r5=xf.abs().powf(0.2);
+		let r5=xf.signum()*(r5-(r5.powi(5)-xf.abs())/(5.0*r5.powi(4)));
+		assert!(ulp_error(x.root_n(5),r5)<0.52,"root_n({},5)",xf);
+		assert!(ulp_error(xa.root_n(4),xf.abs().sqrt().sqrt())<0.52,"root_n({},4)",xf);
+		assert!(ulp_error(xa.root_n(2),xf.abs().sqrt())<0.52,"root_n({},2)",xf);
+		assert_eq!((-x).cbrt(),-x.cbrt());
+		assert_eq!(x.ldexp(3*9000).cbrt(),x.cbrt().ldexp(9000));
+		assert_eq!(x.ldexp(-7*4000).root_n(7),x.root_n(7).ldexp(-4000));
+		// perfect cubes and fifth powers come back exactly
+		let m=rng.gen_range(-(1i64<<15)..(1i64<<15));
+		let c=PseudoDouble::from(m).ldexp(rng.gen_range(-1000..1000));
+		assert_eq!((c*c*c).cbrt(),c);
+		let d=PseudoDouble::from(m>>6).ldexp(rng.gen_range(-1000..1000));
+		assert_eq!((d*d*d*d*d).root_n(5),d);
+	}
+	assert_eq!(pd!(27).cbrt(),pd!(3));
+	assert_eq!(pd!(-0.125).cbrt(),pd!(-0.5));
+	assert_eq!(pd!(-32).root_n(5),pd!(-2));
+	assert_eq!(pd!(81).root_n(-4),pd!(1)/pd!(3));
+	assert_eq!(pd!(1024).root_n(10),pd!(2));
+	assert_eq!(pd!(2).root_n(1),pd!(2));
+	assert_eq!(pd!(0).cbrt(),pd!(0));
+	assert_eq!(pd!(0).root_n(6),pd!(0));
+	assert_eq!(PseudoDouble::MIN_POSITIVE.ldexp(1).cbrt(),pd!(1).ldexp(-10923));
+	assert!(ulp_error(PseudoDouble::MAX.cbrt().ldexp(-10922),f64::from(PseudoDouble::MAX.ldexp(-32766)).cbrt())<0.52);
+	assert_eq!(<PseudoDouble as num_traits::Float>::cbrt(pd!(-8)),pd!(-2));
+	assert!(std::panic::catch_unwind(|| pd!(-16).root_n(4)).is_err());
+	assert!(std::panic::catch_unwind(|| pd!(2).root_n(0)).is_err());
+}
+
+#[test]
+fn pow_tests() {
+	let mut rng=StdRng::seed_from_u64(250);
+	for _ in 0..100000 {
+		let x=PseudoDouble::pdc2(rng.gen_range(-(1i64<<47)..(1i64<<47)),rng.gen_range(-50..-40));
+		let n=rng.gen_range(-40..40);
+		let xf=f64::from(x);
+		// libm pow is good to an f64 ulp, so anything past half an ulp is a rounding error
+		assert!(ulp_error(x.powi(n),libm::pow(xf,n as f64))<0.52,"{}^{}",xf,n);
+		assert_eq!(x.pow_i64(n as i64),x.powi(n));
+		assert_eq!(x.powf(PseudoDouble::from(n)),x.powi(n));
+		assert_eq!((-x).powi(n),if n%2==0 {x.powi(n)} else {-x.powi(n)});
+		// integer powers that fit come back exactly
+		let m=rng.gen_range(-(1i64<<15)..(1i64<<15));
+		let k=rng.gen_range(-1000..1000);
+		let c=PseudoDouble::from(m).ldexp(k);
+		assert_eq!(c.pow_u64(3),PseudoDouble::from(m*m*m).ldexp(3*k));
+		assert_eq!(c.ldexp(-k).pow_u64(2),PseudoDouble::from(m*m));
+	}
+	assert_eq!(pd!(3).powi(2),pd!(9));
+	assert_eq!(pd!(-3).powi(3),pd!(-27));
+	assert_eq!(pd!(0.5).powi(-3),pd!(8));
+	assert_eq!(pd!(2).pow_i64(-32770),PseudoDouble::MIN_POSITIVE);
+	assert_eq!(pd!(2).pow_i64(-32771),pd!(0));
+	assert_eq!(pd!(-1).pow_u64(u64::MAX),pd!(-1));
+	assert_eq!(pd!(-1).pow_i64(i64::MIN),pd!(1));
+	assert_eq!(pd!(0).pow_u64(0),pd!(1));
+	assert_eq!(pd!(0).pow_u64(5),pd!(0));
+	// 3^30 needs 48 bits and rounds to even
+	assert_eq!(pd!(3).powi(30),pd!(205891132094648));
+	assert_eq!(pd!(3).powi(-1),pd!(1)/pd!(3));
+	assert!(ulp_error((pd!(1)+pd!(1).ldexp(-40)).pow_u64(1u64<<40),libm::exp(libm::log1p(ldexp(1.0,-40))*ldexp(1.0,40)))<0.52);
+	assert_eq!(pd!(1.5).powi(-2),pd!(4)/pd!(9));
+	// 1/2<=|x|<1 stops squaring once the result is far out of range
+	let y=PseudoDouble::pdc2((1i64<<47)-1,-47);
+	assert!(ulp_error(y.pow_u64(1u64<<50),libm::exp(-8.0-ldexp(1.0,-45)))<0.52);
+	assert!(ulp_error(y.pow_i64(-(1i64<<50)),libm::exp(8.0+ldexp(1.0,-45)))<0.52);
+	assert_eq!(y.pow_u64(1u64<<62),pd!(0));
+	assert_eq!(pd!(0.5).pow_u64(u64::MAX),pd!(0));
+	assert_eq!(pd!(-0.75).pow_u64(u64::MAX),pd!(0));
+	if cfg!(feature="panic_on_pseudodouble_overflow") {
+		assert!(std::panic::catch_unwind(|| pd!(0.5).pow_i64(i64::MIN)).is_err());
+		assert!(std::panic::catch_unwind(|| pd!(0.5).pow_i64(-i64::MAX)).is_err());
+	} else {
+		assert_eq!(pd!(0.5).pow_i64(i64::MIN),PseudoDouble::MAX);
+		assert_eq!(pd!(0.5).pow_i64(-i64::MAX),PseudoDouble::MAX);
+	}
+	assert_eq!(pd!(3).powf(pd!(2)),pd!(9));
+	assert_eq!(pd!(-2).powf(pd!(3)),pd!(-8));
+	assert_eq!(pd!(-0.5).powf(pd!(-5)),pd!(-32));
+	// anything past 2^63 is an even integer
+	assert_eq!(pd!(-1).powf(pd!(1).ldexp(100)),pd!(1));
+	assert_eq!(pd!(-0.5).powf(pd!(1).ldexp(100)),pd!(0));
+	assert_eq!(pd!(0).powf(pd!(2.5)),pd!(0));
+	assert_eq!(pd!(-8).try_powf(pd!(1)/pd!(3)),Err(PdDomainError{function:"powf"}));
+	if cfg!(feature="panic_on_pseudodouble_overflow") {
+		assert!(std::panic::catch_unwind(|| pd!(-8).powf(pd!(1)/pd!(3))).is_err());
+		assert!(std::panic::catch_unwind(|| pd!(0).powi(-1)).is_err());
+		assert!(std::panic::catch_unwind(|| pd!(2).pow_u64(32766)).is_err());
+	} else {
+		assert_eq!(pd!(-8).powf(pd!(1)/pd!(3)),pd!(0));
+		assert_eq!(pd!(0).powi(-1),PseudoDouble::MAX);
+		assert_eq!(pd!(2).pow_u64(32766),PseudoDouble::MAX);
+	}
+}